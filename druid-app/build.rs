@@ -0,0 +1,136 @@
+//! Convert the PNG and SVG files in `images/` to `ImageData` statics in LVGL's `lv_img_dsc_t` layout.
+//!
+//! Each `images/<name>.png` becomes `pub static <NAME>: ImageData` in `$OUT_DIR/images.rs`,
+//! which is included by `src/images.rs`. Palette images keep their palette and are stored
+//! as `Indexed1Bit` .. `Indexed8Bit`. Other images are stored as `TrueColor` (RGB565),
+//! or `TrueColorAlpha` when they have transparency.
+//!
+//! SVG icons are rasterized here, as there is no room to parse SVG on the watch.
+//! `images/<name>.svg` is rendered at the size declared in the SVG, and
+//! `images/<name>.<width>x<height>.svg` at the requested size, e.g. `sunny.48x48.svg`.
+//! They become `pub static <NAME>: SvgData`, stored as `TrueColorAlpha`.
+
+use std::{env, error::Error, fmt::Write as _, fs, fs::File, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=images");
+    let mut out = String::new();
+    let mut paths: Vec<_> = fs::read_dir("images")
+        .map(|dir| dir.filter_map(|entry| Some(entry.ok()?.path())).collect())
+        .unwrap_or_default();
+    paths.sort();
+    for path in &paths {
+        let converted = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => convert_png(path, &mut out),
+            Some("svg") => convert_svg(path, &mut out),
+            _ => continue,
+        };
+        println!("cargo:rerun-if-changed={}", path.display());
+        converted.unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    }
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("images.rs");
+    fs::write(out_path, out).expect("Failed to write images.rs");
+}
+
+/// Append the `ImageData` static for the PNG file at `path` to `out`.
+fn convert_png(path: &Path, out: &mut String) -> Result<(), Box<dyn Error>> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    let is_indexed = {
+        let (_, reader) = png::Decoder::new(File::open(path)?).read_info()?;
+        reader.info().color_type == png::ColorType::Indexed
+    };
+    // Keep palette indices as they are, as their packing matches LVGL's
+    decoder.set_transformations(if is_indexed {
+        png::Transformations::IDENTITY
+    } else {
+        png::Transformations::EXPAND | png::Transformations::STRIP_16
+    });
+    let (info, mut reader) = decoder.read_info()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut pixels)?;
+
+    let (format, data) = if is_indexed {
+        let bpp = info.bit_depth as usize;
+        let palette = reader.info().palette.clone().unwrap_or_default();
+        let alpha = reader.info().trns.clone().unwrap_or_default();
+        // Palette entries are B, G, R, A, padded to 2^bpp colours
+        let mut data = Vec::new();
+        for index in 0..1 << bpp {
+            let rgb = palette.get(index * 3..index * 3 + 3).unwrap_or(&[0, 0, 0]);
+            data.extend_from_slice(&[rgb[2], rgb[1], rgb[0], *alpha.get(index).unwrap_or(&0xff)]);
+        }
+        data.extend_from_slice(&pixels);
+        (format!("Indexed{}Bit", bpp), data)
+    } else {
+        // Transparency chunks are expanded into an alpha channel
+        let (color_type, _) = reader.output_color_type();
+        let channels = color_type.samples();
+        let has_alpha = color_type == png::ColorType::GrayscaleAlpha || color_type == png::ColorType::RGBA;
+        let rgba = pixels.chunks(channels).map(|pixel| match channels {
+            1 | 2 => [pixel[0], pixel[0], pixel[0], pixel[channels - 1]],
+            _ => [pixel[0], pixel[1], pixel[2], pixel[channels - 1]],
+        });
+        true_color(rgba, has_alpha)
+    };
+    write_image(out, path, "ImageData", &format, (info.width, info.height), &data)
+}
+
+/// Append the `SvgData` static for the SVG file at `path` to `out`.
+fn convert_svg(path: &Path, out: &mut String) -> Result<(), Box<dyn Error>> {
+    let tree = usvg::Tree::from_data(&fs::read(path)?, &usvg::Options::default().to_ref())?;
+    let size = tree.svg_node().size.to_screen_size();
+    let (width, height) = requested_size(path).unwrap_or((size.width(), size.height()));
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or("empty image")?;
+    resvg::render(&tree, usvg::FitTo::Size(width, height), tiny_skia::Transform::default(), pixmap.as_mut())
+        .ok_or("failed to render")?;
+    let rgba = pixmap.pixels().iter().map(|pixel| {
+        let pixel = pixel.demultiply();
+        [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+    });
+    let (format, data) = true_color(rgba, true);
+    write_image(out, path, "SvgData", &format, (width, height), &data)
+}
+
+/// The `<width>x<height>` at the end of a file name like `sunny.48x48.svg`.
+fn requested_size(path: &Path) -> Option<(u32, u32)> {
+    let (_, size) = path.file_stem()?.to_str()?.rsplit_once('.')?;
+    let (width, height) = size.split_once('x')?;
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+/// Encode RGBA pixels as RGB565, followed by the alpha byte if `has_alpha`.
+fn true_color(rgba: impl Iterator<Item = [u8; 4]>, has_alpha: bool) -> (String, Vec<u8>) {
+    let mut data = Vec::new();
+    for [r, g, b, a] in rgba {
+        let rgb565 = (r as u16 >> 3) << 11 | (g as u16 >> 2) << 5 | b as u16 >> 3;
+        data.extend_from_slice(&rgb565.to_le_bytes());
+        if has_alpha {
+            data.push(a);
+        }
+    }
+    (String::from(if has_alpha { "TrueColorAlpha" } else { "TrueColor" }), data)
+}
+
+/// Append `pub static <NAME>: <ty>` for the image converted from `path` to `out`.
+fn write_image(
+    out: &mut String,
+    path: &Path,
+    ty: &str,
+    format: &str,
+    (width, height): (u32, u32),
+    data: &[u8],
+) -> Result<(), Box<dyn Error>> {
+    if width > 255 || height > 255 {
+        return Err("images must fit in 255 x 255 pixels".into());
+    }
+    let stem = path.file_stem().unwrap().to_string_lossy();
+    let name = stem.split('.').next().unwrap().to_uppercase().replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+    writeln!(out, "/// Converted from `{}`", path.display())?;
+    writeln!(out, "pub static {}: {} = {}::new(ColorFormat::{}, {}, {}, &[", name, ty, ty, format, width, height)?;
+    for line in data.chunks(16) {
+        let bytes: Vec<_> = line.iter().map(|byte| format!("0x{:02x},", byte)).collect();
+        writeln!(out, "    {}", bytes.join(" "))?;
+    }
+    writeln!(out, "]);")?;
+    Ok(())
+}
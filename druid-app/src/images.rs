@@ -0,0 +1,9 @@
+//! Images and icons converted from `images/*.png` and `images/*.svg` by `build.rs`.
+//!
+//! Weather icons: `SUNNY`, `CLOUDY`, `RAIN`. Notification icons: `CALL`, `MESSAGE`.
+
+#![allow(dead_code)]
+
+use druid::widget::{ColorFormat, ImageData, SvgData};
+
+include!(concat!(env!("OUT_DIR"), "/images.rs"));
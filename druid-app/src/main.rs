@@ -1,8 +1,13 @@
 use druid::{AppLauncher, WindowDesc, Widget, PlatformError};
-use druid::widget::Label;
+use druid::widget::{Flex, Image, Label, Svg};
+
+mod images;
 
 fn build_ui() -> impl Widget<()> + Clone {
-    Label::new("Hello world")
+    Flex::column()
+        .with_child(Image::new(images::HEART))
+        .with_child(Svg::new(images::SUNNY))
+        .with_child(Label::new("Hello world"))
 }
 
 fn main() -> Result<(), PlatformError> {
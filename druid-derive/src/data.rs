@@ -191,7 +191,12 @@ fn generics_bounds(generics: &syn::Generics) -> proc_macro2::TokenStream {
                 }
             }
             Lifetime(lf) => quote!(#lf),
-            Const(cst) => quote!(#cst),
+            Const(cst) => {
+                // defaults are not allowed on impl generics, so drop them
+                let ident = &cst.ident;
+                let ty = &cst.ty;
+                quote!(const #ident: #ty)
+            }
         }
     });
 
@@ -14,6 +14,8 @@
 
 //! A simple Bloom filter, used to track child widgets.
 
+use ::core::hash::{Hash, Hasher}; ////
+use ::core::marker::PhantomData; ////
 ////use std::hash::{Hash, Hasher};
 ////use std::marker::PhantomData;
 
@@ -111,8 +113,10 @@ impl<T: ?Sized + Hash> Bloom<T> {
     }
 }
 
-impl<T: ?Sized> std::fmt::Debug for Bloom<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<T: ?Sized> ::core::fmt::Debug for Bloom<T> { ////
+////impl<T: ?Sized> std::fmt::Debug for Bloom<T> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result { ////
+    ////fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "Bloom: {:064b}: ({})", self.bits, self.entry_count)
     }
 }
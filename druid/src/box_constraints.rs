@@ -164,15 +164,15 @@ impl BoxConstraints {
     pub fn shrink(&self, diff: impl Into<Size>) -> BoxConstraints {
         let diff = diff.into().expand();
         let min = Size::new(
-            (self.min().width - diff.width).max(0), ////
+            self.min().width.saturating_sub(diff.width), ////
             ////(self.min().width - diff.width).max(0.),
-            (self.min().height - diff.height).max(0), ////
+            self.min().height.saturating_sub(diff.height), ////
             ////(self.min().height - diff.height).max(0.),
         );
         let max = Size::new(
-            (self.max().width - diff.width).max(0), ////
+            self.max().width.saturating_sub(diff.width), ////
             ////(self.max().width - diff.width).max(0.),
-            (self.max().height - diff.height).max(0), ////
+            self.max().height.saturating_sub(diff.height), ////
             ////(self.max().height - diff.height).max(0.),
         );
 
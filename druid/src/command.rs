@@ -20,7 +20,10 @@
     ////sync::{Arc, Mutex},
 ////};
 
-use crate::{WidgetId, WindowId};
+use core::marker::PhantomData; ////
+
+use crate::{Rect, WidgetId, WindowId}; ////
+////use crate::{WidgetId, WindowId};
 
 /// The identity of a [`Selector`].
 ///
@@ -36,13 +39,13 @@ pub(crate) type SelectorSymbol = &'static str;
 /// The type parameter `T` specifies the command's payload type.
 /// See [`Command`] for more information.
 ///
-/// Certain `Selector`s are defined by druid, and have special meaning
-/// to the framework; these are listed in the [`druid::commands`] module.
-///
+////Certain `Selector`s are defined by druid, and have special meaning
+////to the framework; these are listed in the [`druid::commands`] module.
+////
 /// [`Command`]: struct.Command.html
 /// [`Command::get`]: struct.Command.html#method.get
 /// [`get_unchecked`]: struct.Command.html#method.get_unchecked
-/// [`druid::commands`]: commands/index.html
+////[`druid::commands`]: commands/index.html
 #[derive(Debug, PartialEq, Eq)]
 pub struct Selector<T = ()>(SelectorSymbol, PhantomData<*const T>);
 
@@ -51,35 +54,58 @@ pub struct Selector<T = ()>(SelectorSymbol, PhantomData<*const T>);
 /// A `Command` consists of a [`Selector`], that indicates what the command is
 /// and what type of payload it carries, as well as the actual payload.
 ///
-/// If the payload can't or shouldn't be cloned,
-/// wrapping it with [`SingleUse`] allows you to `take` the payload.
-/// The [`SingleUse`] docs give an example on how to do this.
-///
-/// Generic payloads can be achieved with `Selector<Box<dyn Any>>`.
-/// In this case it could make sense to use utility functions to construct
-/// such commands in order to maintain as much static typing as possible.
-/// The [`EventCtx::new_window`] method is an example of this.
+////If the payload can't or shouldn't be cloned,
+////wrapping it with [`SingleUse`] allows you to `take` the payload.
+////The [`SingleUse`] docs give an example on how to do this.
+////
+/// Without dynamic memory the payload can't be an `Arc<dyn Any>`, so it is ////
+/// stored by value as a [`PayloadValue`]: only the types that implement
+/// [`Payload`] may be carried by a command.
 ///
 /// # Examples
 /// ```
-/// use druid::{Command, Selector};
+/// use druid::{Command, Rect, Selector};
 ///
-/// let selector = Selector::new("process_rows");
-/// let rows = vec![1, 3, 10, 12];
-/// let command = Command::new(selector, rows);
+/// let selector = Selector::new("show_rect");
+/// let rect = Rect::new(0, 10, 20, 30);
+/// let command = Command::new(selector, rect);
 ///
-/// assert_eq!(command.get(selector), Some(&vec![1, 3, 10, 12]));
+/// assert_eq!(command.get(selector), Some(&Rect::new(0, 10, 20, 30)));
 /// ```
 ///
-/// [`EventCtx::new_window`]: struct.EventCtx.html#method.new_window
-/// [`SingleUse`]: struct.SingleUse.html
+////[`SingleUse`]: struct.SingleUse.html
 /// [`Selector`]: struct.Selector.html
+/// [`Payload`]: trait.Payload.html
+/// [`PayloadValue`]: enum.PayloadValue.html
 #[derive(Debug, Clone)]
 pub struct Command {
     symbol: SelectorSymbol,
-    payload: Arc<dyn Any>,
+    payload: PayloadValue, ////
+    ////payload: Arc<dyn Any>,
 }
 
+/// The payload of a [`Command`], stored without allocating. ////
+///
+/// [`Command`]: struct.Command.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PayloadValue {
+    /// The payload of a `Selector<()>`.
+    Unit,
+    /// A rectangle, e.g. the region to scroll into view.
+    Rect(Rect),
+}
+
+/// A type that can be carried by a [`Command`]. ////
+///
+/// [`Command`]: struct.Command.html
+pub trait Payload: Copy + 'static {
+    /// Wrap the payload for storing in a `Command`.
+    fn into_value(self) -> PayloadValue;
+    /// Unwrap the payload, if it has this type.
+    fn from_value(value: &PayloadValue) -> Option<&Self>;
+}
+
+/* //// No `Mutex` to share a single-use payload
 /// A wrapper type for [`Command`] payloads that should only be used once.
 ///
 /// This is useful if you have some resource that cannot be
@@ -107,6 +133,7 @@ pub struct Command {
 ///
 /// [`Command`]: struct.Command.html
 pub struct SingleUse<T>(Mutex<Option<T>>);
+*/ ////
 
 /// The target of a command.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -120,6 +147,7 @@ pub enum Target {
     Widget(WidgetId),
 }
 
+/* //// No platform commands yet
 /// Commands with special meaning, defined by druid.
 ///
 /// See [`Command`] for more info.
@@ -243,6 +271,7 @@ pub mod sys {
     /// Redo.
     pub const REDO: Selector = Selector::new("druid-builtin.menu-redo");
 }
+*/ ////
 
 impl Selector<()> {
     /// A selector that does nothing.
@@ -261,7 +290,8 @@ impl<T> Selector<T> {
     }
 }
 
-impl<T: Any> Selector<T> {
+impl<T: Payload> Selector<T> { ////
+////impl<T: Any> Selector<T> {
     /// Convenience method for [`Command::new`] with this selector.
     ///
     /// If the payload is `()` there is no need to call this,
@@ -282,13 +312,16 @@ impl Command {
     ///
     /// [`Selector`]: struct.Selector.html
     /// [`Selector::with`]: struct.Selector.html#method.with
-    pub fn new<T: Any>(selector: Selector<T>, payload: T) -> Self {
+    pub fn new<T: Payload>(selector: Selector<T>, payload: T) -> Self { ////
+    ////pub fn new<T: Any>(selector: Selector<T>, payload: T) -> Self {
         Command {
             symbol: selector.symbol(),
-            payload: Arc::new(payload),
+            payload: payload.into_value(), ////
+            ////payload: Arc::new(payload),
         }
     }
 
+    /* //// No `ExtEventSink` yet
     /// Used to create a command from the types sent via an `ExtEventSink`.
     pub(crate) fn from_ext(symbol: SelectorSymbol, payload: Box<dyn Any>) -> Self {
         Command {
@@ -296,6 +329,7 @@ impl Command {
             payload: payload.into(),
         }
     }
+    */ ////
 
     /// Returns `true` if `self` matches this `selector`.
     pub fn is<T>(&self, selector: Selector<T>) -> bool {
@@ -315,9 +349,11 @@ impl Command {
     ///
     /// [`is`]: #method.is
     /// [`get_unchecked`]: #method.get_unchecked
-    pub fn get<T: Any>(&self, selector: Selector<T>) -> Option<&T> {
+    pub fn get<T: Payload>(&self, selector: Selector<T>) -> Option<&T> { ////
+    ////pub fn get<T: Any>(&self, selector: Selector<T>) -> Option<&T> {
         if self.symbol == selector.symbol() {
-            Some(self.payload.downcast_ref().unwrap_or_else(|| {
+            Some(T::from_value(&self.payload).unwrap_or_else(|| { ////
+            ////Some(self.payload.downcast_ref().unwrap_or_else(|| {
                 panic!(
                     "The selector \"{}\" exists twice with different types. See druid::Command::get for more information",
                     selector.symbol()
@@ -342,7 +378,8 @@ impl Command {
     ///
     /// [`is`]: #method.is
     /// [`get`]: #method.get
-    pub fn get_unchecked<T: Any>(&self, selector: Selector<T>) -> &T {
+    pub fn get_unchecked<T: Payload>(&self, selector: Selector<T>) -> &T { ////
+    ////pub fn get_unchecked<T: Any>(&self, selector: Selector<T>) -> &T {
         self.get(selector).unwrap_or_else(|| {
             panic!(
                 "Expected selector \"{}\" but the command was \"{}\".",
@@ -353,6 +390,7 @@ impl Command {
     }
 }
 
+/* ////
 impl<T: Any> SingleUse<T> {
     pub fn new(data: T) -> Self {
         SingleUse(Mutex::new(Some(data)))
@@ -363,19 +401,48 @@ impl<T: Any> SingleUse<T> {
         self.0.lock().unwrap().take()
     }
 }
+*/ ////
+
+impl Payload for () { ////
+    fn into_value(self) -> PayloadValue {
+        PayloadValue::Unit
+    }
+    fn from_value(value: &PayloadValue) -> Option<&Self> {
+        match value {
+            PayloadValue::Unit => Some(&()),
+            _ => None,
+        }
+    }
+}
+
+impl Payload for Rect { ////
+    fn into_value(self) -> PayloadValue {
+        PayloadValue::Rect(self)
+    }
+    fn from_value(value: &PayloadValue) -> Option<&Self> {
+        match value {
+            PayloadValue::Rect(rect) => Some(rect),
+            _ => None,
+        }
+    }
+}
 
 impl From<Selector> for Command {
     fn from(selector: Selector) -> Command {
         Command {
             symbol: selector.symbol(),
-            payload: Arc::new(()),
+            payload: PayloadValue::Unit, ////
+            ////payload: Arc::new(()),
         }
     }
 }
 
-impl<T> std::fmt::Display for Selector<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "Selector(\"{}\", {})", self.0, any::type_name::<T>())
+impl<T> core::fmt::Display for Selector<T> { ////
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result { ////
+    ////impl<T> std::fmt::Display for Selector<T> {
+        ////fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Selector(\"{}\", {})", self.0, core::any::type_name::<T>()) ////
+        ////write!(f, "Selector(\"{}\", {})", self.0, any::type_name::<T>())
     }
 }
 
@@ -419,8 +486,19 @@ mod tests {
     #[test]
     fn get_payload() {
         let sel = Selector::new("my-selector");
-        let payload = vec![0, 1, 2];
+        let payload = Rect::new(0, 1, 2, 3); ////
+        ////let payload = vec![0, 1, 2];
         let command = Command::new(sel, payload);
-        assert_eq!(command.get(sel), Some(&vec![0, 1, 2]));
+        assert_eq!(command.get(sel), Some(&Rect::new(0, 1, 2, 3))); ////
+        ////assert_eq!(command.get(sel), Some(&vec![0, 1, 2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "exists twice with different types")]
+    fn payload_of_another_type() { ////
+        let sel: Selector<Rect> = Selector::new("my-selector");
+        let command: Command = Selector::new("my-selector").into();
+        assert!(command.is(sel));
+        command.get(sel);
     }
 }
@@ -16,7 +16,7 @@
 
 ////use std::collections::{HashMap, VecDeque};
 
-////use crate::bloom::Bloom;
+use crate::bloom::Bloom; ////
 ////use crate::contexts::ContextState;
 use crate::{Affine, Insets, Point, Rect, Size, Vec2}; ////
 ////use crate::kurbo::{Affine, Insets, Point, Rect, Shape, Size, Vec2};
@@ -29,7 +29,8 @@ use crate::{
     LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Region, Target, TimerToken, UpdateCtx, Widget,
     WidgetId,
 };
-use crate::{Bloom, BoxedWidget, ContextState, HashMap, PietTextLayout, ScreenCoord, Vec, VecDeque}; ////
+use crate::{BoxedWidget, ContextState, PietTextLayout, ScreenCoord, Vec, VecDeque}; ////
+use core::time::Duration; ////
 
 /// Our queue type
 pub(crate) type CommandQueue = VecDeque<(Target, Command)>;
@@ -108,6 +109,9 @@ pub(crate) struct WidgetState {
     /// Descendants of the focused widget are not in the focused path.
     pub(crate) has_focus: bool,
 
+    /// This widget itself has the focus.
+    pub(crate) is_focused: bool, ////
+
     /// Any descendant has requested an animation frame.
     pub(crate) request_anim: bool,
 
@@ -119,7 +123,17 @@ pub(crate) struct WidgetState {
     pub(crate) children: Bloom<WidgetId>,
     pub(crate) children_changed: bool,
     /// Associate timers with widgets that requested them.
-    pub(crate) timers: HashMap<TimerToken, WidgetId>,
+    pub(crate) timers: Vec<Timer>, ////
+    ////pub(crate) timers: HashMap<TimerToken, WidgetId>,
+}
+
+/// A timer requested by a widget, counting down to its deadline. ////
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Timer {
+    pub(crate) token: TimerToken,
+    pub(crate) widget: WidgetId,
+    /// The time left until the timer fires.
+    pub(crate) deadline: Duration,
 }
 
 /// Methods by which a widget can attempt to change focus state.
@@ -179,6 +193,20 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
         self.state.is_hot
     }
 
+    /// Returns `true` if the widget has received [`LifeCycle::WidgetAdded`]. ////
+    ///
+    /// [`LifeCycle::WidgetAdded`]: enum.LifeCycle.html#variant.WidgetAdded
+    pub fn is_initialized(&self) -> bool {
+        self.old_data.is_some()
+    }
+
+    /// Call `update` on the widget even though the data hasn't changed, e.g. ////
+    /// after changing static state that the widget reads.
+    #[cfg(test)]
+    pub(crate) fn request_update(&mut self) {
+        self.state.request_update = true;
+    }
+
     /// Return a reference to the inner widget.
     pub fn widget(&self) -> &W {
         &self.inner
@@ -227,8 +255,7 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
         if WidgetPod::set_hot_state(
             &mut self.inner,
-            self.state.clone(), ////TODO
-            ////&mut self.state,
+            &mut self.state,
             ctx.state,
             layout_rect,
             ctx.mouse_pos,
@@ -336,8 +363,7 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
     /// The provided `child_state` should be merged up if this returns `true`.
     fn set_hot_state(
         child: &mut W,
-        child_state: WidgetState, ////
-        ////child_state: &mut WidgetState,
+        child_state: &mut WidgetState,
         state: ContextState, ////
         ////state: &mut ContextState,
         rect: Rect,
@@ -345,7 +371,6 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
         data: &T,
         env: &Env,
     ) -> bool {
-        /* ////
         let had_hot = child_state.is_hot;
         child_state.is_hot = match mouse_pos {
             Some(pos) => rect.winding(pos) != 0,
@@ -355,9 +380,11 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
             let hot_changed_event = LifeCycle::HotChanged(child_state.is_hot);
             let mut child_ctx = LifeCycleCtx {
                 state,
-                widget_state: child_state,
+                widget_state: child_state.clone(), ////TODO
+                ////widget_state: child_state,
             };
             child.lifecycle(&mut child_ctx, &hot_changed_event, data, env);
+            *child_state = child_ctx.widget_state; ////TODO
             /* ////
             // if hot changes and we're showing widget ids, always repaint
             if env.get(Env::DEBUG_WIDGET_ID) {
@@ -366,7 +393,6 @@ impl<T: Clone, W: Widget<T> + Clone> WidgetPod<T, W> { ////
             */ ////
             return true;
         }
-        */ ////
         false
     }
 }
@@ -402,6 +428,9 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
             depth: ctx.depth,
         };
         self.inner.paint(&mut inner_ctx, data, env);
+        if inner_ctx.is_focused() { ////
+            self.inner.paint_focus_ring(&mut inner_ctx, data, env); ////
+        } ////
 
         /* ////
         let debug_ids = inner_ctx.is_hot() && env.get(Env::DEBUG_WIDGET_ID);
@@ -441,7 +470,8 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
         ctx.with_save(|ctx| {
             let layout_origin = self.layout_rect().origin().to_vec2();
-            ////ctx.transform(Affine::translate(layout_origin)); ////TODO
+            ctx.render_ctx.translate(layout_origin); ////
+            ////ctx.transform(Affine::translate(layout_origin));
             let visible = ctx.region().to_rect().intersect(self.state.paint_rect()) - layout_origin;
             ctx.with_child_ctx(visible, |ctx| self.paint_raw(ctx, data, env));
         });
@@ -531,6 +561,8 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
             mouse_pos: child_mouse_pos,
         };
         let size = self.inner.layout(&mut child_ctx, bc, data, env);
+        // The context has a copy of our state, so keep the insets the widget set ////
+        self.state.paint_insets = child_ctx.widget_state.paint_insets; ////
 
         ctx.widget_state.merge_up(&mut child_ctx.widget_state);
 
@@ -556,7 +588,6 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
     ///
     /// [`event`]: trait.Widget.html#tymethod.event
     pub fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        /*  ////TODO
         if self.old_data.is_none() {
             log::error!(
                 "widget {:?} is receiving an event without having first \
@@ -722,6 +753,7 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
             }
             Event::KeyDown(_) => self.state.has_focus,
             Event::KeyUp(_) => self.state.has_focus,
+            Event::Button(_) => self.state.has_focus, ////
             Event::Paste(_) => self.state.has_focus,
             Event::Zoom(_) => had_active || self.state.is_hot,
             Event::Timer(_) => false, // This event was targeted only to our parent
@@ -730,9 +762,10 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
         if recurse {
             let mut inner_ctx = EventCtx {
-                cursor: ctx.cursor,
+                ////cursor: ctx.cursor,
                 state: ctx.state,
-                widget_state: &mut self.state,
+                widget_state: self.state.clone(), ////TODO
+                ////widget_state: &mut self.state,
                 is_handled: false,
                 is_root: false,
             };
@@ -743,13 +776,13 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
             inner_ctx.widget_state.has_active |= inner_ctx.widget_state.is_active;
             ctx.is_handled |= inner_ctx.is_handled;
+            self.state = inner_ctx.widget_state; ////TODO
         }
 
         // Always merge even if not needed, because merging is idempotent and gives us simpler code.
         // Doing this conditionally only makes sense when there's a measurable performance boost.
         ctx.widget_state.merge_up(&mut self.state);
 
-        */ ////TODO
     }
 
     pub fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
@@ -770,7 +803,9 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
                     } else {
                         if self.state.children_changed {
                             self.state.children.clear();
-                            self.state.focus_chain.clear();
+                            // heapless 0.5 `clear` indexes past the end when dropping the items
+                            self.state.focus_chain = Vec::new(); ////
+                            ////self.state.focus_chain.clear();
                         }
                         self.state.children_changed
                     }
@@ -786,6 +821,7 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
                     if let Some(change) = this_changed {
                         self.state.has_focus = change;
+                        self.state.is_focused = change; ////
                         extra_event = Some(LifeCycle::FocusChanged(change));
                     } else {
                         self.state.has_focus = false;
@@ -799,7 +835,6 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
                         _ => false,
                     }
                 }
-                /* ////
                 #[cfg(test)]
                 InternalLifeCycle::DebugRequestState { widget, state_cell } => {
                     if *widget == self.id() {
@@ -816,7 +851,6 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
                     f.call(&self.state);
                     true
                 }
-                */ ////
             },
             LifeCycle::AnimFrame(_) => {
                 let r = self.state.request_anim;
@@ -831,6 +865,8 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
                 true
             }
+            // The whole subtree goes, shown or not
+            LifeCycle::WidgetRemoved => true, ////
             LifeCycle::Size(_) => {
                 // We are a descendant of a widget that received the Size event.
                 // This event was meant only for our parent, so don't recurse.
@@ -858,6 +894,7 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
         if let Some(event) = extra_event.as_ref() {
             self.inner.lifecycle(&mut child_ctx, event, data, env);
         }
+        self.state = child_ctx.widget_state; ////TODO
 
         ctx.widget_state.merge_up(&mut self.state);
 
@@ -867,9 +904,18 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
                 self.state.children_changed = false;
                 ctx.widget_state.children = ctx.widget_state.children.union(self.state.children.clone()); ////TODO
                 ////ctx.widget_state.children = ctx.widget_state.children.union(self.state.children);
-                ctx.widget_state.focus_chain.extend(&self.state.focus_chain);
+                ////ctx.widget_state.focus_chain.extend(&self.state.focus_chain);
+                for id in self.state.focus_chain.iter() { ////
+                    if ctx.widget_state.focus_chain.push(*id).is_err() { ////
+                        log::warn!("focus chain is full, widget {:?} can't take focus", id); ////
+                    } ////
+                } ////
                 ctx.register_child(self.id());
             }
+            LifeCycle::WidgetRemoved => { ////
+                self.old_data = None;
+                self.state.id.free();
+            }
             _ => (),
         }
     }
@@ -902,6 +948,7 @@ impl<T: Data, W: Widget<T> + Clone> WidgetPod<T, W> { ////
 
         self.inner
             .update(&mut child_ctx, self.old_data.as_ref().unwrap(), data, env);
+        self.state = child_ctx.widget_state; ////TODO
         self.old_data = Some(data.clone());
         self.env = Some(env.clone());
 
@@ -936,18 +983,28 @@ impl WidgetState {
             is_active: false,
             has_active: false,
             has_focus: false,
+            is_focused: false, ////
             request_anim: false,
             request_update: false,
             request_focus: None,
             focus_chain: Vec::new(),
             children: Bloom::new(),
             children_changed: false,
-            timers: HashMap::new(),
+            timers: Vec::new(), ////
+            ////timers: HashMap::new(),
         }
     }
 
-    pub(crate) fn add_timer(&mut self, timer_token: TimerToken) {
-        ////self.timers.insert(timer_token, self.id); ////TODO
+    pub(crate) fn add_timer(&mut self, timer_token: TimerToken, deadline: Duration) { ////
+    ////pub(crate) fn add_timer(&mut self, timer_token: TimerToken) {
+        self.push_timer(Timer { token: timer_token, widget: self.id, deadline }); ////
+        ////self.timers.insert(timer_token, self.id);
+    }
+
+    fn push_timer(&mut self, timer: Timer) { ////
+        if self.timers.push(timer).is_err() {
+            log::warn!("too many timers, {:?} of {:?} won't fire", timer.token, timer.widget);
+        }
     }
 
     /// Update to incorporate state changes from a child.
@@ -957,7 +1014,9 @@ impl WidgetState {
     /// This method is idempotent and can be called multiple times.
     fn merge_up(&mut self, child_state: &mut WidgetState) {
         let mut child_region = child_state.invalid.clone();
-        child_region += child_state.layout_rect().origin().to_vec2() - child_state.viewport_offset;
+        child_region += child_state.layout_rect().origin().to_vec2(); ////
+        child_region -= child_state.viewport_offset; ////
+        ////child_region += child_state.layout_rect().origin().to_vec2() - child_state.viewport_offset;
         let clip = self
             .layout_rect()
             .with_origin(Point::ORIGIN)
@@ -972,7 +1031,12 @@ impl WidgetState {
         self.children_changed |= child_state.children_changed;
         self.request_update |= child_state.request_update;
         self.request_focus = child_state.request_focus.take().or(self.request_focus);
-        ////self.timers.extend_drain(&mut child_state.timers); ////TODO
+        for timer in child_state.timers.iter() { ////
+            self.push_timer(*timer);
+        } ////
+        // heapless 0.5 `clear` indexes past the end when dropping the items
+        child_state.timers = Vec::new(); ////
+        ////self.timers.extend_drain(&mut child_state.timers);
     }
 
     #[inline]
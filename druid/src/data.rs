@@ -22,7 +22,7 @@
 ////use crate::shell::Scale;
 
 pub use druid_derive::Data;
-use crate::String; ////
+use heapless::ArrayLength; ////
 
 /// A trait used to represent value types.
 ///
@@ -78,6 +78,10 @@ use crate::String; ////
 /// If the `im` feature is used, the `im` crate is reexported from the root
 /// of the druid crate.
 ///
+/// On embedded platforms, `Data` is implemented for the fixed-capacity
+/// [`heapless::Vec`] and [`heapless::String`], and for arrays of any length.
+/// These are compared element by element, so keep them short.
+///
 /// ### Example:
 ///
 /// ```
@@ -104,6 +108,8 @@ use crate::String; ////
 ///
 /// [`Data::same`]: trait.Data.html#tymethod.same
 /// [`im` crate]: https://docs.rs/im
+/// [`heapless::Vec`]: https://docs.rs/heapless/0.5.5/heapless/struct.Vec.html
+/// [`heapless::String`]: https://docs.rs/heapless/0.5.5/heapless/struct.String.html
 pub trait Data: Clone + 'static {
     //// ANCHOR: same_fn
     /// Determine whether two values are the same.
@@ -146,9 +152,8 @@ impl_data_simple!(u64);
 impl_data_simple!(usize);
 impl_data_simple!(char);
 impl_data_simple!(bool);
-impl_data_simple!(String);
+////impl_data_simple!(String);
 
-/* ////
 impl Data for f32 {
     fn same(&self, other: &Self) -> bool {
         self.to_bits() == other.to_bits()
@@ -161,6 +166,7 @@ impl Data for f64 {
     }
 }
 
+/* ////
 impl<T: ?Sized + 'static> Data for Arc<T> {
     fn same(&self, other: &Self) -> bool {
         Arc::ptr_eq(self, other)
@@ -454,23 +460,41 @@ impl<T: Data> Data for im::OrdSet<T> {
     }
 }
 
-macro_rules! impl_data_for_array {
-    () => {};
-    ($this:tt $($rest:tt)*) => {
-        impl<T: Data> Data for [T; $this] {
-            fn same(&self, other: &Self) -> bool {
-                self.iter().zip(other.iter()).all(|(a, b)| a.same(b))
-            }
-        }
-        impl_data_for_array!($($rest)*);
+impl<N: ArrayLength<u8> + 'static> Data for heapless::String<N> { ////
+    fn same(&self, other: &Self) -> bool {
+        self == other
     }
 }
 
-impl_data_for_array! { 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 }
+impl<T: Data, N: ArrayLength<T> + 'static> Data for heapless::Vec<T, N> { ////
+    fn same(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().zip(other.iter()).all(|(a, b)| a.same(b))
+    }
+}
+
+impl<T: Data, const N: usize> Data for [T; N] { ////
+    fn same(&self, other: &Self) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.same(b))
+    }
+}
+////macro_rules! impl_data_for_array {
+    ////() => {};
+    ////($this:tt $($rest:tt)*) => {
+        ////impl<T: Data> Data for [T; $this] {
+            ////fn same(&self, other: &Self) -> bool {
+                ////self.iter().zip(other.iter()).all(|(a, b)| a.same(b))
+            ////}
+        ////}
+        ////impl_data_for_array!($($rest)*);
+    ////}
+////}
+
+////impl_data_for_array! { 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 }
 
 #[cfg(test)]
 mod test {
     use super::Data;
+    use heapless::consts::{U4, U8};
 
     #[test]
     fn array_data() {
@@ -479,6 +503,71 @@ mod test {
         assert!(!input.same(&[1u8, 1, 0, 1, 0]));
     }
 
+    #[test]
+    fn long_array_data() {
+        let mut input = [0u16; 64];
+        assert!(input.same(&[0u16; 64]));
+        input[63] = 1;
+        assert!(!input.same(&[0u16; 64]));
+    }
+
+    #[test]
+    fn heapless_string_data() {
+        let hello: heapless::String<U8> = heapless::String::from("hello");
+        assert!(hello.same(&heapless::String::from("hello")));
+        assert!(!hello.same(&heapless::String::from("hell")));
+        assert!(!hello.same(&heapless::String::from("jello")));
+    }
+
+    #[test]
+    fn heapless_vec_data() {
+        let mut one: heapless::Vec<u8, U4> = heapless::Vec::new();
+        one.extend_from_slice(&[1, 2, 3]).unwrap();
+        let mut two = one.clone();
+        assert!(one.same(&two));
+        two.pop();
+        assert!(!one.same(&two));
+        two.push(4).unwrap();
+        assert!(!one.same(&two));
+    }
+
+    #[test]
+    fn derive_with_heapless_fields() {
+        #[derive(Clone, Data)]
+        struct Notification {
+            title: heapless::String<U8>,
+            lines: heapless::Vec<heapless::String<U8>, U4>,
+            unread: [bool; 20],
+        }
+
+        let mut lines = heapless::Vec::new();
+        lines.push(heapless::String::from("hi")).unwrap();
+        let one = Notification {
+            title: heapless::String::from("chat"),
+            lines,
+            unread: [false; 20],
+        };
+        let mut two = one.clone();
+        assert!(one.same(&two));
+        two.lines[0].push('!').unwrap();
+        assert!(!one.same(&two));
+        let mut three = one.clone();
+        three.unread[19] = true;
+        assert!(!one.same(&three));
+    }
+
+    #[test]
+    fn derive_with_const_generic_array() {
+        #[derive(Clone, Data)]
+        struct Samples<const N: usize> {
+            values: [u8; N],
+        }
+
+        let one = Samples { values: [7u8; 32] };
+        assert!(one.same(&one.clone()));
+        assert!(!one.same(&Samples { values: [8u8; 32] }));
+    }
+
     #[test]
     #[cfg(feature = "im")]
     fn im_data() {
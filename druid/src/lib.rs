@@ -95,7 +95,7 @@
 //!
 //! * `im` - Efficient immutable data structures using the [`im` crate],
 //!          which is made available via the [`im` module].
-//! * `svg` - Scalable Vector Graphics for icons and other scalable images using the [`usvg` crate].
+//! * `svg` - Not supported on the watch. SVG icons are rasterized at build time instead, see [`widget::Svg`](widget/struct.Svg.html).
 //! * `image` - Bitmap image support using the [`image` crate].
 //! * `x11` - Work-in-progress X11 Linux backend instead of GTK.
 //!
@@ -129,8 +129,9 @@
 
 ////Begin
 use ::core::fmt;
-use ::core::ops::{Add, AddAssign, Mul, Sub};
+use ::core::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 use ::core::convert::From;
+use ::core::sync::atomic::{AtomicU16, AtomicU32, Ordering};
 
 type StringLength = heapless::consts::U20; //// Max length of strings
 type String = heapless::String::<StringLength>; //// Alias for standard String
@@ -176,7 +177,29 @@ impl Sub<Vec2> for Point {
 
     #[inline]
     fn sub(self, other: Vec2) -> Self {
-        Point::new(self.x - other.x, self.y - other.y)
+        Point::new(self.x.saturating_sub(other.x), self.y.saturating_sub(other.y)) ////
+        ////Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+impl SubAssign<Vec2> for Point {
+    #[inline]
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    #[inline]
+    fn add(self, other: Vec2) -> Self {
+        Point::new(self.x.saturating_add(other.x), self.y.saturating_add(other.y)) ////
+        ////Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+impl AddAssign<Vec2> for Point {
+    #[inline]
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
     }
 }
 
@@ -190,7 +213,7 @@ pub struct Size { ////
 }
 impl Size {
     pub const ZERO: Size = Size{ width: 0, height: 0 };
-    pub fn new(width: ScreenCoord, height: ScreenCoord) -> Self { Size{ width, height } }
+    pub const fn new(width: ScreenCoord, height: ScreenCoord) -> Self { Size{ width, height } }
     /// Returns a new `Size`,
     /// with `width` and `height` rounded away from zero to the nearest integer,
     /// unless they are already an integer.
@@ -224,6 +247,13 @@ impl Size {
         let height = self.height.max(min.height).min(max.height);
         Size { width, height }
     }
+    /// Convert this size into a [`Rect`] with origin `(0, 0)`.
+    ///
+    /// [`Rect`]: struct.Rect.html
+    #[inline]
+    pub const fn to_rect(self) -> Rect {
+        Rect { x0: 0, y0: 0, x1: self.width, y1: self.height }
+    }
 }
 impl From<(ScreenFactor, ScreenFactor)> for Size {
     fn from((x, y): (ScreenFactor, ScreenFactor)) -> Self {
@@ -261,6 +291,11 @@ pub struct Vec2 { ////
 }
 impl Vec2 {
     pub const ZERO: Vec2 = Vec2{ x: 0, y: 0 };
+    /// Create a new vector.
+    #[inline]
+    pub const fn new(x: ScreenCoord, y: ScreenCoord) -> Vec2 {
+        Vec2 { x, y }
+    }
 }
 impl Sub for Vec2 {
     type Output = Vec2;
@@ -303,7 +338,8 @@ impl Rect {
     pub fn from_points(p0: impl Into<Point>, p1: impl Into<Point>) -> Rect {
         let p0 = p0.into();
         let p1 = p1.into();
-        Rect::new(p0.x, p0.y, p1.x, p1.y)
+        Rect::new(p0.x.min(p1.x), p0.y.min(p1.y), p0.x.max(p1.x), p0.y.max(p1.y)) ////
+        ////Rect::new(p0.x, p0.y, p1.x, p1.y)
     }
     /// Create a new `Rect` with the same size as `self` and a new origin.
     pub fn with_origin(self, origin: Point) -> Rect {
@@ -372,10 +408,10 @@ impl Rect {
         let y1 = self.y1.min(other.y1);
         Rect { x0, y0, x1: x1.max(x0), y1: y1.max(y0) }
     }
-    // It's a bit of duplication having both this and the impl method, but
-    // removing that would require using the trait. We'll leave it for now.
+    /// The area of the rectangle.
     fn area(&self) -> f64 {
-        Rect::area(self)
+        self.width() as f64 * self.height() as f64 ////
+        ////Rect::area(self)
     }
     /// The origin of the rectangle.
     ///
@@ -406,17 +442,18 @@ impl Rect {
 impl Sub for Rect {
     type Output = Insets;
     fn sub(self, other: Rect) -> Insets {
-        let x0 = other.x0 - self.x0;
-        let y0 = other.y0 - self.y0;
-        let x1 = self.x1 - other.x1;
-        let y1 = self.y1 - other.y1;
+        //  Insets can't be negative, so a rect that doesn't reach beyond `other` has none
+        let x0 = other.x0.saturating_sub(self.x0);
+        let y0 = other.y0.saturating_sub(self.y0);
+        let x1 = self.x1.saturating_sub(other.x1);
+        let y1 = self.y1.saturating_sub(other.y1);
         Insets { x0, y0, x1, y1 }
     }
 }
 impl Sub<Vec2> for Rect {
     type Output = Rect;
     fn sub(self, v: Vec2) -> Rect {
-        Rect::new(self.x0 - v.x, self.y0 - v.y, self.x1 - v.x, self.y1 - v.y)
+        Rect::new(self.x0.saturating_sub(v.x), self.y0.saturating_sub(v.y), self.x1.saturating_sub(v.x), self.y1.saturating_sub(v.y)) ////
     }
 }
 impl Add<Vec2> for Rect {
@@ -451,16 +488,30 @@ pub struct Insets { ////
 }
 impl Insets {
     pub const ZERO: Insets = Insets { x0: 0, y0: 0, x1: 0, y1: 0 };
+    /// New insets.
+    pub const fn new(x0: ScreenCoord, y0: ScreenCoord, x1: ScreenCoord, y1: ScreenCoord) -> Insets {
+        Insets { x0, y0, x1, y1 }
+    }
+    /// New uniform insets.
+    pub const fn uniform(d: ScreenCoord) -> Insets { Insets { x0: d, y0: d, x1: d, y1: d } }
+}
+impl From<ScreenCoord> for Insets {
+    fn from(src: ScreenCoord) -> Insets { Insets::uniform(src) }
 }
 impl Add<Rect> for Insets {
     type Output = Rect;
 
     fn add(self, other: Rect) -> Rect {
+        //  A `ScreenCoord` can't go past the edges of the screen, so the rect stops there ////
         Rect {
-            x0: other.x0 - self.x0,
-            y0: other.y0 - self.y0,
-            x1: other.x1 + self.x1,
-            y1: other.y1 + self.y1,
+            x0: other.x0.saturating_sub(self.x0), ////
+            y0: other.y0.saturating_sub(self.y0), ////
+            x1: other.x1.saturating_add(self.x1), ////
+            y1: other.y1.saturating_add(self.y1), ////
+            ////x0: other.x0 - self.x0,
+            ////y0: other.y0 - self.y0,
+            ////x1: other.x1 + self.x1,
+            ////y1: other.y1 + self.y1,
         }
     }
 }
@@ -600,24 +651,51 @@ pub struct Line { ////
     /// The line's end point.
     pub p1: Point,
 }
+impl Line {
+    /// Create a new line.
+    pub fn new(p0: impl Into<Point>, p1: impl Into<Point>) -> Line {
+        Line { p0: p0.into(), p1: p1.into() }
+    }
+}
 impl fmt::Debug for Point {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({:?}, {:?})", self.x, self.y)
     }
 }
+impl fmt::Debug for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rect {{ {:?}, {:?} }}", self.origin(), self.size())
+    }
+}
 
 /// A datatype representing color.
 ///
 /// Currently this is only a 32 bit RGBA value, but it will likely
 /// extend to some form of wide-gamut colorspace, and in the meantime
 /// is useful for giving programs proper type.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Color {
     Rgba32(u32),
 }
 impl Color {
     pub const BLACK: Color = Color::Rgba32(0x000000);
     pub const WHITE: Color = Color::Rgba32(0xffffff);
+    /// Create a color from 8 bit per component RGB values.
+    pub const fn rgb8(r: u8, g: u8, b: u8) -> Color {
+        Color::Rgba32((r as u32) << 16 | (g as u32) << 8 | b as u32)
+    }
+    /// The 8 bit RGB components of the color.
+    pub fn as_rgb8(self) -> (u8, u8, u8) {
+        let Color::Rgba32(rgb) = self;
+        ((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+    }
+    /// Blend with `other`, from this color at `t = 0.0` to `other` at `t = 1.0`.
+    pub fn lerp(self, other: Color, t: ScreenFactor) -> Color {
+        let (r0, g0, b0) = self.as_rgb8();
+        let (r1, g1, b1) = other.as_rgb8();
+        let mix = |c0: u8, c1: u8| (c0 as ScreenFactor + (c1 as ScreenFactor - c0 as ScreenFactor) * t + 0.5) as u8;
+        Color::rgb8(mix(r0, r1), mix(g0, g1), mix(b0, b1))
+    }
 }
 ////End
 
@@ -636,8 +714,9 @@ pub use im;
 
 mod app;
 ////mod app_delegate;
-////mod bloom;
+mod bloom; ////
 mod box_constraints;
+mod command; ////
 ////mod command;
 ////mod contexts;
 mod core;
@@ -645,23 +724,25 @@ mod data;
 ////mod env;
 mod event;
 ////mod ext_event;
+pub mod piet; ////
 ////pub mod lens;
 ////mod localization;
 ////mod menu;
 ////mod mouse;
-////#[cfg(not(target_arch = "wasm32"))]
-////#[cfg(test)]
-////mod tests;
-////pub mod text;
+#[cfg(not(target_arch = "wasm32"))]
+#[cfg(test)]
+mod tests; ////
+pub mod text; ////
 ////pub mod theme;
 ////mod util;
 pub mod widget;
 ////mod win_handler;
-////mod window;
+mod window; ////
 
 // Types from kurbo & piet that are required by public API.
 ////pub use kurbo::{Affine, Insets, Point, Rect, Size, Vec2};
 ////pub use piet::{Color, LinearGradient, RadialGradient, RenderContext, UnitPoint};
+pub use piet::{Arc, Circle, IntoBrush, LinearGradient, PaintBrush, Piet, PolyLine, RoundedRect, Shape}; ////
 // these are the types from shell that we expose; others we only use internally.
 ////pub use shell::keyboard_types;
 ////pub use shell::{
@@ -674,11 +755,13 @@ pub use crate::core::WidgetPod;
 pub use app::{AppLauncher, WindowDesc};
 ////pub use app_delegate::{AppDelegate, DelegateCtx};
 pub use box_constraints::BoxConstraints;
+pub use command::{Command, Payload, PayloadValue, Selector, Target}; ////
 ////pub use command::{sys as commands, Command, Selector, SingleUse, Target};
 ////pub use contexts::{EventCtx, LayoutCtx, LifeCycleCtx, PaintCtx, Region, UpdateCtx};
 pub use data::Data;
 ////pub use env::{Env, Key, KeyOrValue, Value, ValueType};
 pub use event::{Event, InternalEvent, InternalLifeCycle, LifeCycle};
+pub use event::{ButtonAction, ButtonEvent, ButtonRecognizer}; ////
 ////pub use ext_event::{ExtEventError, ExtEventSink};
 ////pub use lens::{Lens, LensExt, LensWrap};
 ////pub use localization::LocalizedString;
@@ -690,11 +773,12 @@ pub use widget::{Widget,
 WidgetId};
 pub use widget::{BoxedWidget, MAX_WIDGETS, StaticWidgets}; ////
 ////pub use win_handler::DruidHandler;
+pub use window::Window; ////
 ////pub use window::{Window, WindowId};
 
 #[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
-////pub(crate) use event::{StateCell, StateCheckFn};
+pub(crate) use event::{StateCell, StateCheckFn}; ////
 
 /// The meaning (mapped value) of a keypress.
 ///
@@ -711,7 +795,7 @@ pub type KeyCode = KbKey;
 pub type KeyModifiers = Modifiers;
 
 //// Begin
-use crate::core::WidgetState;
+use crate::core::{FocusChange, WidgetState};
 
 const SCREEN_WIDTH:  ScreenCoord = 240;
 const SCREEN_HEIGHT: ScreenCoord = 240;
@@ -770,32 +854,6 @@ impl<T: Clone> AppState<T> {
     }
 }
 
-/// Bloom Filter
-#[derive(Clone)]
-pub struct Bloom<T>(Vec<T>);
-impl<T: Eq + Clone> Bloom<T> {
-    pub fn new() -> Self { Self(Vec::new()) }
-    pub fn clear(&mut self) { self.0.clear(); }    
-    pub fn may_contain(&self, id: &T) -> bool {
-        for item in &self.0 {
-            if *item == *id { return true; }
-        }
-        false
-    }
-    pub fn add(&mut self, id: &T) {
-        self.0.push(id.clone());
-    }
-    pub fn union(&self, bloom: Bloom<T>) -> Bloom<T> {
-        let mut result = Bloom(self.0.clone());
-        for item in bloom.0 {
-            if !result.may_contain(&item) {
-                result.0.push(item.clone());
-            }
-        }
-        result
-    }
-}
-
 #[derive(Clone)]
 pub struct BoxedAppDelegate<T> (Option<T>);
 impl<T> BoxedAppDelegate<T> {
@@ -835,26 +893,50 @@ impl fmt::Debug for Clipboard {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Clipboard") }
 }
 
-#[derive(Clone)]
-pub struct Command();
-impl fmt::Debug for Command {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Command") }
-}
-
 #[derive(Clone, Copy)]
 pub struct ContextState();
 
-#[derive(Clone, Copy)]
-pub struct Counter();
+/// Allocates ids from 0 up to a limit, recycling the ids that are freed.
+///
+/// The ids in use are marked in a bitmap of atomic words, so ids may be
+/// allocated and freed from interrupt handlers too. The static widget table
+/// is searched by id, so any id may be allocated, including 0.
+pub struct Counter {
+    /// Bit `id % 32` of word `id / 32` is set when `id` is in use.
+    used: [AtomicU32; 8],
+    /// Ids from `limit` up are never allocated, e.g. as they are reserved.
+    limit: CounterType,
+}
 impl Counter {
-    pub fn new() -> Self { Counter{}}
-    pub fn next_nonzero(self) -> CounterType {
-        let count = unsafe { COUNTER };
-        unsafe { COUNTER += 1 };
-        count
+    /// Create a counter that allocates ids below `limit`.
+    pub const fn new(limit: CounterType) -> Self {
+        const FREE: AtomicU32 = AtomicU32::new(0);
+        Counter { used: [FREE; 8], limit }
+    }
+    /// Allocate the lowest free id. Panics if all the ids are in use.
+    pub fn next(&self) -> CounterType {
+        for id in 0..self.limit {
+            let (word, bit) = Self::bit(id);
+            if self.used[word].fetch_or(bit, Ordering::SeqCst) & bit == 0 {
+                return id;
+            }
+        }
+        panic!("out of ids")
+    }
+    /// Return `id` to the counter, for reuse by `next`.
+    pub fn free(&self, id: CounterType) {
+        let (word, bit) = Self::bit(id);
+        self.used[word].fetch_and(!bit, Ordering::SeqCst);
+    }
+    /// Returns `true` if `id` has been allocated and not freed.
+    pub fn is_used(&self, id: CounterType) -> bool {
+        let (word, bit) = Self::bit(id);
+        self.used[word].load(Ordering::SeqCst) & bit != 0
+    }
+    fn bit(id: CounterType) -> (usize, u32) {
+        (id as usize / 32, 1 << (id % 32))
     }
 }
-static mut COUNTER: u8 = 0;
 
 #[derive(Clone)]
 pub struct DruidHandler<T> {
@@ -873,8 +955,74 @@ impl Env {
     pub fn same(&self, env: &Env) -> bool { true } ////TODO
 }
 
+/// A context provided to event handling methods of widgets.
 #[derive(Clone)]
-pub struct EventCtx();
+pub struct EventCtx {
+    pub state: ContextState,
+    pub(crate) widget_state: WidgetState,
+    pub(crate) is_handled: bool,
+    pub(crate) is_root: bool,
+}
+impl EventCtx {
+    /// The id of the current widget.
+    pub fn widget_id(&self) -> WidgetId { self.widget_state.id }
+    /// The layout size of the current widget.
+    pub fn size(&self) -> Size { self.widget_state.size() }
+    /// The "hot" (aka hover) status of the widget.
+    pub fn is_hot(&self) -> bool { self.widget_state.is_hot }
+    /// The "active" status of the widget, which is set while a touch that
+    /// started on the widget is held down.
+    pub fn is_active(&self) -> bool { self.widget_state.is_active }
+    /// Set the "active" state of the widget.
+    pub fn set_active(&mut self, active: bool) {
+        self.widget_state.is_active = active;
+    }
+    /// Request a paint of the whole widget.
+    pub fn request_paint(&mut self) {
+        let rect = self.widget_state.size().to_rect();
+        self.widget_state.invalid.add_rect(rect);
+    }
+    /// Request a layout pass.
+    pub fn request_layout(&mut self) {
+        self.widget_state.needs_layout = true;
+    }
+    /// Indicate that the children of this widget have changed.
+    pub fn children_changed(&mut self) {
+        self.widget_state.children_changed = true;
+        self.request_layout();
+    }
+    /// Request an animation frame.
+    pub fn request_anim_frame(&mut self) {
+        self.widget_state.request_anim = true;
+        self.request_paint();
+    }
+    /// Request a timer event, delivered as `Event::Timer` after `deadline`.
+    pub fn request_timer(&mut self, deadline: ::core::time::Duration) -> TimerToken {
+        let token = TimerToken::next();
+        self.widget_state.add_timer(token, deadline); ////
+        token
+    }
+    /// Set the event as "handled", which stops its propagation to other widgets.
+    pub fn set_handled(&mut self) { self.is_handled = true; }
+    /// Determine whether the event has been handled by some other widget.
+    pub fn is_handled(&self) -> bool { self.is_handled }
+    /// The focus status of the widget. Button events are sent to the focused widget.
+    pub fn is_focused(&self) -> bool { self.widget_state.is_focused }
+    /// Request keyboard focus, to receive button events.
+    pub fn request_focus(&mut self) {
+        self.widget_state.request_focus = Some(FocusChange::Focus(self.widget_state.id));
+    }
+    /// Move the focus to the next widget in the focus chain, wrapping around.
+    pub fn focus_next(&mut self) { self.widget_state.request_focus = Some(FocusChange::Next); }
+    /// Move the focus to the previous widget in the focus chain, wrapping around.
+    pub fn focus_prev(&mut self) { self.widget_state.request_focus = Some(FocusChange::Previous); }
+    /// Give up the focus, if this widget has it.
+    pub fn resign_focus(&mut self) {
+        if self.is_focused() {
+            self.widget_state.request_focus = Some(FocusChange::Resign);
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct ExtEventHost();
@@ -909,6 +1057,9 @@ pub struct KeyOrValue<T>(T);
 impl<T> KeyOrValue<T> {
     pub fn resolve(self, env: &Env) -> T { self.0 } 
 }
+impl<T> From<T> for KeyOrValue<T> {
+    fn from(value: T) -> Self { KeyOrValue(value) }
+}
 
 #[derive(Clone)]
 pub struct LayoutCtx {
@@ -918,7 +1069,22 @@ pub struct LayoutCtx {
 }
 impl LayoutCtx {
     pub fn text(&self) -> PietText { PietText{} }
-    pub fn set_paint_insets(&self, insets: Insets) {}
+    /// Set explicit paint [`Insets`] for this widget.
+    ///
+    /// You are not required to set explicit paint bounds unless you need
+    /// to paint outside of your layout bounds. In this case, the argument
+    /// should be an [`Insets`] struct that indicates where your widget
+    /// needs to overpaint, relative to its bounds.
+    ///
+    /// For more information, see [`WidgetPod::paint_insets`].
+    ///
+    /// [`Insets`]: struct.Insets.html
+    /// [`WidgetPod::paint_insets`]: struct.WidgetPod.html#method.paint_insets
+    pub fn set_paint_insets(&mut self, insets: impl Into<Insets>) {
+        self.widget_state.paint_insets = insets.into(); ////
+        ////let insets = insets.into();
+        ////self.widget_state.paint_insets = insets.nonnegative();
+    }
 }
 
 #[derive(Clone)]
@@ -930,6 +1096,28 @@ impl LifeCycleCtx {
     pub fn register_child(&mut self, child_id: WidgetId) {
         self.widget_state.children.add(&child_id);
     }
+    /// Register this widget to be eligible to accept focus automatically.
+    ///
+    /// This should only be called in response to a `LifeCycle::WidgetAdded` event.
+    /// The widgets are visited in the order they were added, up to the capacity of the focus chain.
+    pub fn register_for_focus(&mut self) {
+        let id = self.widget_state.id;
+        if self.widget_state.focus_chain.push(id).is_err() {
+            log::warn!("focus chain is full, widget {:?} can't take focus", id);
+        }
+    }
+    /// The layout size of the current widget.
+    pub fn size(&self) -> Size { self.widget_state.size() }
+    /// Request a paint of the whole widget.
+    pub fn request_paint(&mut self) {
+        let rect = self.widget_state.size().to_rect();
+        self.widget_state.invalid.add_rect(rect);
+    }
+    /// Request an animation frame.
+    pub fn request_anim_frame(&mut self) {
+        self.widget_state.request_anim = true;
+        self.request_paint();
+    }
 }
 
 #[derive(Clone)]
@@ -943,10 +1131,20 @@ impl<T> LocalizedString<T> {
 #[derive(Clone)]
 pub struct Modifiers();
 
-#[derive(Clone)]
-pub struct MouseEvent();
-impl fmt::Debug for MouseEvent {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "MouseEvent") }
+/// The state of the mouse for a click, mouse-up, move, or wheel event.
+///
+/// On the PineTime there is no mouse: touches on the touch panel are
+/// reported as `MouseDown`, drags as `MouseMove` and lifts as `MouseUp`.
+#[derive(Clone, Debug)] ////
+pub struct MouseEvent {
+    /// The position of the touch in the coordinate space of the receiver.
+    pub pos: Point,
+    /// The position of the touch in the coordinate space of the window.
+    pub window_pos: Point,
+}
+impl MouseEvent {
+    /// Create a touch event at the given window position.
+    pub fn new(pos: Point) -> Self { MouseEvent { pos, window_pos: pos } }
 }
 
 #[derive(Clone)]
@@ -970,7 +1168,58 @@ pub struct PaintCtx {
 }
 impl PaintCtx {
     pub fn region(&self) -> Region { self.region }
+    /// The layout size of the current widget.
+    pub fn size(&self) -> Size { self.widget_state.size() }
+    /// The focus status of the widget, to paint it highlighted.
+    pub fn is_focused(&self) -> bool { self.widget_state.is_focused }
+    /// The "hot" (aka hover) status of the widget.
+    pub fn is_hot(&self) -> bool { self.widget_state.is_hot }
+    /// The "active" status of the widget, to paint it pressed.
+    pub fn is_active(&self) -> bool { self.widget_state.is_active }
+    /// Restrict painting to `rect`, until the enclosing `with_save` returns.
+    ///
+    /// Widgets outside the clipped region are skipped by `WidgetPod::paint`.
+    pub fn clip(&mut self, rect: Rect) {
+        self.region.intersect_with(rect);
+        self.render_ctx.clip(rect);
+        ////TODO: Clip the LVGL object with lv_obj_set_size(...)
+    }
+    /// Fill a shape with a `Color` or `LinearGradient`.
+    ///
+    /// Shapes are a `Rect`, `RoundedRect`, `Circle`, `Arc`, `Line` or `PolyLine`.
+    pub fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush) {
+        //// TODO: Render with LVGL
+        /*
+        lv_canvas_draw_rect(canvas, x, y, w, h, &rect_dsc);              //  Rect, RoundedRect and Circle with rect_dsc.radius
+        lv_canvas_draw_arc(canvas, x, y, r, start, end, &arc_dsc);       //  Arc, with arc_dsc.width = r for a pie
+        lv_canvas_draw_line(canvas, points, point_cnt, &line_dsc);       //  Line and PolyLine
+        */
+        self.render_ctx.fill(shape, brush);
+    }
+    /// Stroke the outline of a shape. The stroke is drawn inside the shape.
+    pub fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush, width: ScreenCoord) {
+        //// TODO: Render with LVGL
+        /*
+        rect_dsc.bg_opa = LV_OPA_TRANSP;                                 //  Only the border of a Rect,
+        rect_dsc.border_width = width;                                   //  RoundedRect and Circle
+        lv_canvas_draw_rect(canvas, x, y, w, h, &rect_dsc);
+        arc_dsc.width = width; line_dsc.width = width;                   //  Arc, Line and PolyLine as for fill
+        */
+        self.render_ctx.stroke(shape, brush, width);
+    }
+    /// Draw an image placed and scaled by `transform`, e.g. from `FillStrat::affine_to_fill`.
+    pub fn draw_image(&mut self, image: &crate::widget::ImageData, transform: Affine) {
+        //// TODO: Render with LVGL
+        /*
+        lv_canvas_draw_img(canvas, x, y, &img_dsc, &img_draw_dsc);      //  img_draw_dsc.zoom is the scale * 256
+        */
+        self.render_ctx.draw_image(image, transform);
+    }
+    /// Saves the origin and clip of the render context, and the visible region,
+    /// and restores them after `f` returns.
     pub fn with_save(&mut self, f: impl FnOnce(&mut PaintCtx)) {
+        let saved = self.render_ctx; ////
+        let region = self.region; ////
         if let Err(e) = self.render_ctx.save() {
             log::error!("Failed to save RenderContext: '{}'", e);
             return;
@@ -981,6 +1230,8 @@ impl PaintCtx {
         if let Err(e) = self.render_ctx.restore() {
             log::error!("Failed to restore RenderContext: '{}'", e);
         }
+        self.render_ctx = saved; ////
+        self.region = region; ////
     }
     /// Creates a temporary `PaintCtx` with a new visible region, and calls
     /// the provided function with that `PaintCtx`.
@@ -999,13 +1250,29 @@ impl PaintCtx {
         f(&mut child_ctx);
         ////self.z_ops.append(&mut child_ctx.z_ops); ////TODO
     }
-}
-
-#[derive(Clone, Copy)]
-pub struct Piet();
-impl Piet {
-    pub fn save(self) -> Result<(), String> { Ok(()) } ////TODO
-    pub fn restore(self) -> Result<(), String> { Ok(()) } ////TODO
+    /// Allows to specify order for paint operations.
+    ///
+    /// Larger `z_index` indicate that an operation will be executed later.
+    /// As closures can't be boxed here, the operation is the index of a child,
+    /// which the container paints after sorting with `take_z_ops`.
+    pub fn paint_with_z_index(&mut self, z_index: u32, child: usize) { ////
+    ////pub fn paint_with_z_index(&mut self, z_index: u32, paint_func: impl FnOnce(&mut PaintCtx) + 'static) {
+        //// TODO: Raise the LVGL object of the child
+        /*
+        lv_obj_move_foreground(child);                                   //  In order of z_index
+        */
+        if self.z_ops.push(ZOrderPaintOp { z_index, child }).is_err() {
+            log::warn!("Too many z-ordered paint operations");
+        }
+    }
+    /// Remove the queued z-ordered paint operations, in the order they should be painted.
+    ///
+    /// Operations with the same `z_index` are painted in order of their child index.
+    pub fn take_z_ops(&mut self) -> Vec<ZOrderPaintOp> {
+        let mut z_ops = ::core::mem::replace(&mut self.z_ops, Vec::new());
+        z_ops.sort_unstable_by_key(|op| (op.z_index, op.child));
+        z_ops
+    }
 }
 
 #[derive(Clone)]
@@ -1079,9 +1346,14 @@ impl AddAssign<Vec2> for Region {
         self.0 = self.0 + offset;
     }
 }
+impl SubAssign<Vec2> for Region { ////
+    fn sub_assign(&mut self, offset: Vec2) {
+        self.0 = self.0 - offset;
+    }
+}
 
-#[derive(Clone)]
-pub struct Shape();
+////#[derive(Clone)]
+////pub struct Shape();
 
 /*
 #[derive(Clone)]
@@ -1096,20 +1368,32 @@ impl SizedBox {
 }
 */
 
-#[derive(Clone)]
-pub struct Target();
-impl fmt::Debug for Target {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "Target") }
+/// A token that uniquely identifies a running timer.
+///
+/// Tokens wrap around after 65535 timers, long after a timer with the same
+/// token has fired, even with a timer for each blink of a cursor.
+#[derive(Clone, Copy, PartialEq, Eq)] ////
+pub struct TimerToken(u16);
+impl TimerToken {
+    /// A token that does not correspond to any timer.
+    pub const INVALID: TimerToken = TimerToken(0);
+    /// Create a new token, never `INVALID`.
+    pub fn next() -> TimerToken {
+        loop {
+            let count = TIMER_COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+            if count != 0 {
+                return TimerToken(count);
+            }
+        }
+    }
 }
-
-#[derive(Clone, Copy)]
-pub struct TimerToken();
+static TIMER_COUNTER: AtomicU16 = AtomicU16::new(0);
 impl fmt::Debug for TimerToken {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "TimerToken") }
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "TimerToken({})", self.0) }
 }
 
 /// A representation of a point relative to a unit rectangle. Based on https://docs.rs/piet/0.0.6/src/piet/gradient.rs.html
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UnitPoint {
     u: ScreenFactor,
     v: ScreenFactor,
@@ -1148,8 +1432,25 @@ pub struct UpdateCtx {
     pub state: ContextState,
 }
 impl UpdateCtx {
-    pub fn request_layout(&self) {
-        ////TODO
+    /// Request a layout pass.
+    pub fn request_layout(&mut self) { ////
+    ////pub fn request_layout(&self) {
+        self.widget_state.needs_layout = true;
+    }
+    /// Indicate that the children of this widget have changed.
+    pub fn children_changed(&mut self) {
+        self.widget_state.children_changed = true;
+        self.request_layout();
+    }
+    /// Request a paint of the whole widget.
+    pub fn request_paint(&mut self) {
+        let rect = self.widget_state.size().to_rect();
+        self.widget_state.invalid.add_rect(rect);
+    }
+    /// Request an animation frame.
+    pub fn request_anim_frame(&mut self) {
+        self.widget_state.request_anim = true;
+        self.request_paint();
     }
 }
 
@@ -1216,7 +1517,8 @@ impl<T: Data + Clone> WindowBuilder<T> {
 
         //  Paint the widget
         let region = Region( Rect{ x0: 0, y0: 0, x1: SCREEN_WIDTH, y1: SCREEN_HEIGHT } );
-        let render_ctx = Piet{};
+        let render_ctx = Piet::new(region.to_rect().size()); ////
+        ////let render_ctx = Piet{};
         let mut paint_ctx = PaintCtx{ 
             region, render_ctx,
             depth: 0, 
@@ -1236,20 +1538,47 @@ impl WindowHandle {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct WindowId();
 impl WindowId {
     pub fn next() -> Self { Self{} }  ////TODO
 }
 
 pub mod theme {
-    use crate::{ Color, Env, KeyOrValue, ScreenFactor };
+    use crate::{ Color, Env, KeyOrValue, ScreenCoord, ScreenFactor };
     pub fn init() -> Env { Env{} }
     pub static LABEL_COLOR: KeyOrValue<Color> = KeyOrValue(Color::Rgba32(0xffffff));
     pub static TEXT_SIZE_NORMAL: KeyOrValue<ScreenFactor> = KeyOrValue(1.0);
     pub static FONT_NAME: KeyOrValue<&'static str> = KeyOrValue("standard_font");
+    pub static BASIC_WIDGET_HEIGHT: KeyOrValue<ScreenCoord> = KeyOrValue(36);  //  Big enough for a fingertip
+    pub static WIDE_WIDGET_WIDTH: KeyOrValue<ScreenCoord> = KeyOrValue(200);
+    pub static BORDERED_WIDGET_HEIGHT: KeyOrValue<ScreenCoord> = KeyOrValue(48);
+    pub static BORDER_DARK: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x3a, 0x3a, 0x3a));
+    pub static BORDER_LIGHT: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0xa1, 0xa1, 0xa1));
+    pub static BACKGROUND_LIGHT: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x3a, 0x3a, 0x3a));
+    pub static BACKGROUND_DARK: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x31, 0x31, 0x31));
+    pub static FOREGROUND_LIGHT: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0xf9, 0xf9, 0xf9));
+    pub static FOREGROUND_DARK: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0xbf, 0xbf, 0xbf));
+    pub static BUTTON_DARK: KeyOrValue<Color> = KeyOrValue(Color::BLACK);
+    pub static BUTTON_LIGHT: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x21, 0x21, 0x21));
+    pub static PRIMARY_LIGHT: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x5c, 0xc4, 0xff));
+    pub static PRIMARY_DARK: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x00, 0x8d, 0xdd));
+    pub static SELECTION_COLOR: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0xf3, 0x00, 0x21));
+    pub static CURSOR_COLOR: KeyOrValue<Color> = KeyOrValue(Color::WHITE);
+    pub static FOCUS_RING_COLOR: KeyOrValue<Color> = KeyOrValue(Color::rgb8(0x5c, 0xc4, 0xff));
+    pub static FOCUS_RING_WIDTH: KeyOrValue<ScreenCoord> = KeyOrValue(2);  //  Or 0 to hide the ring
+    pub static CLOCK_24_HOUR: KeyOrValue<bool> = KeyOrValue(true);  //  Or AM and PM
+    pub static TEXTBOX_BORDER_RADIUS: KeyOrValue<ScreenCoord> = KeyOrValue(2);
+    pub static PROGRESS_BAR_RADIUS: KeyOrValue<ScreenCoord> = KeyOrValue(4);
+}
+
+/// A paint operation queued by `PaintCtx::paint_with_z_index`, referring to a child by index.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZOrderPaintOp { ////
+////pub struct ZOrderPaintOp();
+    /// Larger values are painted later, i.e. on top
+    pub z_index: u32,
+    /// Index of the child to be painted, as known to the container
+    pub child: usize,
 }
-
-#[derive(Clone)]
-pub struct ZOrderPaintOp();
 //// End
@@ -0,0 +1,736 @@
+//! Software renderer for `PaintCtx`. Based on the `RenderContext` API of https://docs.rs/piet/0.1.0/piet/
+//!
+//! Shapes are rasterized into horizontal spans of pixels, which are sent to the
+//! display driver registered with [`set_display`]. When no display is registered
+//! (e.g. when LVGL renders the widgets), painting does nothing.
+//!
+//! [`set_display`]: fn.set_display.html
+
+use crate::{Affine, Color, Line, Point, Rect, ScreenCoord, ScreenFactor, Size, UnitPoint, Vec2};
+use crate::widget::ImageData;
+
+/// Fills a rectangle of the display with a color. The rectangle is in window coordinates.
+pub type DrawFn = fn(Rect, Color);
+
+/// The display driver that renders the spans, if any.
+static mut DISPLAY: Option<DrawFn> = None;
+
+/// Render widgets in software by sending the painted spans to `draw`,
+/// e.g. a function that sets a window of pixels on the ST7789 display.
+pub fn set_display(draw: DrawFn) {
+    unsafe { DISPLAY = Some(draw); }
+}
+
+/// The render context, which tracks the origin and clip of the widget being painted.
+#[derive(Clone, Copy)]
+pub struct Piet {
+    /// Origin of the current widget, in window coordinates. It's above or left
+    /// of the window when the widget is scrolled out of view.
+    origin: (i16, i16),
+    /// Area that may be painted, in window coordinates.
+    clip: Rect,
+    /// Display driver that renders the spans.
+    draw: Option<DrawFn>,
+    /// Colour behind the current widget, which anti-aliased edges are blended into,
+    /// as the display can't be read back.
+    background: Color,
+}
+
+impl Piet {
+    /// Create a render context for a window of `size`, painting to the registered display.
+    pub fn new(size: Size) -> Piet {
+        Piet::with_display(size, unsafe { DISPLAY })
+    }
+
+    /// Create a render context for a window of `size`, painting to `draw`.
+    pub fn with_display(size: Size, draw: Option<DrawFn>) -> Piet {
+        Piet {
+            origin: (0, 0),
+            clip: size.to_rect(),
+            draw,
+            background: Color::BLACK,
+        }
+    }
+
+    pub fn save(self) -> Result<(), crate::String> { Ok(()) } ////TODO
+    pub fn restore(self) -> Result<(), crate::String> { Ok(()) } ////TODO
+
+    /// Move the origin by `offset`, e.g. to the layout origin of a child widget.
+    pub fn translate(&mut self, offset: Vec2) {
+        self.origin.0 += offset.x as i16;
+        self.origin.1 += offset.y as i16;
+    }
+
+    /// Move the origin back by `offset`, e.g. by the scroll offset of scrolled content.
+    pub fn translate_back(&mut self, offset: Vec2) {
+        self.origin.0 -= offset.x as i16;
+        self.origin.1 -= offset.y as i16;
+    }
+
+    /// Restrict painting to `rect`, in the coordinates of the current widget.
+    pub fn clip(&mut self, rect: Rect) {
+        self.clip = self.clip.intersect(self.to_window(rect));
+    }
+
+    /// Convert `rect` from the coordinates of the current widget to window coordinates,
+    /// cutting off the parts outside the window.
+    fn to_window(&self, rect: Rect) -> Rect {
+        let (x, y) = self.origin;
+        Rect::new(
+            clamp_coord(rect.x0 as i16 + x),
+            clamp_coord(rect.y0 as i16 + y),
+            clamp_coord(rect.x1 as i16 + x),
+            clamp_coord(rect.y1 as i16 + y),
+        )
+    }
+
+    /// Set the colour behind the current widget and its children, e.g. the background of a `Container`.
+    pub fn set_background(&mut self, color: Color) {
+        self.background = color;
+    }
+
+    /// The colour behind the current widget, to blend colours into as there's no alpha.
+    pub fn background(&self) -> Color {
+        self.background
+    }
+
+    /// Fill a shape with a brush.
+    pub fn fill(&mut self, shape: impl Shape, brush: &impl IntoBrush) {
+        shape.rasterize(self, &brush.make_brush(), None);
+    }
+
+    /// Stroke the outline of a shape with a brush. The stroke is drawn inside the shape.
+    pub fn stroke(&mut self, shape: impl Shape, brush: &impl IntoBrush, width: ScreenCoord) {
+        shape.rasterize(self, &brush.make_brush(), Some(width));
+    }
+
+    /// Fill each row of `shape`, leaving out the `hole` if any.
+    fn fill_spans(&mut self, shape: RoundedRect, hole: Option<RoundedRect>, brush: &PaintBrush) {
+        for y in shape.rect.y0..shape.rect.y1 {
+            let (x0, x1) = shape.span(y);
+            match hole.filter(|hole| !hole.is_empty() && y >= hole.rect.y0 && y < hole.rect.y1) {
+                Some(hole) => {
+                    let (h0, h1) = hole.span(y);
+                    self.fill_span(y, x0, h0, brush, shape.rect);
+                    self.fill_span(y, h1, x1, brush, shape.rect);
+                }
+                None => self.fill_span(y, x0, x1, brush, shape.rect),
+            }
+        }
+    }
+
+    /// Fill the pixels `x0..x1` of row `y`. Gradients are resolved within `bounds`.
+    ///
+    /// This is the primitive that every `Shape` is rasterized with.
+    pub fn fill_span(&mut self, y: ScreenCoord, x0: ScreenCoord, x1: ScreenCoord, brush: &PaintBrush, bounds: Rect) {
+        let draw = match self.draw {
+            Some(draw) => draw,
+            None => return,
+        };
+        let span = self.to_window(Rect::new(x0, y, x1, y.saturating_add(1)));
+        let span = span.intersect(self.clip);
+        if span.x0 >= span.x1 || span.y0 >= span.y1 {
+            return;
+        }
+        match brush {
+            PaintBrush::Color(color) => draw(span, *color),
+            PaintBrush::Linear(gradient) if gradient.is_vertical() => {
+                draw(span, gradient.color_at(Point::new(x0, y), bounds));
+            }
+            PaintBrush::Linear(gradient) => {
+                // colour changes along the row, so every pixel is drawn separately
+                for x in span.x0..span.x1 {
+                    let color = gradient.color_at(Point::new(clamp_coord(x as i16 - self.origin.0), y), bounds);
+                    draw(Rect::new(x, span.y0, x + 1, span.y1), color);
+                }
+            }
+        }
+    }
+    /// Draw `image` placed and scaled by `transform`, sampling the nearest pixel.
+    ///
+    /// Only scaling and translation are supported. Transparent pixels are skipped,
+    /// and each run of same-coloured pixels is drawn as one span.
+    pub fn draw_image(&mut self, image: &ImageData, transform: Affine) {
+        let [scale_x, _, _, scale_y, origin_x, origin_y] = transform.as_coeffs();
+        let size = image.size();
+        if size.width == 0 || size.height == 0 || scale_x <= 0. || scale_y <= 0. {
+            return;
+        }
+        let bounds = Rect::new(
+            clamp_coord(origin_x as i16),
+            clamp_coord(origin_y as i16),
+            clamp_coord((origin_x + size.width as ScreenFactor * scale_x) as i16),
+            clamp_coord((origin_y + size.height as ScreenFactor * scale_y) as i16),
+        );
+        // source pixel under the centre of the target pixel
+        let source = |x: ScreenCoord, origin: ScreenFactor, scale: ScreenFactor| {
+            ((x as ScreenFactor + 0.5 - origin) / scale) as ScreenCoord
+        };
+        for y in bounds.y0..bounds.y1 {
+            let source_y = source(y, origin_y, scale_y);
+            let mut run: Option<(ScreenCoord, Color)> = None;
+            for x in bounds.x0 as u16..=bounds.x1 as u16 {
+                let x = x as ScreenCoord;
+                let color = if x < bounds.x1 {
+                    image.pixel(source(x, origin_x, scale_x), source_y)
+                } else {
+                    None
+                };
+                match run {
+                    Some((_, run_color)) if Some(run_color) == color => continue,
+                    Some((start, run_color)) => self.fill_span(y, start, x, &PaintBrush::Color(run_color), bounds),
+                    None => {}
+                }
+                run = color.map(|color| (x, color));
+            }
+        }
+    }
+    /// Stroke `arc` with `width` pixels inside its radius, blending the edges into the background.
+    ///
+    /// With `rounded_caps` the ends of the arc are semicircles, like `lv_arc` with rounded lines.
+    pub fn stroke_arc_antialiased(&mut self, arc: Arc, color: Color, width: ScreenCoord, rounded_caps: bool) {
+        let half_width = width.min(arc.radius) as ScreenFactor / 2.;
+        let middle = arc.radius as ScreenFactor - half_width;
+        let sweep = arc.end_angle as ScreenFactor - arc.start_angle as ScreenFactor;
+        let caps = [arc.start_angle as ScreenFactor, arc.end_angle as ScreenFactor]
+            .map(|angle| polar_precise(middle, angle));
+        let coverage = |dx: ScreenFactor, dy: ScreenFactor| {
+            let distance = sqrt(dx * dx + dy * dy);
+            let off_middle = if distance > middle { distance - middle } else { middle - distance };
+            let radial = clamp_unit(half_width - off_middle + 0.5);
+            let angular = if sweep >= 360. {
+                1.
+            } else {
+                // distance in pixels from the nearest end, negative outside the arc
+                let into = (angle_of(dx, dy) - arc.start_angle as ScreenFactor + 720.) % 360.;
+                let from_end = if into <= sweep { into.min(sweep - into) } else { -(into - sweep).min(360. - into) };
+                clamp_unit(from_end * DEGREES_TO_RADIANS * distance + 0.5)
+            };
+            let mut coverage = radial * angular;
+            if rounded_caps && sweep < 360. {
+                for (x, y) in caps.iter() {
+                    let cap = sqrt((dx - x) * (dx - x) + (dy - y) * (dy - y));
+                    coverage = coverage.max(clamp_unit(half_width - cap + 0.5));
+                }
+            }
+            coverage
+        };
+        let bounds = Rect::new(
+            arc.center.x.saturating_sub(arc.radius),
+            arc.center.y.saturating_sub(arc.radius),
+            arc.center.x.saturating_add(arc.radius),
+            arc.center.y.saturating_add(arc.radius),
+        );
+        for y in bounds.y0..bounds.y1 {
+            let mut run: Option<(ScreenCoord, Color)> = None;
+            for x in bounds.x0 as u16..=bounds.x1 as u16 {
+                let x = x as ScreenCoord;
+                let dx = x as ScreenFactor + 0.5 - arc.center.x as ScreenFactor;
+                let dy = y as ScreenFactor + 0.5 - arc.center.y as ScreenFactor;
+                // coverage in eighths, so neighbouring pixels share a colour and make runs
+                let eighths = if x < bounds.x1 { (coverage(dx, dy) * 8. + 0.5) as u8 } else { 0 };
+                let pixel = match eighths {
+                    0 => None,
+                    8 => Some(color),
+                    _ => Some(self.background.lerp(color, eighths as ScreenFactor / 8.)),
+                };
+                match run {
+                    Some((_, run_color)) if Some(run_color) == pixel => continue,
+                    Some((start, run_color)) => self.fill_span(y, start, x, &PaintBrush::Color(run_color), bounds),
+                    None => {}
+                }
+                run = pixel.map(|pixel| (x, pixel));
+            }
+        }
+    }
+}
+
+/// Something that can be filled or stroked by the software renderer.
+pub trait Shape {
+    /// Paint the shape with `brush`, as a stroke of `width` pixels or filled if `None`.
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>);
+}
+
+impl Shape for Rect {
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        RoundedRect::from(*self).rasterize(piet, brush, width)
+    }
+}
+
+impl Shape for RoundedRect {
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        let hole = width.map(|width| self.shrink(width));
+        piet.fill_spans(*self, hole, brush);
+    }
+}
+
+/// A rectangle with rounded corners.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundedRect {
+    rect: Rect,
+    radius: ScreenCoord,
+}
+
+impl RoundedRect {
+    /// A new rounded rectangle. The radius is limited to half the shorter side.
+    pub fn from_rect(rect: Rect, radius: ScreenCoord) -> RoundedRect {
+        let radius = radius.min(rect.width() / 2).min(rect.height() / 2);
+        RoundedRect { rect, radius }
+    }
+
+    /// The bounding rectangle.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// The corner radius.
+    pub fn radius(&self) -> ScreenCoord {
+        self.radius
+    }
+
+    /// The same shape, `width` pixels smaller on every side.
+    pub fn shrink(&self, width: ScreenCoord) -> RoundedRect {
+        let width = width.min(self.rect.width() / 2).min(self.rect.height() / 2);
+        let rect = Rect::new(
+            self.rect.x0 + width,
+            self.rect.y0 + width,
+            self.rect.x1 - width,
+            self.rect.y1 - width,
+        );
+        RoundedRect::from_rect(rect, self.radius.saturating_sub(width))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rect.width() == 0 || self.rect.height() == 0
+    }
+
+    /// The pixels `x0..x1` covered by row `y`.
+    fn span(&self, y: ScreenCoord) -> (ScreenCoord, ScreenCoord) {
+        let rows_from_edge = (y - self.rect.y0).min(self.rect.y1 - 1 - y);
+        if rows_from_edge >= self.radius {
+            return (self.rect.x0, self.rect.x1);
+        }
+        // distance from the centre of the corner to the centre of the row, in half pixels
+        let r = self.radius as u32;
+        let dy = 2 * r - 2 * rows_from_edge as u32 - 1;
+        let chord = isqrt(4 * r * r - dy * dy);
+        // skip the pixels whose centres are outside the corner
+        let inset = ((2 * r - chord) / 2) as ScreenCoord;
+        (self.rect.x0 + inset, self.rect.x1 - inset)
+    }
+}
+
+impl From<Rect> for RoundedRect {
+    fn from(rect: Rect) -> RoundedRect {
+        RoundedRect::from_rect(rect, 0)
+    }
+}
+
+impl Rect {
+    /// Create a `RoundedRect` from this `Rect` with the provided `radius`.
+    pub fn to_rounded_rect(self, radius: ScreenCoord) -> RoundedRect {
+        RoundedRect::from_rect(self, radius)
+    }
+}
+
+/// A circle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Point,
+    pub radius: ScreenCoord,
+}
+
+impl Circle {
+    /// A new circle from center and radius.
+    pub fn new(center: impl Into<Point>, radius: ScreenCoord) -> Circle {
+        Circle { center: center.into(), radius }
+    }
+}
+
+impl Shape for Circle {
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        let rect = Rect::new(
+            self.center.x.saturating_sub(self.radius),
+            self.center.y.saturating_sub(self.radius),
+            self.center.x.saturating_add(self.radius),
+            self.center.y.saturating_add(self.radius),
+        );
+        rect.to_rounded_rect(self.radius).rasterize(piet, brush, width)
+    }
+}
+
+impl Shape for Line {
+    /// Lines are always stroked; filling a line strokes it 1 pixel wide.
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        let width = width.unwrap_or(1).max(1) as i16;
+        let bounds = Rect::from_points(self.p0, self.p1);
+        // Bresenham's line, with a square pen of `width` pixels at every step
+        let (mut x, mut y) = (self.p0.x as i16, self.p0.y as i16);
+        let (end_x, end_y) = (self.p1.x as i16, self.p1.y as i16);
+        let (dx, dy) = ((end_x - x).abs(), -(end_y - y).abs());
+        let (sx, sy) = (if x < end_x { 1 } else { -1 }, if y < end_y { 1 } else { -1 });
+        let mut err = dx + dy;
+        loop {
+            let x0 = clamp_coord(x - width / 2);
+            let x1 = clamp_coord(x - width / 2 + width);
+            for pen_y in y - width / 2..y - width / 2 + width {
+                if pen_y >= 0 && pen_y <= ScreenCoord::MAX as i16 {
+                    piet.fill_span(pen_y as ScreenCoord, x0, x1, brush, bounds);
+                }
+            }
+            if x == end_x && y == end_y {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+}
+
+/// Connected line segments through a list of points, e.g. a heart rate graph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PolyLine<'a> {
+    pub points: &'a [Point],
+}
+
+impl<'a> PolyLine<'a> {
+    /// A new polyline through `points`.
+    pub fn new(points: &'a [Point]) -> PolyLine<'a> {
+        PolyLine { points }
+    }
+}
+
+impl Shape for PolyLine<'_> {
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        for segment in self.points.windows(2) {
+            Line::new(segment[0], segment[1]).rasterize(piet, brush, width);
+        }
+    }
+}
+
+/// An arc of a circle, from `start_angle` to `end_angle` in degrees.
+///
+/// As in LVGL, angles are measured clockwise from 3 o'clock, so an arc from
+/// 270 to 90 covers the top half of the circle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arc {
+    pub center: Point,
+    pub radius: ScreenCoord,
+    pub start_angle: u16,
+    pub end_angle: u16,
+}
+
+impl Arc {
+    /// A new arc of the circle at `center` with `radius`.
+    pub fn new(center: impl Into<Point>, radius: ScreenCoord, start_angle: u16, end_angle: u16) -> Arc {
+        Arc { center: center.into(), radius, start_angle, end_angle }
+    }
+
+    /// Whether the direction `angle` is within the arc.
+    fn contains_angle(&self, angle: ScreenFactor) -> bool {
+        let start = (self.start_angle % 360) as ScreenFactor;
+        let end = (self.end_angle % 360) as ScreenFactor;
+        let sweep = (end - start + 360.) % 360.;
+        if self.end_angle >= self.start_angle + 360 {
+            return true;
+        }
+        (angle - start + 360.) % 360. <= sweep
+    }
+}
+
+impl Shape for Arc {
+    /// Arcs are stroked inside the radius; filling an arc paints a pie.
+    fn rasterize(&self, piet: &mut Piet, brush: &PaintBrush, width: Option<ScreenCoord>) {
+        let r = self.radius as ScreenFactor;
+        let inner = r - width.unwrap_or(self.radius) as ScreenFactor;
+        let bounds = Circle::new(self.center, self.radius);
+        let bounds = Rect::new(
+            bounds.center.x.saturating_sub(bounds.radius),
+            bounds.center.y.saturating_sub(bounds.radius),
+            bounds.center.x.saturating_add(bounds.radius),
+            bounds.center.y.saturating_add(bounds.radius),
+        );
+        for y in bounds.y0..bounds.y1 {
+            // paint the runs of pixels whose centres are on the arc
+            let mut run_start = None;
+            for x in bounds.x0..=bounds.x1 {
+                let dx = x as ScreenFactor + 0.5 - self.center.x as ScreenFactor;
+                let dy = y as ScreenFactor + 0.5 - self.center.y as ScreenFactor;
+                let distance = dx * dx + dy * dy;
+                let inside = x < bounds.x1
+                    && distance <= r * r
+                    && (inner <= 0. || distance >= inner * inner)
+                    && self.contains_angle(angle_of(dx, dy));
+                match (inside, run_start) {
+                    (true, None) => run_start = Some(x),
+                    (false, Some(x0)) => {
+                        piet.fill_span(y, x0, x, brush, bounds);
+                        run_start = None;
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+}
+
+/// Clamp a pixel coordinate to the screen.
+fn clamp_coord(x: i16) -> ScreenCoord {
+    x.clamp(0, ScreenCoord::MAX as i16) as ScreenCoord
+}
+
+/// Angle of the vector `(x, y)` in degrees, clockwise from 3 o'clock since y points down.
+///
+/// Uses a polynomial approximation of `atan`, accurate to about 0.1 degree,
+/// since `f32::atan2` needs the standard library.
+fn angle_of(x: ScreenFactor, y: ScreenFactor) -> ScreenFactor {
+    let (ax, ay) = (if x < 0. { -x } else { x }, if y < 0. { -y } else { y });
+    if ax == 0. && ay == 0. {
+        return 0.;
+    }
+    let a = if ax >= ay { ay / ax } else { ax / ay };
+    let atan = a * (45. - (a - 1.) * (14.02 + 3.8 * a)); // degrees, for 0 <= a <= 1
+    let mut angle = if ax >= ay { atan } else { 90. - atan };
+    if x < 0. {
+        angle = 180. - angle;
+    }
+    if y < 0. {
+        angle = 360. - angle;
+    }
+    angle
+}
+
+/// The point at `radius` from `center` in the direction `angle`, in degrees clockwise from 3 o'clock.
+pub fn polar(center: Point, radius: ScreenFactor, angle: ScreenFactor) -> Point {
+    let x = center.x as ScreenFactor + radius * sin_deg(angle + 90.);
+    let y = center.y as ScreenFactor + radius * sin_deg(angle);
+    Point::new(clamp_coord((x + 0.5) as i16), clamp_coord((y + 0.5) as i16))
+}
+
+/// Sine of `angle` in degrees.
+///
+/// Uses Bhaskara's approximation, accurate to about 0.002, since `f32::sin`
+/// needs the standard library.
+fn sin_deg(angle: ScreenFactor) -> ScreenFactor {
+    let angle = (angle % 360. + 360.) % 360.;
+    let (angle, sign) = if angle > 180. { (angle - 180., -1.) } else { (angle, 1.) };
+    let p = angle * (180. - angle);
+    sign * 4. * p / (40500. - p)
+}
+
+const DEGREES_TO_RADIANS: ScreenFactor = core::f32::consts::PI / 180.;
+
+/// Clamp to the range `0.0..=1.0`.
+fn clamp_unit(x: ScreenFactor) -> ScreenFactor {
+    x.clamp(0., 1.)
+}
+
+/// Square root to 1/16 of a pixel, since `f32::sqrt` needs the standard library.
+fn sqrt(x: ScreenFactor) -> ScreenFactor {
+    isqrt((x * 256.) as u32) as ScreenFactor / 16.
+}
+
+/// The offset at `radius` in the direction `angle`, without rounding to a pixel.
+fn polar_precise(radius: ScreenFactor, angle: ScreenFactor) -> (ScreenFactor, ScreenFactor) {
+    (radius * sin_deg(angle + 90.), radius * sin_deg(angle))
+}
+
+/// Integer square root, rounded down.
+fn isqrt(n: u32) -> u32 {
+    // one bit of the root at a time, from the highest
+    let (mut root, mut rest) = (0, n);
+    let mut bit = 1 << 30;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if rest >= root + bit {
+            rest -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    root
+}
+
+/// A gradient between two colors, along the line from `start` to `end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearGradient {
+    start: UnitPoint,
+    end: UnitPoint,
+    stops: (Color, Color),
+}
+
+impl LinearGradient {
+    /// Create a gradient from `start` to `end` of the painted shape, e.g. `UnitPoint::TOP` to `UnitPoint::BOTTOM`.
+    pub fn new(start: UnitPoint, end: UnitPoint, stops: (Color, Color)) -> LinearGradient {
+        LinearGradient { start, end, stops }
+    }
+
+    /// The color at the top (or start) and at the bottom (or end).
+    pub fn stops(&self) -> (Color, Color) {
+        self.stops
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.start.u == self.end.u
+    }
+
+    /// The color at `point`, for a gradient across `bounds`.
+    fn color_at(&self, point: Point, bounds: Rect) -> Color {
+        let width = bounds.width() as ScreenFactor;
+        let height = bounds.height() as ScreenFactor;
+        let (sx, sy) = (self.start.u * width, self.start.v * height);
+        let (dx, dy) = ((self.end.u - self.start.u) * width, (self.end.v - self.start.v) * height);
+        let length = dx * dx + dy * dy;
+        if length == 0. {
+            return self.stops.0;
+        }
+        // centre of the pixel, projected onto the gradient line
+        let px = (point.x - bounds.x0) as ScreenFactor + 0.5 - sx;
+        let py = (point.y - bounds.y0) as ScreenFactor + 0.5 - sy;
+        let t = ((px * dx + py * dy) / length).clamp(0., 1.);
+        self.stops.0.lerp(self.stops.1, t)
+    }
+}
+
+/// A brush to fill or stroke shapes with.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaintBrush {
+    Color(Color),
+    Linear(LinearGradient),
+}
+
+/// Something that can be used to paint, like a `Color` or a `LinearGradient`.
+pub trait IntoBrush {
+    fn make_brush(&self) -> PaintBrush;
+}
+
+impl IntoBrush for Color {
+    fn make_brush(&self) -> PaintBrush { PaintBrush::Color(*self) }
+}
+
+impl IntoBrush for LinearGradient {
+    fn make_brush(&self) -> PaintBrush { PaintBrush::Linear(*self) }
+}
+
+impl IntoBrush for PaintBrush {
+    fn make_brush(&self) -> PaintBrush { *self }
+}
+
+impl From<Color> for PaintBrush {
+    fn from(color: Color) -> PaintBrush { PaintBrush::Color(color) }
+}
+
+impl From<LinearGradient> for PaintBrush {
+    fn from(gradient: LinearGradient) -> PaintBrush { PaintBrush::Linear(gradient) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+
+    const RED: Color = Color::rgb8(0xff, 0, 0);
+
+    fn piet(framebuffer: &Framebuffer) -> Piet {
+        Piet::with_display(framebuffer.size(), Some(draw))
+    }
+
+    #[test]
+    fn rounded_corners_leave_out_the_corner_pixels() {
+        let fb = Framebuffer::lock();
+        piet(&fb).fill(Rect::new(0, 0, 8, 8).to_rounded_rect(4), &RED);
+        assert_eq!(fb.row(0)[..8], [0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+        assert_eq!(fb.row(4)[..8], [0xff; 8]);
+        assert_eq!(fb.row(7)[..8], [0, 0, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+    }
+
+    #[test]
+    fn strokes_are_drawn_inside_translated_and_clipped() {
+        let fb = Framebuffer::lock();
+        let mut piet = piet(&fb);
+        piet.translate(Vec2::new(2, 2));
+        piet.clip(Rect::new(0, 0, 5, 10));
+        piet.stroke(Rect::new(0, 0, 6, 6), &RED, 1);
+        assert_eq!(fb.row(2)[..9], [0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+        assert_eq!(fb.row(3)[..9], [0, 0, 0xff, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(fb.row(7)[..9], [0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+    }
+
+    #[test]
+    fn vertical_gradients_blend_from_top_to_bottom() {
+        let fb = Framebuffer::lock();
+        let gradient = LinearGradient::new(UnitPoint::TOP, UnitPoint::BOTTOM, (Color::BLACK, RED));
+        piet(&fb).fill(Rect::new(0, 0, 1, 10), &gradient);
+        assert!(fb.row(0)[0] < fb.row(5)[0] && fb.row(5)[0] < fb.row(9)[0]);
+    }
+
+    #[test]
+    fn lines_and_polylines_include_both_ends() {
+        let fb = Framebuffer::lock();
+        let mut piet = piet(&fb);
+        piet.stroke(Line::new((0., 1.), (5., 1.)), &RED, 1);
+        assert_eq!(fb.row(1)[..8], [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0]);
+
+        fb.clear();
+        let points = [Point::new(0, 0), Point::new(3, 3), Point::new(6, 0)];
+        piet.fill(PolyLine::new(&points), &RED);
+        assert_eq!(fb.row(0)[..8], [0xff, 0, 0, 0, 0, 0, 0xff, 0]);
+        assert_eq!(fb.row(3)[..8], [0, 0, 0, 0xff, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn circles_are_round_rects() {
+        let fb = Framebuffer::lock();
+        piet(&fb).fill(Circle::new((6., 6.), 3), &RED);
+        assert_eq!(fb.row(6)[..12], [0, 0, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0]);
+    }
+
+    #[test]
+    fn arcs_only_cover_their_angles() {
+        let fb = Framebuffer::lock();
+        piet(&fb).stroke(Arc::new((6., 6.), 6, 180, 360), &RED, 2);
+        assert!(fb.row(0).contains(&0xff) && fb.row(5).contains(&0xff));
+        assert!(!fb.row(7).contains(&0xff) && !fb.row(11).contains(&0xff));
+        assert_eq!(fb.row(5)[4..8], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn antialiased_arcs_blend_their_edges_into_the_background() {
+        let fb = Framebuffer::lock();
+        let mut piet = piet(&fb);
+        piet.set_background(Color::rgb8(0x20, 0, 0));
+        piet.stroke_arc_antialiased(Arc::new((6., 6.), 6, 180, 360), RED, 2, false);
+        assert_eq!(fb.row(0)[6], 0xff);
+        assert!(fb.row(0)[3] > 0x20 && fb.row(0)[3] < 0xff);
+        assert_eq!(fb.row(2)[6], 0);
+        assert_eq!(fb.row(9), [0; SIZE]);
+    }
+
+    #[test]
+    fn rounded_caps_reach_past_the_ends_of_the_arc() {
+        let fb = Framebuffer::lock();
+        let mut piet = piet(&fb);
+        piet.set_background(Color::rgb8(0x20, 0, 0));
+        piet.stroke_arc_antialiased(Arc::new((6., 6.), 6, 180, 360), RED, 2, true);
+        assert!(fb.row(6)[0] > 0x20 && fb.row(6)[11] > 0x20);
+    }
+
+    #[test]
+    fn polar_points_go_clockwise_from_3_oclock() {
+        let center = Point::new(6, 6);
+        assert_eq!(polar(center, 5., 0.), Point::new(11, 6));
+        assert_eq!(polar(center, 5., 90.), Point::new(6, 11));
+        assert_eq!(polar(center, 5., 225.), Point::new(2, 2));
+    }
+}
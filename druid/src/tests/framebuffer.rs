@@ -0,0 +1,61 @@
+//! A framebuffer for the tests of the software renderer.
+//!
+//! The display driver is a plain function, so the pixels are static and each
+//! test locks the framebuffer while it paints. Tests that also create a
+//! `Harness` must lock the framebuffer first.
+
+use crate::{Color, Rect, ScreenCoord, Size};
+use ::core::sync::atomic::{AtomicBool, Ordering};
+
+/// Width and height of the framebuffer in pixels, as tall as a bordered widget.
+pub(crate) const SIZE: usize = 48;
+
+/// Pixels painted by `draw`, as 0 or the color's red channel.
+static mut PIXELS: [[u8; SIZE]; SIZE] = [[0; SIZE]; SIZE];
+
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// Lock on the framebuffer, released when it's dropped, even if the test panics.
+pub(crate) struct Framebuffer(());
+
+impl Framebuffer {
+    /// Wait for the other tests to release the framebuffer, then clear it.
+    pub(crate) fn lock() -> Self {
+        while LOCKED.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            ::core::hint::spin_loop();
+        }
+        let framebuffer = Framebuffer(());
+        framebuffer.clear();
+        framebuffer
+    }
+
+    /// The size of the framebuffer, e.g. for the window of a `Harness`.
+    pub(crate) fn size(&self) -> Size {
+        Size::new(SIZE as ScreenCoord, SIZE as ScreenCoord)
+    }
+
+    /// The painted pixels of row `y`.
+    pub(crate) fn row(&self, y: usize) -> [u8; SIZE] {
+        unsafe { PIXELS[y] }
+    }
+
+    /// Set all the pixels back to 0.
+    pub(crate) fn clear(&self) {
+        unsafe { PIXELS = [[0; SIZE]; SIZE]; }
+    }
+}
+
+impl Drop for Framebuffer {
+    fn drop(&mut self) {
+        LOCKED.store(false, Ordering::Release);
+    }
+}
+
+/// The display driver that paints into the framebuffer.
+pub(crate) fn draw(rect: Rect, color: Color) {
+    for y in rect.y0..rect.y1 {
+        for x in rect.x0..rect.x1 {
+            unsafe { PIXELS[y as usize][x as usize] = color.as_rgb8().0; }
+        }
+    }
+}
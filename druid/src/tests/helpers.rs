@@ -12,16 +12,238 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+
 //! Helper types for test writing.
 //!
-//! This includes tools for making throwaway widgets more easily.
+//! This includes tools for making throwaway widgets more easily, and contexts ////
+//! for testing a widget's methods outside of a window. ////
 
-use std::cell::RefCell;
-use std::collections::VecDeque;
-use std::rc::Rc;
+use ::core::sync::atomic::{AtomicBool, AtomicUsize, Ordering}; ////
+////use std::cell::RefCell;
+////use std::collections::VecDeque;
+////use std::rc::Rc;
 
+use crate::core::WidgetState; ////
 use crate::*;
 
+pub(crate) type EventFn<T> = fn(&mut ModularWidget<T>, &mut EventCtx, &Event, &mut T, &Env); ////
+pub(crate) type LifeCycleFn<T> = fn(&mut ModularWidget<T>, &mut LifeCycleCtx, &LifeCycle, &T, &Env); ////
+pub(crate) type LayoutFn<T> = fn(&mut ModularWidget<T>, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size; ////
+////pub type EventFn<S, T> = dyn FnMut(&mut S, &mut EventCtx, &Event, &mut T, &Env);
+////pub type LifeCycleFn<S, T> = dyn FnMut(&mut S, &mut LifeCycleCtx, &LifeCycle, &T, &Env);
+////pub type UpdateFn<S, T> = dyn FnMut(&mut S, &mut UpdateCtx, &T, &T, &Env);
+////pub type LayoutFn<S, T> = dyn FnMut(&mut S, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size;
+////pub type PaintFn<S, T> = dyn FnMut(&mut S, &mut PaintCtx, &T, &Env);
+
+/// A widget that can be constructed from individual functions, builder-style.
+///
+/// The functions can't capture any state, so they are passed the widget, ////
+/// with its children and the log of its focus changes. ////
+#[derive(Clone)] ////
+pub struct ModularWidget<T> { ////
+////pub struct ModularWidget<S, T> {
+    id: WidgetId, ////
+    pub(crate) children: Vec<WidgetPod<T, BoxedWidget<T>>>, ////
+    focus_log: Option<&'static FocusLog>, ////
+    ////state: S,
+    event: Option<EventFn<T>>, ////
+    lifecycle: Option<LifeCycleFn<T>>, ////
+    layout: Option<LayoutFn<T>>, ////
+    ////event: Option<Box<EventFn<S, T>>>,
+    ////lifecycle: Option<Box<LifeCycleFn<S, T>>>,
+    ////update: Option<Box<UpdateFn<S, T>>>,
+    ////layout: Option<Box<LayoutFn<S, T>>>,
+    ////paint: Option<Box<PaintFn<S, T>>>,
+}
+
+#[allow(dead_code)]
+impl<T: Data> ModularWidget<T> { ////
+////impl<S, T> ModularWidget<S, T> {
+    pub(crate) fn new() -> Self { ////
+    ////pub fn new(state: S) -> Self {
+        ModularWidget {
+            id: WidgetId::next(), ////
+            children: Vec::new(), ////
+            focus_log: None, ////
+            ////state,
+            event: None,
+            lifecycle: None,
+            ////update: None,
+            layout: None,
+            ////paint: None,
+        }
+    }
+
+    /// Builder-style method to add a child, passed on to the functions in `children`.
+    pub(crate) fn with_child(mut self, child: impl Widget<T> + 'static + Clone) -> Self {
+        self.children.push(WidgetPod::new(child).boxed()).ok();
+        self
+    }
+
+    /// Builder-style method to record the `FocusChanged` events sent to the widget.
+    pub(crate) fn with_focus_log(mut self, focus_log: &'static FocusLog) -> Self {
+        self.focus_log = Some(focus_log);
+        self
+    }
+
+    pub(crate) fn event_fn(mut self, f: EventFn<T>) -> Self { ////
+    ////pub fn event_fn(
+        ////mut self,
+        ////f: impl FnMut(&mut S, &mut EventCtx, &Event, &mut T, &Env) + 'static,
+    ////) -> Self {
+        self.event = Some(f); ////
+        ////self.event = Some(Box::new(f));
+        self
+    }
+
+    pub(crate) fn lifecycle_fn(mut self, f: LifeCycleFn<T>) -> Self { ////
+    ////pub fn lifecycle_fn(
+        ////mut self,
+        ////f: impl FnMut(&mut S, &mut LifeCycleCtx, &LifeCycle, &T, &Env) + 'static,
+    ////) -> Self {
+        self.lifecycle = Some(f); ////
+        ////self.lifecycle = Some(Box::new(f));
+        self
+    }
+
+    pub(crate) fn layout_fn(mut self, f: LayoutFn<T>) -> Self { ////
+    ////pub fn layout_fn(
+        ////mut self,
+        ////f: impl FnMut(&mut S, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size + 'static,
+    ////) -> Self {
+        self.layout = Some(f); ////
+        ////self.layout = Some(Box::new(f));
+        self
+    }
+}
+
+impl<T: Data> Widget<T> for ModularWidget<T> { ////
+////impl<S, T: Data> Widget<T> for ModularWidget<S, T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Some(f) = self.event { ////
+            f(self, ctx, event, data, env) ////
+        ////if let Some(f) = self.event.as_mut() {
+            ////f(&mut self.state, ctx, event, data, env)
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let (LifeCycle::FocusChanged(focus), Some(focus_log)) = (event, self.focus_log) { ////
+            focus_log.record(*focus); ////
+        } ////
+        if let Some(f) = self.lifecycle { ////
+            f(self, ctx, event, data, env) ////
+        ////if let Some(f) = self.lifecycle.as_mut() {
+            ////f(&mut self.state, ctx, event, data, env)
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {} ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        ////if let Some(f) = self.update.as_mut() {
+            ////f(&mut self.state, ctx, old_data, data, env)
+        ////}
+    ////}
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        match self.layout { ////
+            Some(f) => f(self, ctx, bc, data, env), ////
+            None => Size::new(100, 100), ////
+        } ////
+        ////let ModularWidget {
+            ////ref mut state,
+            ////ref mut layout,
+            ////..
+        ////} = self;
+        ////layout
+            ////.as_mut()
+            ////.map(|f| f(state, ctx, bc, data, env))
+            ////.unwrap_or_else(|| Size::new(100., 100.))
+    }
+
+    fn paint(&mut self, _ctx: &mut PaintCtx, _data: &T, _env: &Env) {} ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        ////if let Some(f) = self.paint.as_mut() {
+            ////f(&mut self.state, ctx, data, env)
+        ////}
+    ////}
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Modular(self) ////
+    } ////
+}
+
+/// The focus changes of a widget.
+///
+/// Widgets can't share a `Cell` with the test, so each test keeps its logs in statics.
+pub(crate) struct FocusLog {
+    changes: AtomicUsize,
+    focused: AtomicBool,
+}
+
+impl FocusLog {
+    pub(crate) const fn new() -> Self {
+        FocusLog { changes: AtomicUsize::new(0), focused: AtomicBool::new(false) }
+    }
+
+    fn record(&self, focused: bool) {
+        self.focused.store(focused, Ordering::Relaxed);
+        self.changes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The focus change since the last call, if any. Panics if the focus changed more than once.
+    pub(crate) fn take(&self) -> Option<bool> {
+        match self.changes.swap(0, Ordering::Relaxed) {
+            0 => None,
+            1 => Some(self.focused.load(Ordering::Relaxed)),
+            changes => panic!("focus changed {} times", changes),
+        }
+    }
+}
+
+/// An `EventCtx` for calling a widget's methods directly, outside of a window.
+pub(crate) fn event_ctx() -> EventCtx {
+    EventCtx {
+        state: ContextState(),
+        widget_state: WidgetState::new(WidgetId::reserved(0)),
+        is_handled: false,
+        is_root: false,
+    }
+}
+
+/// A `LifeCycleCtx` for calling a widget's methods directly, outside of a window.
+pub(crate) fn lifecycle_ctx() -> LifeCycleCtx {
+    LifeCycleCtx {
+        widget_state: WidgetState::new(WidgetId::reserved(0)),
+        state: ContextState(),
+    }
+}
+
+/// A `LayoutCtx` for calling a widget's methods directly, outside of a window.
+pub(crate) fn layout_ctx() -> LayoutCtx {
+    LayoutCtx {
+        state: ContextState(),
+        widget_state: WidgetState::new(WidgetId::reserved(0)),
+        mouse_pos: None,
+    }
+}
+
+/// A `PaintCtx` for a screen of `size` without a display, outside of a window.
+pub(crate) fn paint_ctx(size: Size) -> PaintCtx {
+    static STATE: ContextState = ContextState();
+    PaintCtx {
+        state: &STATE,
+        widget_state: WidgetState::new(WidgetId::reserved(0)),
+        render_ctx: Piet::new(size),
+        z_ops: Vec::new(),
+        region: Region::from(size.to_rect()),
+        depth: 0,
+    }
+}
+
+/* //// No closures, `Box` or `Rc` for replacing children or recording widgets
 // taken from the matches crate; useful for the Recorder widget.
 #[macro_export]
 macro_rules! assert_matches {
@@ -33,26 +255,8 @@ macro_rules! assert_matches {
      }
  }
 
-pub type EventFn<S, T> = dyn FnMut(&mut S, &mut EventCtx, &Event, &mut T, &Env);
-pub type LifeCycleFn<S, T> = dyn FnMut(&mut S, &mut LifeCycleCtx, &LifeCycle, &T, &Env);
-pub type UpdateFn<S, T> = dyn FnMut(&mut S, &mut UpdateCtx, &T, &T, &Env);
-pub type LayoutFn<S, T> = dyn FnMut(&mut S, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size;
-pub type PaintFn<S, T> = dyn FnMut(&mut S, &mut PaintCtx, &T, &Env);
-
 pub const REPLACE_CHILD: Selector = Selector::new("druid-test.replace-child");
 
-/// A widget that can be constructed from individual functions, builder-style.
-///
-/// This widget is generic over its state, which is passed in at construction time.
-pub struct ModularWidget<S, T> {
-    state: S,
-    event: Option<Box<EventFn<S, T>>>,
-    lifecycle: Option<Box<LifeCycleFn<S, T>>>,
-    update: Option<Box<UpdateFn<S, T>>>,
-    layout: Option<Box<LayoutFn<S, T>>>,
-    paint: Option<Box<PaintFn<S, T>>>,
-}
-
 /// A widget that can replace its child on command
 pub struct ReplaceChild<T> {
     inner: WidgetPod<T, Box<dyn Widget<T>>>,
@@ -111,95 +315,6 @@ pub trait TestWidgetExt<T: Data>: Widget<T> + Sized + 'static {
 
 impl<T: Data, W: Widget<T> + 'static> TestWidgetExt<T> for W {}
 
-#[allow(dead_code)]
-impl<S, T> ModularWidget<S, T> {
-    pub fn new(state: S) -> Self {
-        ModularWidget {
-            state,
-            event: None,
-            lifecycle: None,
-            update: None,
-            layout: None,
-            paint: None,
-        }
-    }
-
-    pub fn event_fn(
-        mut self,
-        f: impl FnMut(&mut S, &mut EventCtx, &Event, &mut T, &Env) + 'static,
-    ) -> Self {
-        self.event = Some(Box::new(f));
-        self
-    }
-
-    pub fn lifecycle_fn(
-        mut self,
-        f: impl FnMut(&mut S, &mut LifeCycleCtx, &LifeCycle, &T, &Env) + 'static,
-    ) -> Self {
-        self.lifecycle = Some(Box::new(f));
-        self
-    }
-
-    pub fn update_fn(
-        mut self,
-        f: impl FnMut(&mut S, &mut UpdateCtx, &T, &T, &Env) + 'static,
-    ) -> Self {
-        self.update = Some(Box::new(f));
-        self
-    }
-
-    pub fn layout_fn(
-        mut self,
-        f: impl FnMut(&mut S, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size + 'static,
-    ) -> Self {
-        self.layout = Some(Box::new(f));
-        self
-    }
-
-    pub fn paint_fn(mut self, f: impl FnMut(&mut S, &mut PaintCtx, &T, &Env) + 'static) -> Self {
-        self.paint = Some(Box::new(f));
-        self
-    }
-}
-
-impl<S, T: Data> Widget<T> for ModularWidget<S, T> {
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
-        if let Some(f) = self.event.as_mut() {
-            f(&mut self.state, ctx, event, data, env)
-        }
-    }
-
-    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
-        if let Some(f) = self.lifecycle.as_mut() {
-            f(&mut self.state, ctx, event, data, env)
-        }
-    }
-
-    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
-        if let Some(f) = self.update.as_mut() {
-            f(&mut self.state, ctx, old_data, data, env)
-        }
-    }
-
-    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
-        let ModularWidget {
-            ref mut state,
-            ref mut layout,
-            ..
-        } = self;
-        layout
-            .as_mut()
-            .map(|f| f(state, ctx, bc, data, env))
-            .unwrap_or_else(|| Size::new(100., 100.))
-    }
-
-    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        if let Some(f) = self.paint.as_mut() {
-            f(&mut self.state, ctx, data, env)
-        }
-    }
-}
-
 impl<T: Data> ReplaceChild<T> {
     pub fn new<W: Widget<T> + 'static>(
         inner: impl Widget<T> + 'static,
@@ -353,3 +468,4 @@ pub fn widget_id6() -> (WidgetId, WidgetId, WidgetId, WidgetId, WidgetId, Widget
         WidgetId::next(),
     )
 }
+*/ ////
@@ -0,0 +1,378 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests related to layout.
+
+////use float_cmp::approx_eq;
+use ::core::cell::Cell; ////
+
+use super::*;
+
+#[test]
+fn simple_layout() {
+    const BOX_WIDTH: ScreenCoord = 200; ////
+    const PADDING: ScreenCoord = 10; ////
+    ////const BOX_WIDTH: f64 = 200.;
+    ////const PADDING: f64 = 10.;
+
+    let id_1 = Cell::new(None); ////
+    ////let id_1 = WidgetId::next();
+
+    let widget = || { ////
+        let split = Split::columns(Label::new("hi"), Label::new("there")); ////
+        let padding = Padding::new(PADDING, SizedBox::new(split).width(BOX_WIDTH).height(BOX_WIDTH)); ////
+        id_1.set(padding.id()); ////
+        Align::centered(padding) ////
+    }; ////
+    ////let widget = Split::columns(Label::new("hi"), Label::new("there"))
+        ////.fix_size(BOX_WIDTH, BOX_WIDTH)
+        ////.padding(10.0)
+        ////.with_id(id_1)
+        ////.center();
+
+    Harness::create_simple((), widget, |harness| { ////
+    ////Harness::create_simple(true, widget, |harness| {
+        harness.send_initial_events();
+        harness.just_layout();
+        let state = harness.get_state(id_1.get().unwrap()); ////
+        ////let state = harness.get_state(id_1);
+        assert_eq!( ////
+        ////approx_eq!(
+            ////f64,
+            state.layout_rect().x0,
+            ((DEFAULT_SIZE.width - BOX_WIDTH) / 2) - PADDING ////
+            ////((DEFAULT_SIZE.width - BOX_WIDTH) / 2.) - PADDING
+        );
+    })
+}
+
+#[test]
+fn row_column() {
+    let ids = Cell::new(None); ////
+    ////let (id1, id2, id3, id4, id5, id6) = widget_id6();
+    let widget = || { ////
+        let expanded = || SizedBox::<()>::empty().expand(); ////
+        let (b1, b2, b3, b4, b5, b6) = (expanded(), expanded(), expanded(), expanded(), expanded(), expanded()); ////
+        ids.set(Some((b1.id().unwrap(), b2.id().unwrap(), b3.id().unwrap(), b5.id().unwrap()))); ////
+        Flex::row()
+            .must_fill_main_axis(true)
+            .with_flex_child(
+                Flex::column()
+                    .with_flex_child(b1, 1.0) ////
+                    .with_flex_child(b2, 1.0), ////
+                    ////.with_flex_child(SizedBox::empty().expand().with_id(id1), 1.0)
+                    ////.with_flex_child(SizedBox::empty().expand().with_id(id2), 1.0),
+                1.0,
+            )
+            .with_flex_child(
+                SizedBox::new( ////
+                    Flex::column()
+                        .with_flex_child(b3, 1.0) ////
+                        .with_flex_child(b4, 1.0) ////
+                        .with_flex_child(b5, 1.0) ////
+                        .with_flex_child(b6, 1.0) ////
+                        ////.with_flex_child(SizedBox::empty().expand().with_id(id3), 1.0)
+                        ////.with_flex_child(SizedBox::empty().expand().with_id(id4), 1.0)
+                        ////.with_flex_child(SizedBox::empty().expand().with_id(id5), 1.0)
+                        ////.with_flex_child(SizedBox::empty().expand().with_id(id6), 1.0)
+                ) ////
+                .expand_width(),
+                1.0,
+            )
+    }; ////
+
+    Harness::create_simple((), widget, |harness| {
+        let (id1, id2, id3, id5) = ids.get().unwrap(); ////
+        harness.send_initial_events();
+        harness.just_layout();
+        let state1 = harness.get_state(id1);
+        assert_eq!(state1.layout_rect().origin(), Point::ORIGIN); ////
+        let state2 = harness.get_state(id2);
+        assert_eq!(state2.layout_rect().origin(), Point::new(0, 120)); ////
+        ////assert_eq!(state2.layout_rect().origin(), Point::new(0., 200.));
+        let state3 = harness.get_state(id3);
+        assert_eq!(state3.layout_rect().origin(), Point::ORIGIN); ////
+        let state5 = harness.get_state(id5);
+        assert_eq!(state5.layout_rect().origin(), Point::new(0, 120)); ////
+        ////assert_eq!(state5.layout_rect().origin(), Point::new(0., 200.));
+    })
+}
+
+#[test]
+fn simple_paint_rect() {
+    let ids = Cell::new(None); ////
+    ////let (id1, id2) = widget_id2();
+
+    let widget = || { ////
+        let modular = ModularWidget::<()>::new() ////
+        ////let widget = ModularWidget::<(), ()>::new(())
+            .layout_fn(|_, ctx, bc, _, _| {
+                // this widget paints twenty points below its layout bounds, ////
+                // as a `ScreenCoord` can't go above its parent ////
+                ctx.set_paint_insets(Insets::new(0, 0, 0, 20)); ////
+                ////// this widget paints twenty points above below its layout bounds
+                ////ctx.set_paint_insets(Insets::uniform_xy(0., 20.));
+                bc.max()
+            });
+        let sized = SizedBox::new(modular).width(100).height(100); ////
+        let id1 = sized.id().unwrap(); ////
+        let padding = Padding::new(10, sized); ////
+        ids.set(Some((id1, padding.id().unwrap()))); ////
+        Align::centered(Container::new(padding).background(Color::BLACK)) ////
+    }; ////
+        ////.with_id(id1)
+        ////.fix_size(100., 100.)
+        ////.padding(10.0)
+        ////.with_id(id2)
+        ////.background(Color::BLACK)
+        ////.center();
+
+    Harness::create_simple((), widget, |harness| {
+        let (id1, id2) = ids.get().unwrap(); ////
+        harness.send_initial_events();
+        harness.just_layout();
+
+        let state = harness.get_state(id1);
+
+        // offset by padding
+        assert_eq!(state.layout_rect().origin(), Point::new(10, 10)); ////
+        ////assert_eq!(state.layout_rect().origin(), Point::new(10., 10.,));
+        // offset by padding, as the paint insets are below ////
+        assert_eq!(state.paint_rect().origin(), Point::new(10, 10)); ////
+        ////// offset by padding, but then inset by paint insets
+        ////assert_eq!(state.paint_rect().origin(), Point::new(10., -10.,));
+        // layout size is fixed
+        assert_eq!(state.layout_rect().size(), Size::new(100, 100)); ////
+        ////assert_eq!(state.layout_rect().size(), Size::new(100., 100.,));
+        // paint size is modified by insets
+        assert_eq!(state.paint_rect().size(), Size::new(100, 120)); ////
+        ////assert_eq!(state.paint_rect().size(), Size::new(100., 140.,));
+
+        // now does the container widget correctly propogate the child's paint rect?
+        let state = harness.get_state(id2);
+
+        assert_eq!(state.layout_rect().origin(), Point::ORIGIN); ////
+        assert_eq!(state.paint_rect().origin(), Point::ORIGIN); ////
+        ////// offset by padding, but then inset by paint insets
+        ////assert_eq!(state.paint_rect().origin(), Point::new(0., -10.,));
+        // 100 + 10 on each side
+        assert_eq!(state.layout_rect().size(), Size::new(120, 120)); ////
+        ////assert_eq!(state.layout_rect().size(), Size::new(120., 120.,));
+        // paint size is modified by the insets beyond the padding ////
+        assert_eq!(state.paint_rect().size(), Size::new(120, 130)); ////
+        ////// paint size is modified by insets
+        ////assert_eq!(state.paint_rect().size(), Size::new(120., 140.,));
+    })
+}
+
+#[test]
+/// Does a Flex correctly compute the union of multiple children's paint rects?
+fn flex_paint_rect_overflow() {
+    let id = Cell::new(None); ////
+    ////let id = WidgetId::next();
+
+    // The children fill their share of the Flex, as there aren't enough ////
+    // static widgets to wrap each of them in an expanded SizedBox. ////
+    let widget = || { ////
+        let flex = Flex::row()
+            .with_flex_child(
+                ModularWidget::new()
+                    .layout_fn(|_, ctx, bc, _, _| {
+                        ctx.set_paint_insets(Insets::new(20, 0, 0, 0)); ////
+                        ////ctx.set_paint_insets(Insets::new(20., 0., 0., 0.));
+                        bc.max() ////
+                        ////bc.constrain(Size::new(10., 10.))
+                    }),
+                    ////.expand(),
+                1.0,
+            )
+            .with_flex_child(
+                ModularWidget::new()
+                    .layout_fn(|_, ctx, bc, _, _| {
+                        ctx.set_paint_insets(Insets::new(0, 20, 0, 0)); ////
+                        ////ctx.set_paint_insets(Insets::new(0., 20., 0., 0.));
+                        bc.max() ////
+                        ////bc.constrain(Size::new(10., 10.))
+                    }),
+                    ////.expand(),
+                1.0,
+            )
+            .with_flex_child(
+                ModularWidget::new()
+                    .layout_fn(|_, ctx, bc, _, _| {
+                        ctx.set_paint_insets(Insets::new(0, 0, 0, 20)); ////
+                        ////ctx.set_paint_insets(Insets::new(0., 0., 0., 20.));
+                        bc.max() ////
+                        ////bc.constrain(Size::new(10., 10.))
+                    }),
+                    ////.expand(),
+                1.0,
+            )
+            .with_flex_child(
+                ModularWidget::new()
+                    .layout_fn(|_, ctx, bc, _, _| {
+                        ctx.set_paint_insets(Insets::new(0, 0, 20, 0)); ////
+                        ////ctx.set_paint_insets(Insets::new(0., 0., 20., 0.));
+                        bc.max() ////
+                        ////bc.constrain(Size::new(10., 10.))
+                    }),
+                    ////.expand(),
+                1.0,
+            );
+        let sized = SizedBox::new(flex).height(200); ////
+        id.set(sized.id()); ////
+        Align::centered(Padding::new(10, sized)) ////
+    }; ////
+        ////.with_id(id)
+        ////.fix_height(200.)
+        ////.padding(10.)
+        ////.center();
+
+    Harness::create_simple((), widget, |harness| {
+        ////harness.set_initial_size(Size::new(300., 300.));
+        harness.send_initial_events();
+        harness.just_layout();
+
+        let state = harness.get_state(id.get().unwrap()); ////
+        ////let state = harness.get_state(id);
+        assert_eq!(state.layout_rect().origin(), Point::new(10, 10)); ////
+        assert_eq!(state.paint_rect().origin(), Point::new(10, 10)); ////
+        ////assert_eq!(state.layout_rect().origin(), Point::new(10., 10.,));
+        ////assert_eq!(state.paint_rect().origin(), Point::new(-10., -10.,));
+
+        // each of our children insets 20. on a different side; a `ScreenCoord` ////
+        // can't go above or left of the Flex, so their union is 20 below and right. ////
+        let expected_paint_rect = state.layout_rect() + Insets::new(0, 0, 20, 20); ////
+        ////// each of our children insets 20. on a different side; their union
+        ////// is a uniform 20. inset.
+        ////let expected_paint_rect = state.layout_rect() + Insets::uniform(20.);
+        assert_eq!(state.paint_rect().size(), expected_paint_rect.size());
+    })
+}
+
+#[test]
+/// Does a bounded Grid share the space between its cells, and center each child in its cell?
+fn grid_shares_the_space() {
+    let ids = Cell::new(None);
+    let widget = || {
+        let cell = || SizedBox::<()>::empty().width(20).height(10);
+        let (corner, middle) = (cell(), cell());
+        ids.set(Some((corner.id().unwrap(), middle.id().unwrap())));
+        let mut grid = Grid::new(3, 3).with_gap(6);
+        grid.add_child(corner, 0, 0);
+        grid.add_child(middle, 1, 2);
+        // A full 3 x 3 grid fits in the static widgets
+        for (row, column) in [(0, 1), (0, 2), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)] {
+            grid.add_child(cell(), row, column);
+        }
+        Align::centered(grid)
+    };
+
+    Harness::create_simple((), widget, |harness| {
+        let (corner, middle) = ids.get().unwrap();
+        harness.send_initial_events();
+        harness.just_layout();
+        // Each cell is (240 - 2 * 6) / 3 = 76 wide and high, 82 apart
+        assert_eq!(harness.get_state(corner).layout_rect(), Rect::new(28, 33, 48, 43));
+        assert_eq!(harness.get_state(middle).layout_rect(), Rect::new(164 + 28, 82 + 33, 164 + 48, 82 + 43));
+    })
+}
+
+#[test]
+/// Are the cells of an unbounded Grid just as big as its biggest child?
+fn grid_fits_its_children() {
+    let ids = Cell::new(None);
+    let widget = || {
+        let spanning = SizedBox::<()>::empty().width(20).height(26);
+        let spanning_id = spanning.id().unwrap();
+        let grid = Grid::new(3, 2)
+            .with_row_gap(6)
+            .with_child(SizedBox::empty().width(20).height(10), 0, 0)
+            .with_spanning_child(spanning, 1, 1, 2, 1);
+        ids.set(Some((grid.id().unwrap(), spanning_id)));
+        Scroll::new(grid)
+    };
+
+    Harness::create_simple((), widget, |harness| {
+        let (grid, spanning) = ids.get().unwrap();
+        harness.send_initial_events();
+        harness.just_layout();
+        // Two rows of 10 and a gap of 6 cover the spanning child, so the rows are 10 high,
+        // and the columns share the width of the screen
+        assert_eq!(harness.get_state(grid).layout_rect().height(), 3 * 10 + 2 * 6);
+        assert_eq!(harness.get_state(spanning).layout_rect(), Rect::new(170, 16, 190, 42));
+    });
+
+    let ids = Cell::new(None);
+    let widget = || {
+        let tall = SizedBox::<()>::empty().width(20).height(255);
+        let tall_id = tall.id().unwrap();
+        let grid = Grid::new(1, 2).with_child(tall, 0, 0);
+        ids.set(Some((grid.id().unwrap(), tall_id)));
+        Scroll::new(grid)
+    };
+
+    Harness::create_simple((), widget, |harness| {
+        let (grid, tall) = ids.get().unwrap();
+        harness.send_initial_events();
+        harness.just_layout();
+        // The tallest possible child fits its cell exactly
+        assert_eq!(harness.get_state(grid).layout_rect().height(), 255);
+        assert_eq!(harness.get_state(tall).layout_rect(), Rect::new(50, 0, 70, 255));
+    })
+}
+
+#[test]
+/// Do a Stepper and a Slider fill a window of any size, and take touches where they paint?
+fn stepper_and_slider_fill_the_window() {
+    let id = Cell::new(None);
+    let widget = || {
+        let stepper = Stepper::new().with_range(0., 10.);
+        id.set(stepper.id());
+        stepper
+    };
+    Harness::create_simple(5u8, widget, |harness| {
+        harness.set_initial_size(Size::new(36, 100));
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id.get().unwrap()).layout_rect(), Rect::new(0, 0, 36, 100));
+        // The up arrow is painted above the middle, past the default height of the stepper
+        let touch = MouseEvent::new(Point::new(18, 40));
+        harness.event(Event::MouseDown(touch.clone()));
+        harness.event(Event::MouseUp(touch));
+        assert_eq!(*harness.data(), 6);
+        let touch = MouseEvent::new(Point::new(18, 60));
+        harness.event(Event::MouseDown(touch.clone()));
+        harness.event(Event::MouseUp(touch));
+        assert_eq!(*harness.data(), 5);
+    });
+
+    let id = Cell::new(None);
+    let widget = || {
+        let slider = Slider::new().with_range(0., 10.).with_step(1.);
+        id.set(slider.id());
+        slider
+    };
+    Harness::create_simple(0f32, widget, |harness| {
+        harness.set_initial_size(Size::new(120, 36));
+        harness.send_initial_events();
+        harness.just_layout();
+        assert_eq!(harness.get_state(id.get().unwrap()).layout_rect(), Rect::new(0, 0, 120, 36));
+        // The track ends at the right edge of the narrow window
+        let touch = MouseEvent::new(Point::new(119, 18));
+        harness.event(Event::MouseDown(touch.clone()));
+        harness.event(Event::MouseUp(touch));
+        assert_eq!(*harness.data(), 10.);
+    });
+}
@@ -14,21 +14,25 @@
 
 //! Additional unit tests that cross file or module boundaries.
 
+pub(crate) mod framebuffer; ////
 pub(crate) mod harness;
-mod helpers;
+pub(crate) mod helpers; ////
+////mod helpers;
 mod layout_tests;
 
-use std::cell::Cell;
-use std::env;
-use std::fs;
-use std::rc::Rc;
+use ::core::cell::Cell; ////
+////use std::cell::Cell;
+////use std::env;
+////use std::fs;
+////use std::rc::Rc;
 
 use crate::widget::*;
 use crate::*;
 use harness::*;
 use helpers::*;
-use kurbo::Vec2;
+////use kurbo::Vec2;
 
+/* //// No filesystem or recording widgets yet
 /// This function creates a temporary directory and returns a PathBuf to it.
 ///
 /// This directory will be created relative to the executable and will therefor
@@ -148,14 +152,20 @@ fn propogate_hot() {
         assert!(root_rec.is_empty() && padding_rec.is_empty() && button_rec.is_empty());
     });
 }
+*/ ////
+
 #[test]
 fn take_focus() {
     const TAKE_FOCUS: Selector = Selector::new("druid-tests.take-focus");
 
     /// A widget that takes focus when sent a particular command.
-    /// The widget records focus change events into the inner cell.
-    fn make_focus_taker(inner: Rc<Cell<Option<bool>>>) -> impl Widget<bool> {
-        ModularWidget::new(inner)
+    /// The widget records focus change events into its log. ////
+    ////The widget records focus change events into the inner cell.
+    fn make_focus_taker(focus_log: &'static FocusLog) -> ModularWidget<()> { ////
+    ////fn make_focus_taker(inner: Rc<Cell<Option<bool>>>) -> impl Widget<bool> {
+        ModularWidget::new() ////
+            .with_focus_log(focus_log) ////
+        ////ModularWidget::new(inner)
             .event_fn(|_, ctx, event, _data, _env| {
                 if let Event::Command(cmd) = event {
                     if cmd.is(TAKE_FOCUS) {
@@ -163,55 +173,78 @@ fn take_focus() {
                     }
                 }
             })
-            .lifecycle_fn(|is_focused, _, event, _data, _env| {
-                if let LifeCycle::FocusChanged(focus) = event {
-                    is_focused.set(Some(*focus));
-                }
-            })
+            ////.lifecycle_fn(|is_focused, _, event, _data, _env| {
+                ////if let LifeCycle::FocusChanged(focus) = event {
+                    ////is_focused.set(Some(*focus));
+                ////}
+            ////})
     }
 
-    let (id_1, id_2, _id_3) = widget_id3();
+    let ids = Cell::new(None); ////
+    ////let (id_1, id_2, _id_3) = widget_id3();
 
     // we use these so that we can check the widget's internal state
-    let left_focus: Rc<Cell<Option<bool>>> = Default::default();
-    let right_focus: Rc<Cell<Option<bool>>> = Default::default();
-    assert!(left_focus.get().is_none());
-
-    let left = make_focus_taker(left_focus.clone()).with_id(id_1);
-    let right = make_focus_taker(right_focus.clone()).with_id(id_2);
-    let app = Split::columns(left, right).padding(5.0);
-    let data = true;
-
-    Harness::create_simple(data, app, |harness| {
+    static LEFT_FOCUS: FocusLog = FocusLog::new(); ////
+    static RIGHT_FOCUS: FocusLog = FocusLog::new(); ////
+    ////let left_focus: Rc<Cell<Option<bool>>> = Default::default();
+    ////let right_focus: Rc<Cell<Option<bool>>> = Default::default();
+    ////assert!(left_focus.get().is_none());
+
+    let app = || { ////
+        let (left, right) = (make_focus_taker(&LEFT_FOCUS), make_focus_taker(&RIGHT_FOCUS)); ////
+        ids.set(Some((left.id().unwrap(), right.id().unwrap()))); ////
+        Padding::new(5, Split::columns(left, right)) ////
+    }; ////
+    ////let left = make_focus_taker(left_focus.clone()).with_id(id_1);
+    ////let right = make_focus_taker(right_focus.clone()).with_id(id_2);
+    ////let app = Split::columns(left, right).padding(5.0);
+    ////let data = true;
+
+    Harness::create_simple((), app, |harness| { ////
+    ////Harness::create_simple(data, app, |harness| {
+        let (id_1, id_2) = ids.get().unwrap(); ////
         harness.send_initial_events();
         // nobody should have focus
-        assert!(left_focus.get().is_none());
-        assert!(right_focus.get().is_none());
+        assert!(LEFT_FOCUS.take().is_none()); ////
+        assert!(RIGHT_FOCUS.take().is_none()); ////
+        ////assert!(left_focus.get().is_none());
+        ////assert!(right_focus.get().is_none());
 
         // this is sent to all widgets; the last widget to request focus should get it
         harness.submit_command(TAKE_FOCUS, None);
         assert_eq!(harness.window().focus, Some(id_2));
-        assert_eq!(left_focus.get(), None);
-        assert_eq!(right_focus.get(), Some(true));
+        assert_eq!(LEFT_FOCUS.take(), None); ////
+        assert_eq!(RIGHT_FOCUS.take(), Some(true)); ////
+        ////assert_eq!(left_focus.get(), None);
+        ////assert_eq!(right_focus.get(), Some(true));
 
         // this is sent to all widgets; the last widget to request focus should still get it
         // NOTE: This tests siblings in particular, so careful when moving away from Split.
         harness.submit_command(TAKE_FOCUS, None);
         assert_eq!(harness.window().focus, Some(id_2));
-        assert_eq!(left_focus.get(), None);
-        assert_eq!(right_focus.get(), Some(true));
+        // so neither of them is told that its focus changed ////
+        assert_eq!(LEFT_FOCUS.take(), None); ////
+        assert_eq!(RIGHT_FOCUS.take(), None); ////
+        ////assert_eq!(left_focus.get(), None);
+        ////assert_eq!(right_focus.get(), Some(true));
 
         // this is sent to a specific widget; it should get focus
-        harness.submit_command(TAKE_FOCUS, id_1);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_1)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_1);
         assert_eq!(harness.window().focus, Some(id_1));
-        assert_eq!(left_focus.get(), Some(true));
-        assert_eq!(right_focus.get(), Some(false));
+        assert_eq!(LEFT_FOCUS.take(), Some(true)); ////
+        assert_eq!(RIGHT_FOCUS.take(), Some(false)); ////
+        ////assert_eq!(left_focus.get(), Some(true));
+        ////assert_eq!(right_focus.get(), Some(false));
 
         // this is sent to a specific widget; it should get focus
-        harness.submit_command(TAKE_FOCUS, id_2);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_2)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_2);
         assert_eq!(harness.window().focus, Some(id_2));
-        assert_eq!(left_focus.get(), Some(false));
-        assert_eq!(right_focus.get(), Some(true));
+        assert_eq!(LEFT_FOCUS.take(), Some(false)); ////
+        assert_eq!(RIGHT_FOCUS.take(), Some(true)); ////
+        ////assert_eq!(left_focus.get(), Some(false));
+        ////assert_eq!(right_focus.get(), Some(true));
     })
 }
 
@@ -221,9 +254,13 @@ fn focus_changed() {
     const ALL_TAKE_FOCUS_BEFORE: Selector = Selector::new("druid-tests.take-focus-before");
     const ALL_TAKE_FOCUS_AFTER: Selector = Selector::new("druid-tests.take-focus-after");
 
-    fn make_focus_container(children: Vec<WidgetPod<(), Box<dyn Widget<()>>>>) -> impl Widget<()> {
-        ModularWidget::new(children)
-            .event_fn(|children, ctx, event, data, env| {
+    fn make_focus_container(focus_log: &'static FocusLog) -> ModularWidget<()> { ////
+    ////fn make_focus_container(children: Vec<WidgetPod<(), Box<dyn Widget<()>>>>) -> impl Widget<()> {
+        ModularWidget::new() ////
+            .with_focus_log(focus_log) ////
+        ////ModularWidget::new(children)
+            .event_fn(|widget, ctx, event, data, env| { ////
+            ////.event_fn(|children, ctx, event, data, env| {
                 if let Event::Command(cmd) = event {
                     if cmd.is(TAKE_FOCUS) {
                         ctx.request_focus();
@@ -234,7 +271,8 @@ fn focus_changed() {
                         ctx.request_focus();
                     }
                 }
-                children
+                widget.children ////
+                ////children
                     .iter_mut()
                     .for_each(|a| a.event(ctx, event, data, env));
                 if let Event::Command(cmd) = event {
@@ -243,82 +281,105 @@ fn focus_changed() {
                     }
                 }
             })
-            .lifecycle_fn(|children, ctx, event, data, env| {
-                children
+            .lifecycle_fn(|widget, ctx, event, data, env| { ////
+            ////.lifecycle_fn(|children, ctx, event, data, env| {
+                widget.children ////
+                ////children
                     .iter_mut()
                     .for_each(|a| a.lifecycle(ctx, event, data, env));
             })
     }
 
-    let a_rec = Recording::default();
-    let b_rec = Recording::default();
-    let c_rec = Recording::default();
-
-    let (id_a, id_b, id_c) = widget_id3();
-
-    // a contains b which contains c
-    let c = make_focus_container(vec![]).record(&c_rec).with_id(id_c);
-    let b = make_focus_container(vec![WidgetPod::new(c).boxed()])
-        .record(&b_rec)
-        .with_id(id_b);
-    let a = make_focus_container(vec![WidgetPod::new(b).boxed()])
-        .record(&a_rec)
-        .with_id(id_a);
-
-    let f = |a| match a {
-        Record::L(LifeCycle::FocusChanged(c)) => Some(c),
-        _ => None,
-    };
-    let no_change = |a: &Recording| a.drain().filter_map(f).count() == 0;
-    let changed = |a: &Recording, b| a.drain().filter_map(f).eq(std::iter::once(b));
+    static A_REC: FocusLog = FocusLog::new(); ////
+    static B_REC: FocusLog = FocusLog::new(); ////
+    static C_REC: FocusLog = FocusLog::new(); ////
+    ////let a_rec = Recording::default();
+    ////let b_rec = Recording::default();
+    ////let c_rec = Recording::default();
+
+    let ids = Cell::new(None); ////
+    ////let (id_a, id_b, id_c) = widget_id3();
+
+    let a = || { ////
+        // a contains b which contains c
+        let c = make_focus_container(&C_REC); ////
+        let id_c = c.id().unwrap(); ////
+        let b = make_focus_container(&B_REC).with_child(c); ////
+        let id_b = b.id().unwrap(); ////
+        let a = make_focus_container(&A_REC).with_child(b); ////
+        ids.set(Some((a.id().unwrap(), id_b, id_c))); ////
+        a ////
+    }; ////
+    ////let c = make_focus_container(vec![]).record(&c_rec).with_id(id_c);
+    ////let b = make_focus_container(vec![WidgetPod::new(c).boxed()])
+        ////.record(&b_rec)
+        ////.with_id(id_b);
+    ////let a = make_focus_container(vec![WidgetPod::new(b).boxed()])
+        ////.record(&a_rec)
+        ////.with_id(id_a);
+
+    ////let f = |a| match a {
+        ////Record::L(LifeCycle::FocusChanged(c)) => Some(c),
+        ////_ => None,
+    ////};
+    let no_change = |a: &FocusLog| a.take().is_none(); ////
+    let changed = |a: &FocusLog, b| a.take() == Some(b); ////
+    ////let no_change = |a: &Recording| a.drain().filter_map(f).count() == 0;
+    ////let changed = |a: &Recording, b| a.drain().filter_map(f).eq(std::iter::once(b));
 
     Harness::create_simple((), a, |harness| {
+        let (id_a, id_b, id_c) = ids.get().unwrap(); ////
         harness.send_initial_events();
 
         // focus none -> a
-        harness.submit_command(TAKE_FOCUS, id_a);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_a)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_a);
         assert_eq!(harness.window().focus, Some(id_a));
-        assert!(changed(&a_rec, true));
-        assert!(no_change(&b_rec));
-        assert!(no_change(&c_rec));
+        assert!(changed(&A_REC, true)); ////
+        assert!(no_change(&B_REC)); ////
+        assert!(no_change(&C_REC)); ////
 
         // focus a -> b
-        harness.submit_command(TAKE_FOCUS, id_b);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_b)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_b);
         assert_eq!(harness.window().focus, Some(id_b));
-        assert!(changed(&a_rec, false));
-        assert!(changed(&b_rec, true));
-        assert!(no_change(&c_rec));
+        assert!(changed(&A_REC, false)); ////
+        assert!(changed(&B_REC, true)); ////
+        assert!(no_change(&C_REC)); ////
 
         // focus b -> c
-        harness.submit_command(TAKE_FOCUS, id_c);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_c)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_c);
         assert_eq!(harness.window().focus, Some(id_c));
-        assert!(no_change(&a_rec));
-        assert!(changed(&b_rec, false));
-        assert!(changed(&c_rec, true));
+        assert!(no_change(&A_REC)); ////
+        assert!(changed(&B_REC, false)); ////
+        assert!(changed(&C_REC, true)); ////
 
         // focus c -> a
-        harness.submit_command(TAKE_FOCUS, id_a);
+        harness.submit_command(TAKE_FOCUS, Target::from(id_a)); ////
+        ////harness.submit_command(TAKE_FOCUS, id_a);
         assert_eq!(harness.window().focus, Some(id_a));
-        assert!(changed(&a_rec, true));
-        assert!(no_change(&b_rec));
-        assert!(changed(&c_rec, false));
+        assert!(changed(&A_REC, true)); ////
+        assert!(no_change(&B_REC)); ////
+        assert!(changed(&C_REC, false)); ////
 
         // all focus before passing down the event
         harness.submit_command(ALL_TAKE_FOCUS_BEFORE, None);
         assert_eq!(harness.window().focus, Some(id_c));
-        assert!(changed(&a_rec, false));
-        assert!(no_change(&b_rec));
-        assert!(changed(&c_rec, true));
+        assert!(changed(&A_REC, false)); ////
+        assert!(no_change(&B_REC)); ////
+        assert!(changed(&C_REC, true)); ////
 
         // all focus after passing down the event
         harness.submit_command(ALL_TAKE_FOCUS_AFTER, None);
         assert_eq!(harness.window().focus, Some(id_a));
-        assert!(changed(&a_rec, true));
-        assert!(no_change(&b_rec));
-        assert!(changed(&c_rec, false));
+        assert!(changed(&A_REC, true)); ////
+        assert!(no_change(&B_REC)); ////
+        assert!(changed(&C_REC, false)); ////
     })
 }
 
+/* //// No recording widgets or `ReplaceChild` yet
 #[test]
 fn simple_lifecyle() {
     let record = Recording::default();
@@ -363,26 +424,57 @@ fn adding_child_lifecycle() {
         assert!(record_new_child.is_empty());
     })
 }
+*/ ////
 
 #[test]
 fn participate_in_autofocus() {
-    let (id_1, id_2, id_3, id_4, id_5, id_6) = widget_id6();
-
-    // this widget starts with a single child, and will replace them with a split
-    // when we send it a command.
-    let replacer = ReplaceChild::new(TextBox::new().with_id(id_4), move || {
-        Split::columns(TextBox::new().with_id(id_5), TextBox::new().with_id(id_6))
-    });
-
-    let widget = Split::columns(
-        Flex::row()
-            .with_flex_child(TextBox::new().with_id(id_1), 1.0)
-            .with_flex_child(TextBox::new().with_id(id_2), 1.0)
-            .with_flex_child(TextBox::new().with_id(id_3), 1.0),
-        replacer,
-    );
-
-    Harness::create_simple("my test text".to_string(), widget, |harness| {
+    const REPLACE_CHILD: Selector = Selector::new("druid-test.replace-child"); ////
+
+    let ids = Cell::new(None); ////
+    ////let (id_1, id_2, id_3, id_4, id_5, id_6) = widget_id6();
+
+    let widget = || { ////
+        let focusable = || RadioGroup::new([("focus", ())]); ////
+        let (w1, w2, w3, w4, w5, w6) = (focusable(), focusable(), focusable(), focusable(), focusable(), focusable()); ////
+        let id = |focusable: &RadioGroup<()>| focusable.id().unwrap(); ////
+        ids.set(Some([id(&w1), id(&w2), id(&w3), id(&w4), id(&w5), id(&w6)])); ////
+
+        // this widget starts with a single child, and will replace them with a split
+        // when we send it a command.
+        // The split is built up front, as the functions can't capture a builder. ////
+        let replacer = ModularWidget::new() ////
+            .with_child(w4) ////
+            .with_child(Split::columns(w5, w6)) ////
+            .event_fn(|widget, ctx, event, data, env| { ////
+                if let Event::Command(cmd) = event { ////
+                    if cmd.is(REPLACE_CHILD) { ////
+                        widget.children.swap_remove(0); ////
+                        ctx.children_changed(); ////
+                        return; ////
+                    } ////
+                } ////
+                widget.children[0].event(ctx, event, data, env) ////
+            }) ////
+            .lifecycle_fn(|widget, ctx, event, data, env| widget.children[0].lifecycle(ctx, event, data, env)); ////
+        ////let replacer = ReplaceChild::new(TextBox::new().with_id(id_4), move || {
+            ////Split::columns(TextBox::new().with_id(id_5), TextBox::new().with_id(id_6))
+        ////});
+
+        Split::columns(
+            Flex::row()
+                .with_flex_child(w1, 1.0) ////
+                .with_flex_child(w2, 1.0) ////
+                .with_flex_child(w3, 1.0), ////
+                ////.with_flex_child(TextBox::new().with_id(id_1), 1.0)
+                ////.with_flex_child(TextBox::new().with_id(id_2), 1.0)
+                ////.with_flex_child(TextBox::new().with_id(id_3), 1.0),
+            replacer,
+        )
+    }; ////
+
+    Harness::create_simple((), widget, |harness| { ////
+    ////Harness::create_simple("my test text".to_string(), widget, |harness| {
+        let [id_1, id_2, id_3, id_4, id_5, id_6] = ids.get().unwrap(); ////
         // verify that all widgets are marked as having children_changed
         // (this should always be true for a new widget)
         harness.inspect_state(|state| assert!(state.children_changed));
@@ -407,17 +499,29 @@ fn participate_in_autofocus() {
 
 #[test]
 fn child_tracking() {
-    let (id_1, id_2, id_3, id_4) = widget_id4();
-
-    let widget = Split::columns(
-        SizedBox::empty().with_id(id_1),
-        SizedBox::empty().with_id(id_2),
-    )
-    .with_id(id_3)
-    .padding(5.0)
-    .with_id(id_4);
-
-    Harness::create_simple(true, widget, |harness| {
+    let ids = Cell::new(None); ////
+    ////let (id_1, id_2, id_3, id_4) = widget_id4();
+
+    let widget = || { ////
+        let (empty_1, empty_2) = (SizedBox::<()>::empty(), SizedBox::<()>::empty()); ////
+        let (id_1, id_2) = (empty_1.id().unwrap(), empty_2.id().unwrap()); ////
+        let split = Split::columns(empty_1, empty_2); ////
+        let id_3 = split.id().unwrap(); ////
+        let padding = Padding::new(5, split); ////
+        ids.set(Some((id_1, id_2, id_3, padding.id().unwrap()))); ////
+        padding ////
+    }; ////
+    ////let widget = Split::columns(
+        ////SizedBox::empty().with_id(id_1),
+        ////SizedBox::empty().with_id(id_2),
+    ////)
+    ////.with_id(id_3)
+    ////.padding(5.0)
+    ////.with_id(id_4);
+
+    Harness::create_simple((), widget, |harness| { ////
+    ////Harness::create_simple(true, widget, |harness| {
+        let (id_1, id_2, id_3, id_4) = ids.get().unwrap(); ////
         harness.send_initial_events();
         let root = harness.get_state(id_4);
         assert_eq!(root.children.entry_count(), 3);
@@ -432,6 +536,7 @@ fn child_tracking() {
     });
 }
 
+/* //// No `ReplaceChild` or `update_fn` for a `ModularWidget` yet
 #[test]
 /// Test that all children are registered correctly after a child is replaced.
 fn register_after_adding_child() {
@@ -487,3 +592,4 @@ fn request_update() {
         assert!(updated.get());
     })
 }
+*/ ////
@@ -16,7 +16,64 @@
 
 use crate::text::{EditableText, EditableTextCursor, Selection};
 
-use xi_unicode::*;
+////use xi_unicode::*;
+
+/// Emoji properties from `xi_unicode`, which needs `std`. ////
+///
+/// The emoji and modifier base tables are approximated by the blocks that
+/// contain them, which is close enough for deleting whole emoji sequences.
+trait EmojiExt { ////
+    fn is_regional_indicator_symbol(self) -> bool;
+    fn is_emoji_modifier(self) -> bool;
+    fn is_emoji_combining_enclosing_keycap(self) -> bool;
+    fn is_emoji(self) -> bool;
+    fn is_emoji_modifier_base(self) -> bool;
+    fn is_tag_spec_char(self) -> bool;
+    fn is_emoji_cancel_tag(self) -> bool;
+    fn is_zwj(self) -> bool;
+}
+
+impl EmojiExt for char { ////
+    fn is_regional_indicator_symbol(self) -> bool {
+        ('\u{1F1E6}'..='\u{1F1FF}').contains(&self)
+    }
+    fn is_emoji_modifier(self) -> bool {
+        ('\u{1F3FB}'..='\u{1F3FF}').contains(&self)
+    }
+    fn is_emoji_combining_enclosing_keycap(self) -> bool {
+        self == '\u{20E3}'
+    }
+    fn is_emoji(self) -> bool {
+        matches!(self,
+            '\u{00A9}' | '\u{00AE}' | '\u{203C}' | '\u{2049}' | '\u{2122}' | '\u{2139}'
+            | '\u{2194}'..='\u{21AA}' | '\u{231A}'..='\u{23FF}' | '\u{24C2}'
+            | '\u{25AA}'..='\u{27BF}' | '\u{2934}' | '\u{2935}' | '\u{2B05}'..='\u{2B55}'
+            | '\u{3030}' | '\u{303D}' | '\u{3297}' | '\u{3299}' | '\u{1F000}'..='\u{1FAFF}')
+    }
+    fn is_emoji_modifier_base(self) -> bool {
+        matches!(self,
+            '\u{261D}' | '\u{26F9}' | '\u{270A}'..='\u{270D}'
+            | '\u{1F385}'..='\u{1F3CC}' | '\u{1F442}'..='\u{1F4AA}' | '\u{1F574}'..='\u{1F64F}'
+            | '\u{1F6A3}'..='\u{1F6CC}' | '\u{1F90C}'..='\u{1F9DD}' | '\u{1FAC3}'..='\u{1FAF8}')
+    }
+    fn is_tag_spec_char(self) -> bool {
+        ('\u{E0020}'..='\u{E007E}').contains(&self)
+    }
+    fn is_emoji_cancel_tag(self) -> bool {
+        self == '\u{E007F}'
+    }
+    fn is_zwj(self) -> bool {
+        self == '\u{200D}'
+    }
+}
+
+fn is_variation_selector(c: char) -> bool { ////
+    ('\u{FE00}'..='\u{FE0F}').contains(&c) || ('\u{E0100}'..='\u{E01EF}').contains(&c)
+}
+
+fn is_keycap_base(c: char) -> bool { ////
+    c.is_ascii_digit() || c == '#' || c == '*'
+}
 
 #[allow(clippy::cognitive_complexity)]
 fn backspace_offset(text: &impl EditableText, start: usize) -> usize {
@@ -16,58 +16,30 @@
 
 ////use std::borrow::Cow;
 ////use std::ops::Range;
+use core::ops::Range; ////
 
+use heapless::ArrayLength; ////
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
-use crate::String; ////
+////use crate::String; ////
 
 /// An EditableText trait.
+///
+/// Only `as_str` and `edit` need to be implemented, the movements
+/// are computed from the string slice. ////
 pub trait EditableText: Sized {
     // TODO: would be nice to have something like
     // type Cursor: EditableTextCursor<Self>;
 
+    /// Get the whole text as a string slice.
+    fn as_str(&self) -> &str; ////
+
     /// Create a cursor with a reference to the text and a offset position.
     ///
     /// Returns None if the position isn't a codepoint boundary.
-    fn cursor(&self, position: usize) -> Option<StringCursor>;
-
-    /// Replace range with new text.
-    /// Can panic if supplied an invalid range.
-    // TODO: make this generic over Self
-    fn edit(&mut self, range: Range<usize>, new: impl Into<String>);
-
-    /// Get slice of text at range.
-    fn slice(&self, range: Range<usize>) -> Option<Cow<str>>;
-
-    /// Get length of text (in bytes).
-    fn len(&self) -> usize;
-
-    /// Get the previous word offset from the given offset, if it exists.
-    fn prev_word_offset(&self, offset: usize) -> Option<usize>;
-
-    /// Get the next word offset from the given offset, if it exists.
-    fn next_word_offset(&self, offset: usize) -> Option<usize>;
-
-    /// Get the next grapheme offset from the given offset, if it exists.
-    fn prev_grapheme_offset(&self, offset: usize) -> Option<usize>;
-
-    /// Get the next grapheme offset from the given offset, if it exists.
-    fn next_grapheme_offset(&self, offset: usize) -> Option<usize>;
-
-    /// Get the previous codepoint offset from the given offset, if it exists.
-    fn prev_codepoint_offset(&self, offset: usize) -> Option<usize>;
-
-    /// Get the next codepoint offset from the given offset, if it exists.
-    fn next_codepoint_offset(&self, offset: usize) -> Option<usize>;
-
-    fn is_empty(&self) -> bool;
-
-    fn from_str(s: &str) -> Self;
-}
-
-impl EditableText for String {
-    fn cursor<'a>(&self, position: usize) -> Option<StringCursor> {
+    fn cursor(&self, position: usize) -> Option<StringCursor> {
         let new_cursor = StringCursor {
-            text: &self,
+            text: self.as_str(), ////
+            ////text: &self,
             position,
         };
 
@@ -78,50 +50,32 @@ impl EditableText for String {
         }
     }
 
-    fn edit(&mut self, range: Range<usize>, new: impl Into<String>) {
-        self.replace_range(range, &new.into());
-    }
+    /// Replace range with new text.
+    /// Can panic if supplied an invalid range.
+    ///
+    /// As the text has a fixed capacity, `new` may be cut short at a codepoint
+    /// boundary. Returns the length of the text that was inserted. ////
+    // TODO: make this generic over Self
+    fn edit(&mut self, range: Range<usize>, new: &str) -> usize; ////
+    ////fn edit(&mut self, range: Range<usize>, new: impl Into<String>);
 
-    fn slice(&self, range: Range<usize>) -> Option<Cow<str>> {
-        if let Some(slice) = self.get(range) {
-            Some(Cow::from(slice))
-        } else {
-            None
-        }
+    /// Get slice of text at range.
+    fn slice(&self, range: Range<usize>) -> Option<&str> { ////
+    ////fn slice(&self, range: Range<usize>) -> Option<Cow<str>>;
+        self.as_str().get(range) ////
     }
 
+    /// Get length of text (in bytes).
     fn len(&self) -> usize {
-        self.len()
-    }
-
-    fn prev_grapheme_offset(&self, from: usize) -> Option<usize> {
-        let mut c = GraphemeCursor::new(from, self.len(), true);
-        c.prev_boundary(self, 0).unwrap()
-    }
-
-    fn next_grapheme_offset(&self, from: usize) -> Option<usize> {
-        let mut c = GraphemeCursor::new(from, self.len(), true);
-        c.next_boundary(self, 0).unwrap()
-    }
-
-    fn prev_codepoint_offset(&self, from: usize) -> Option<usize> {
-        let mut c = self.cursor(from).unwrap();
-        c.prev()
-    }
-
-    fn next_codepoint_offset(&self, from: usize) -> Option<usize> {
-        let mut c = self.cursor(from).unwrap();
-        if c.next().is_some() {
-            Some(c.pos())
-        } else {
-            None
-        }
+        self.as_str().len() ////
     }
 
+    /// Get the previous word offset from the given offset, if it exists.
     fn prev_word_offset(&self, from: usize) -> Option<usize> {
         let mut offset = from;
         let mut passed_alphanumeric = false;
-        for prev_grapheme in self.get(0..from)?.graphemes(true).rev() {
+        for prev_grapheme in self.as_str().get(0..from)?.graphemes(true).rev() { ////
+        ////for prev_grapheme in self.get(0..from)?.graphemes(true).rev() {
             let is_alphanumeric = prev_grapheme.chars().next()?.is_alphanumeric();
             if is_alphanumeric {
                 passed_alphanumeric = true;
@@ -133,10 +87,12 @@ impl EditableText for String {
         None
     }
 
+    /// Get the next word offset from the given offset, if it exists.
     fn next_word_offset(&self, from: usize) -> Option<usize> {
         let mut offset = from;
         let mut passed_alphanumeric = false;
-        for next_grapheme in self.get(from..)?.graphemes(true) {
+        for next_grapheme in self.as_str().get(from..)?.graphemes(true) { ////
+        ////for next_grapheme in self.get(from..)?.graphemes(true) {
             let is_alphanumeric = next_grapheme.chars().next()?.is_alphanumeric();
             if is_alphanumeric {
                 passed_alphanumeric = true;
@@ -148,15 +104,78 @@ impl EditableText for String {
         Some(self.len())
     }
 
+    /// Get the next grapheme offset from the given offset, if it exists.
+    fn prev_grapheme_offset(&self, from: usize) -> Option<usize> {
+        let mut c = GraphemeCursor::new(from, self.len(), true);
+        c.prev_boundary(self.as_str(), 0).unwrap() ////
+        ////c.prev_boundary(self, 0).unwrap()
+    }
+
+    /// Get the next grapheme offset from the given offset, if it exists.
+    fn next_grapheme_offset(&self, from: usize) -> Option<usize> {
+        let mut c = GraphemeCursor::new(from, self.len(), true);
+        c.next_boundary(self.as_str(), 0).unwrap() ////
+        ////c.next_boundary(self, 0).unwrap()
+    }
+
+    /// Get the previous codepoint offset from the given offset, if it exists.
+    fn prev_codepoint_offset(&self, from: usize) -> Option<usize> {
+        let mut c = self.cursor(from).unwrap();
+        c.prev()
+    }
+
+    /// Get the next codepoint offset from the given offset, if it exists.
+    fn next_codepoint_offset(&self, from: usize) -> Option<usize> {
+        let mut c = self.cursor(from).unwrap();
+        if c.next().is_some() {
+            Some(c.pos())
+        } else {
+            None
+        }
+    }
+
     fn is_empty(&self) -> bool {
-        self.is_empty()
+        self.as_str().is_empty() ////
     }
 
+    ////fn from_str(s: &str) -> Self;
+}
+
+impl<N: ArrayLength<u8>> EditableText for heapless::String<N> { ////
+////impl EditableText for String {
+    fn as_str(&self) -> &str { ////
+        self ////
+    } ////
+
+    fn edit(&mut self, range: Range<usize>, new: &str) -> usize { ////
+    ////fn edit(&mut self, range: Range<usize>, new: impl Into<String>) {
+        let room = self.capacity() - (self.len() - range.len()); ////
+        let new = truncate(new, room); ////
+        let mut edited = heapless::String::<N>::new(); ////
+        edited.push_str(&self[..range.start]).unwrap(); ////
+        edited.push_str(new).unwrap(); ////
+        edited.push_str(&self[range.end..]).unwrap(); ////
+        *self = edited; ////
+        new.len() ////
+        ////self.replace_range(range, &new.into());
+    }
+
+    /* //// Not needed for editing, and heapless has `From<&str>`
     fn from_str(s: &str) -> Self {
         s.to_string()
     }
+    */ ////
 }
 
+/// The longest prefix of `s` that fits in `len` bytes, ending at a codepoint boundary. ////
+fn truncate(s: &str, len: usize) -> &str { ////
+    let mut end = len.min(s.len()); ////
+    while !s.is_char_boundary(end) { ////
+        end -= 1; ////
+    } ////
+    &s[..end] ////
+} ////
+
 /// A cursor with convenience functions for moving through EditableText.
 pub trait EditableTextCursor<EditableText> {
     /// Set cursor position.
@@ -200,7 +219,8 @@ pub struct StringCursor<'a> {
     position: usize,
 }
 
-impl<'a> EditableTextCursor<&'a String> for StringCursor<'a> {
+impl<'a> EditableTextCursor<&'a str> for StringCursor<'a> { ////
+////impl<'a> EditableTextCursor<&'a String> for StringCursor<'a> {
     fn set(&mut self, position: usize) {
         self.position = position;
     }
@@ -290,6 +310,9 @@ pub fn len_utf8_from_first_byte(b: u8) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use heapless::consts::U64; ////
+
+    type String = heapless::String<U64>; ////
 
     #[test]
     fn replace() {
@@ -298,6 +321,15 @@ mod tests {
         assert_eq!("herald", a);
     }
 
+    #[test]
+    fn edit_is_cut_to_capacity() { ////
+        let mut a = heapless::String::<heapless::consts::U8>::from("hello");
+        assert_eq!(a.edit(5..5, " w\u{00f6}rld"), 2);
+        assert_eq!("hello w", a);
+        assert_eq!(a.edit(0..1, "\u{00f6}"), 2);
+        assert_eq!("\u{00f6}ello w", a);
+    }
+
     #[test]
     fn prev_codepoint_offset() {
         let a = String::from("a\u{00A1}\u{4E00}\u{1F4A9}");
@@ -306,7 +338,7 @@ mod tests {
         assert_eq!(Some(1), a.prev_codepoint_offset(3));
         assert_eq!(Some(0), a.prev_codepoint_offset(1));
         assert_eq!(None, a.prev_codepoint_offset(0));
-        let b = a.slice(1..10).unwrap().to_string();
+        let b = String::from(a.slice(1..10).unwrap());
         assert_eq!(Some(5), b.prev_codepoint_offset(9));
         assert_eq!(Some(2), b.prev_codepoint_offset(5));
         assert_eq!(Some(0), b.prev_codepoint_offset(2));
@@ -321,7 +353,7 @@ mod tests {
         assert_eq!(Some(3), a.next_codepoint_offset(1));
         assert_eq!(Some(1), a.next_codepoint_offset(0));
         assert_eq!(None, a.next_codepoint_offset(10));
-        let b = a.slice(1..10).unwrap().to_string();
+        let b = String::from(a.slice(1..10).unwrap());
         assert_eq!(Some(9), b.next_codepoint_offset(5));
         assert_eq!(Some(5), b.next_codepoint_offset(2));
         assert_eq!(Some(2), b.next_codepoint_offset(0));
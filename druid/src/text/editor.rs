@@ -0,0 +1,247 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Perform `EditAction`s on a text and its selection.
+
+use crate::text::{
+    movement, offset_for_delete_backwards, EditAction, EditableText, Movement, Selection,
+};
+
+/// The selection of a text being edited, and the edits that move it.
+///
+/// This is shared by the `TextBox` and the `Keyboard`, so that either of them
+/// can edit the text at the caret.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Editor {
+    selection: Selection,
+}
+
+impl Editor {
+    /// Create an editor with the caret at the start of the text.
+    pub fn new() -> Self {
+        Editor {
+            selection: Selection::caret(0),
+        }
+    }
+
+    /// Create an editor with the caret at `offset`, such as the end of the text.
+    pub fn with_caret(offset: usize) -> Self {
+        Editor {
+            selection: Selection::caret(offset),
+        }
+    }
+
+    /// The current selection.
+    pub fn selection(&self) -> Selection {
+        self.selection
+    }
+
+    /// Return the active edge of the current selection or cursor.
+    pub fn cursor(&self) -> usize {
+        self.selection.end
+    }
+
+    /// Keep the selection within `text`, in case it was changed elsewhere.
+    pub fn constrain_to(&mut self, text: &impl EditableText) {
+        self.selection = self.selection.constrain_to(text);
+    }
+
+    /// Perform `edit_action` on `text`.
+    pub fn do_edit_action(&mut self, edit_action: &EditAction, text: &mut impl EditableText) {
+        // EditableText's edit method will panic if selection is greater than
+        // text length, hence we try to constrain it.
+        //
+        // This is especially needed when data was modified externally.
+        self.constrain_to(text);
+        match edit_action {
+            EditAction::Insert(chars) | EditAction::Paste(chars) => self.insert(text, chars),
+            EditAction::Backspace => self.delete_backward(text),
+            EditAction::Delete => self.delete_forward(text),
+            EditAction::JumpDelete(movement) => {
+                self.move_selection(*movement, text, true);
+                self.delete_forward(text)
+            }
+            EditAction::JumpBackspace(movement) => {
+                self.move_selection(*movement, text, true);
+                self.delete_backward(text)
+            }
+            EditAction::Move(movement) => self.move_selection(*movement, text, false),
+            EditAction::ModifySelection(movement) => self.move_selection(*movement, text, true),
+            EditAction::SelectAll => self.selection.all(text),
+            EditAction::Click(action) => {
+                if action.extend {
+                    self.selection.end = action.column;
+                } else {
+                    self.caret_to(text, action.column);
+                }
+            }
+            EditAction::Drag(action) => self.selection.end = action.column,
+        }
+    }
+
+    /// Insert text at the cursor position.
+    /// Replaces selected text if there's a selection.
+    ///
+    /// Text that doesn't fit in the capacity of `text` is dropped.
+    pub fn insert(&mut self, text: &mut impl EditableText, new: &str) {
+        let selection = self.selection.constrain_to(text);
+        let inserted = text.edit(selection.range(), new);
+        self.selection = Selection::caret(selection.min() + inserted);
+    }
+
+    /// Set the selection to be a caret at the given offset, if that's a valid
+    /// codepoint boundary.
+    fn caret_to(&mut self, text: &impl EditableText, to: usize) {
+        match text.cursor(to) {
+            Some(_) => self.selection = Selection::caret(to),
+            None => log::error!("You can't move the cursor there."),
+        }
+    }
+
+    /// Edit a selection using a `Movement`.
+    fn move_selection(&mut self, mvmnt: Movement, text: &impl EditableText, modify: bool) {
+        // This movement function should ensure all movements are legit.
+        // If they aren't, that's a problem with the movement function.
+        self.selection = movement(mvmnt, self.selection, text, modify);
+    }
+
+    /// Delete to previous grapheme if in caret mode.
+    /// Otherwise just delete everything inside the selection.
+    pub fn delete_backward(&mut self, text: &mut impl EditableText) {
+        if self.selection.is_caret() {
+            let cursor = self.cursor();
+            let new_cursor = offset_for_delete_backwards(&self.selection, text);
+            text.edit(new_cursor..cursor, "");
+            self.caret_to(text, new_cursor);
+        } else {
+            text.edit(self.selection.range(), "");
+            self.caret_to(text, self.selection.min());
+        }
+    }
+
+    /// Delete the next grapheme if in caret mode.
+    /// Otherwise just delete everything inside the selection.
+    pub fn delete_forward(&mut self, text: &mut impl EditableText) {
+        if self.selection.is_caret() {
+            // Never touch the characters before the cursor.
+            if text.next_grapheme_offset(self.cursor()).is_some() {
+                self.move_selection(Movement::Right, text, false);
+                self.delete_backward(text);
+            }
+        } else {
+            self.delete_backward(text);
+        }
+    }
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Editor::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::consts::U32;
+
+    type String = heapless::String<U32>;
+
+    /// Test that when data is mutated externally the editor
+    /// can still be used to insert characters.
+    #[test]
+    fn data_can_be_changed_externally() {
+        let mut editor = Editor::new();
+        let mut data = String::new();
+
+        // First insert some chars
+        editor.insert(&mut data, "o");
+        editor.insert(&mut data, "n");
+        editor.insert(&mut data, "e");
+
+        assert_eq!("one", data);
+        assert_eq!(Selection::caret(3), editor.selection());
+
+        // Modify data externally (e.g data was changed in the parent widget)
+        data = String::new();
+
+        // Insert again
+        editor.insert(&mut data, "a");
+        assert_eq!("a", data);
+    }
+
+    /// Test backspace on the combo character o̷
+    #[test]
+    fn backspace_combining() {
+        let mut editor = Editor::new();
+        let mut data = String::new();
+
+        editor.insert(&mut data, "\u{0073}\u{006F}\u{0337}\u{0073}");
+
+        editor.delete_backward(&mut data);
+        editor.delete_backward(&mut data);
+
+        assert_eq!(data, String::from("\u{0073}\u{006F}"))
+    }
+
+    /// Devanagari codepoints are 3 utf-8 code units each.
+    #[test]
+    fn backspace_devanagari() {
+        let mut editor = Editor::new();
+        let mut data = String::new();
+
+        editor.insert(&mut data, "हिन्दी");
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("हिन्द"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("हिन्"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("हिन"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("हि"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("ह"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from(""));
+    }
+
+    /// Emoji sequences are deleted as a whole.
+    #[test]
+    fn backspace_emoji() {
+        let mut editor = Editor::new();
+        let mut data = String::new();
+
+        // Family, joined by ZWJ, after a waving hand with skin tone
+        editor.insert(&mut data, "a\u{1F44B}\u{1F3FD}\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}");
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("a\u{1F44B}\u{1F3FD}"));
+        editor.delete_backward(&mut data);
+        assert_eq!(data, String::from("a"));
+    }
+
+    #[test]
+    fn insert_at_caret() {
+        let mut editor = Editor::new();
+        let mut data = String::from("wifi");
+        editor.do_edit_action(&EditAction::Move(Movement::RightOfLine), &mut data);
+        editor.do_edit_action(&EditAction::Move(Movement::Left), &mut data);
+        editor.do_edit_action(&EditAction::Insert("-f".into()), &mut data);
+        assert_eq!("wif-fi", data);
+        editor.do_edit_action(&EditAction::ModifySelection(Movement::LeftOfLine), &mut data);
+        editor.do_edit_action(&EditAction::Delete, &mut data);
+        assert_eq!("i", data);
+        assert_eq!(Selection::caret(0), editor.selection());
+    }
+}
@@ -27,4 +27,8 @@ pub mod backspace;
 pub use self::backspace::offset_for_delete_backwards;
 
 mod text_input;
-pub use self::text_input::{BasicTextInput, EditAction, MouseAction, TextInput};
+pub use self::text_input::{EditAction, MouseAction}; ////
+////pub use self::text_input::{BasicTextInput, EditAction, MouseAction, TextInput};
+
+mod editor; ////
+pub use self::editor::Editor; ////
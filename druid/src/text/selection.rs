@@ -14,13 +14,16 @@
 
 //! A Selection type for text editing.
 
+use core::cmp::{max, min}; ////
 ////use std::cmp::{max, min};
+use core::ops::Range; ////
 ////use std::ops::Range;
 
 use crate::text::EditableText;
 
 /// A Selection type for EditableText.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)] ////
+////#[derive(Debug, Clone, Copy)]
 pub struct Selection {
     /// The inactive edge of a selection, as a byte offset. When
     /// equal to end, the selection range acts as a caret.
@@ -15,7 +15,7 @@
 //! Map input to `EditAction`s
 
 use super::Movement;
-use crate::{HotKey, KbKey, KeyEvent, Modifiers, SysMods};
+////use crate::{HotKey, KbKey, KeyEvent, Modifiers, SysMods};
 use crate::String;
 
 // This following enumerations are heavily inspired by xi-editors enumerations found at
@@ -44,9 +44,12 @@ pub enum EditAction {
 pub struct MouseAction {
     pub row: usize,
     pub column: usize,
-    pub mods: Modifiers,
+    /// Extend the selection instead of moving the caret ////
+    pub extend: bool, ////
+    ////pub mods: Modifiers,
 }
 
+/* //// No keyboard on the watch. TODO: Map the hardware buttons
 pub trait TextInput {
     /// Handle a key event and return an edit action to be executed
     /// for the key event
@@ -162,3 +165,4 @@ fn key_event_is_printable(event: &KeyEvent) -> bool {
         false
     }
 }
+*/ ////
@@ -0,0 +1,223 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! An arc widget, for rings that show progress.
+
+use crate::piet::Arc as ArcShape;
+use crate::theme;
+use crate::{
+    BoxConstraints, Color, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, Size, UpdateCtx, Widget,
+};
+use crate::{ScreenCoord, ScreenFactor, WidgetId, WidgetType};
+
+/// A ring that fills from its start angle to its end angle as the value goes
+/// from `0.0` to `1.0`, over an optional background track, like LVGL's `lv_arc`.
+///
+/// This type impls `Widget<f32>`; use [`Arc::with_value`] to show a value from other data.
+/// Angles are in degrees clockwise from 3 o'clock, and the end may be past 360.
+///
+/// The ring is drawn inside the widget, `inset` pixels from its edge, with no
+/// background of its own. Arcs with increasing insets may be stacked in an overlay,
+/// or painted in the same `Painter`, to make concentric activity rings.
+///
+/// ```
+/// use druid::widget::Arc;
+/// use druid::Color;
+///
+/// struct Activity { steps: u32, active_minutes: u8 }
+///
+/// // Two concentric rings, starting from 12 o'clock
+/// let steps = Arc::with_value(|data: &Activity| data.steps as f32 / 10_000.)
+///     .with_angles(270, 270 + 360)
+///     .with_color(Color::rgb8(0xff, 0x20, 0x40));
+/// let minutes = Arc::with_value(|data: &Activity| data.active_minutes as f32 / 30.)
+///     .with_angles(270, 270 + 360)
+///     .with_color(Color::rgb8(0x40, 0xff, 0x20))
+///     .with_inset(12);
+/// ```
+///
+/// [`Arc::with_value`]: struct.Arc.html#method.with_value
+#[derive(Clone)]
+pub struct Arc<T> {
+    id: WidgetId,
+    /// Reads the value, in the range `0.0..1.0`.
+    get: fn(&T) -> ScreenFactor,
+    start_angle: u16,
+    end_angle: u16,
+    thickness: ScreenCoord,
+    rounded: bool,
+    inset: ScreenCoord,
+    color: KeyOrValue<Color>,
+    track_color: Option<KeyOrValue<Color>>,
+}
+
+impl Arc<f32> {
+    /// Create an arc showing a value from `0.0` to `1.0`.
+    pub fn new() -> Arc<f32> {
+        Arc::with_value(|data| *data)
+    }
+}
+
+impl Default for Arc<f32> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arc<T> {
+    /// Create an arc showing the value returned by `get`, from `0.0` to `1.0`.
+    ///
+    /// Like `lv_arc`, the arc covers 270 degrees opening at the bottom,
+    /// with rounded ends and a dark track.
+    pub fn with_value(get: fn(&T) -> ScreenFactor) -> Self {
+        Arc {
+            id: WidgetId::next(),
+            get,
+            start_angle: 135,
+            end_angle: 45 + 360,
+            thickness: 10,
+            rounded: true,
+            inset: 0,
+            color: theme::LABEL_COLOR,
+            track_color: Some(Color::rgb8(0x30, 0x30, 0x30).into()),
+        }
+    }
+
+    /// Builder-style method to set the angles where the arc starts and ends.
+    /// If `end_angle` is before `start_angle`, the arc goes on past 360 degrees.
+    pub fn with_angles(mut self, start_angle: u16, end_angle: u16) -> Self {
+        self.start_angle = start_angle % 360;
+        self.end_angle = end_angle % 360 + if end_angle % 360 <= self.start_angle { 360 } else { 0 };
+        self
+    }
+
+    /// Builder-style method to set the thickness of the ring in pixels.
+    pub fn with_thickness(mut self, thickness: ScreenCoord) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Builder-style method to choose rounded or square ends.
+    pub fn rounded(mut self, rounded: bool) -> Self {
+        self.rounded = rounded;
+        self
+    }
+
+    /// Builder-style method to shrink the ring by `inset` pixels from the edge of the widget.
+    pub fn with_inset(mut self, inset: ScreenCoord) -> Self {
+        self.inset = inset;
+        self
+    }
+
+    /// Builder-style method to set the color of the filled part.
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Builder-style method to set the color of the track behind the arc, or `None` for no track.
+    pub fn with_track_color(mut self, color: Option<KeyOrValue<Color>>) -> Self {
+        self.track_color = color;
+        self
+    }
+
+    /// The angle where the filled part ends, for the value in `data`.
+    fn value_angle(&self, data: &T) -> u16 {
+        let clamped = (self.get)(data).clamp(0.0, 1.0);
+        let sweep = (self.end_angle - self.start_angle) as ScreenFactor;
+        self.start_angle + (clamped * sweep + 0.5) as u16
+    }
+}
+
+impl<T: Data> Widget<T> for Arc<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        bc.debug_check("Arc");
+
+        // square, as large as allowed
+        let size = if bc.is_width_bounded() && bc.is_height_bounded() {
+            let max = bc.max();
+            max.width.min(max.height)
+        } else {
+            theme::WIDE_WIDGET_WIDTH.resolve(env) / 2
+        };
+        bc.constrain(Size::new(size, size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let center = Point::new(size.width / 2, size.height / 2);
+        let radius = (size.width.min(size.height) / 2).saturating_sub(self.inset);
+        let value_angle = self.value_angle(data);
+
+        //// TODO: Render with LVGL
+        /*
+        let arc = lv_arc_create(lv_scr_act(), NULL);                     //  Add an arc to the current screen
+        lv_obj_set_size(arc, size.width - 2 * inset, size.height - 2 * inset);
+        lv_arc_set_bg_angles(arc, start_angle, end_angle);               //  The track
+        lv_arc_set_angles(arc, start_angle, value_angle);                //  The filled part
+        lv_obj_set_style_local_bg_opa(arc, LV_ARC_PART_BG, LV_STATE_DEFAULT, LV_OPA_TRANSP);  //  No box behind the ring, so arcs can be stacked
+        lv_obj_set_style_local_border_width(arc, LV_ARC_PART_BG, LV_STATE_DEFAULT, 0);
+        lv_obj_set_style_local_line_width(arc, LV_ARC_PART_BG, LV_STATE_DEFAULT, thickness);
+        lv_obj_set_style_local_line_width(arc, LV_ARC_PART_INDIC, LV_STATE_DEFAULT, thickness);
+        lv_obj_set_style_local_line_rounded(arc, LV_ARC_PART_INDIC, LV_STATE_DEFAULT, rounded);
+        lv_obj_set_style_local_line_color(arc, LV_ARC_PART_BG, LV_STATE_DEFAULT, track_color);   //  Or LV_OPA_TRANSP for no track
+        lv_obj_set_style_local_line_color(arc, LV_ARC_PART_INDIC, LV_STATE_DEFAULT, color);
+        */
+
+        if let Some(track_color) = self.track_color {
+            let track = ArcShape::new(center, radius, self.start_angle, self.end_angle);
+            ctx.render_ctx.stroke_arc_antialiased(track, track_color.resolve(env), self.thickness, self.rounded);
+        }
+        if value_angle > self.start_angle {
+            let filled = ArcShape::new(center, radius, self.start_angle, value_angle);
+            ctx.render_ctx.stroke_arc_antialiased(filled, self.color.resolve(env), self.thickness, self.rounded);
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::Arc(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_angle_is_clamped() {
+        let arc = Arc::new();
+        assert_eq!(arc.value_angle(&0.5), 135 + 135);
+        assert_eq!(arc.value_angle(&2.), 45 + 360);
+
+        // an activity ring from 12 o'clock all the way round
+        let ring = Arc::new().with_angles(270, 270);
+        assert_eq!((ring.start_angle, ring.end_angle), (270, 270 + 360));
+        assert_eq!(ring.value_angle(&-1.), 270);
+        assert_eq!(ring.value_angle(&0.25), 360);
+    }
+}
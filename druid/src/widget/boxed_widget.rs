@@ -1,7 +1,7 @@
 //! `BoxedWidget` contains a `Widget`. Allows for dynamic dispatch with static `Widgets` in `[no_std]`.
 use crate::{
     BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size, UpdateCtx, Widget, WidgetId,
-    widget::{Align, Flex, Label, Padding, SizedBox, Spacer},
+    widget::{Align, Arc, Checkbox, Container, DatePicker, Either, Flex, Gauge, Grid, Image, Keyboard, Label, List, Padding, Painter, ProgressBar, Radio, RadioGroup, Roller, Scroll, SizedBox, Slider, Spacer, Spinner, Split, Stepper, Svg, Switch, TextBox, TimePicker, ViewSwitcher, ZStack},
 };
 
 /// Max number of `Widgets` on embedded platforms
@@ -9,16 +9,19 @@ pub const MAX_WIDGETS: usize = 10;
 
 /// Specialised Trait for handling static `Widgets` on embedded platforms
 pub trait StaticWidgets<D: Clone /* Data + 'static + Default */> {
-    /// Fetch the static `Widgets` for the Data type
-    fn get_widgets(&self) -> &'static mut [ WidgetType<D> ];
+    /// Fetch the static `Widget` in this box
+    fn get_widget(&self) -> &'static mut WidgetType<D>;
     /// Add a `Widget` for the Data type
     fn add_widget(&self, widget: WidgetType<D>);
+    /// Remove the `Widget` for the Data type, so that its `WidgetId` may be reused
+    fn remove_widget(&self);
 }
 
 /// Default Trait will not have static `Widgets`
 impl<D: Clone /* Data + 'static + Default */> StaticWidgets<D> for BoxedWidget<D> {
-    default fn get_widgets(&self) -> &'static mut [ WidgetType<D> ] { panic!("no global widgets") }
+    default fn get_widget(&self) -> &'static mut WidgetType<D> { panic!("no global widgets") }
     default fn add_widget(&self, _widget: WidgetType<D>) { panic!("no global widgets") }
+    default fn remove_widget(&self) { panic!("no global widgets") }
 }
 
 #[derive(Clone)]
@@ -34,6 +37,10 @@ impl<D: Clone> BoxedWidget<D> {
         let id = widget.id().unwrap();
         let boxed_widget = Self::new_by_id(id);
         let widget_type: WidgetType<D> = widget.to_type();
+        if let WidgetType::None = widget_type {
+            //  Already boxed, e.g. the root of a `WindowDesc`
+            return boxed_widget;
+        }
         boxed_widget.clone().add_widget(widget_type);
         boxed_widget
     }
@@ -43,19 +50,53 @@ impl<D: Clone> BoxedWidget<D> {
             None
         ) 
     }
+    /// Drop the `Widget` from the box, and free its `WidgetId` for reuse.
+    /// Other boxes and `WidgetPod`s for the `Widget` must not be used afterwards.
+    pub fn remove(self) {
+        self.remove_widget();
+        self.0.free();
+    }
 }
 
 impl<D: Data> Widget<D> for BoxedWidget<D> { ////
 ////impl<D> Widget<D> for Box<dyn Widget<D>> {
 
     fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut D, env: &Env) {
-        match &mut self.get_widgets()[self.0.0 as usize] {
+        match self.get_widget() {
             WidgetType::Align(w)   => w.event(ctx, event, data, env),
+            WidgetType::Arc(w)     => w.event(ctx, event, data, env),
+            WidgetType::Checkbox(w) => w.event(ctx, event, data, env),
+            WidgetType::Container(w) => w.event(ctx, event, data, env),
+            WidgetType::DatePicker(w) => w.event(ctx, event, data, env),
+            WidgetType::Either(w)   => w.event(ctx, event, data, env),
             //  WidgetType::Button(w)  => w.event(ctx, event, data, env),
             WidgetType::Flex(w)    => w.event(ctx, event, data, env),
+            WidgetType::Gauge(w)   => w.event(ctx, event, data, env),
+            WidgetType::Grid(w)   => w.event(ctx, event, data, env),
+            WidgetType::Image(w)   => w.event(ctx, event, data, env),
+            WidgetType::Keyboard(w) => w.event(ctx, event, data, env),
             WidgetType::Label(w)   => w.event(ctx, event, data, env),
+            WidgetType::List(w)    => w.event(ctx, event, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.event(ctx, event, data, env),
             WidgetType::Padding(w) => w.event(ctx, event, data, env),
+            WidgetType::Painter(w) => w.event(ctx, event, data, env),
+            WidgetType::ProgressBar(w) => w.event(ctx, event, data, env),
+            WidgetType::Radio(w)   => w.event(ctx, event, data, env),
+            WidgetType::RadioGroup(w) => w.event(ctx, event, data, env),
+            WidgetType::Roller(w) => w.event(ctx, event, data, env),
+            WidgetType::Scroll(w)  => w.event(ctx, event, data, env),
             WidgetType::SizedBox(w) => w.event(ctx, event, data, env),
+            WidgetType::Slider(w)  => w.event(ctx, event, data, env),
+            WidgetType::Spinner(w) => w.event(ctx, event, data, env),
+            WidgetType::Split(w) => w.event(ctx, event, data, env),
+            WidgetType::Stepper(w) => w.event(ctx, event, data, env),
+            WidgetType::Svg(w)     => w.event(ctx, event, data, env),
+            WidgetType::Switch(w)  => w.event(ctx, event, data, env),
+            WidgetType::TextBox(w) => w.event(ctx, event, data, env),
+            WidgetType::TimePicker(w) => w.event(ctx, event, data, env),
+            WidgetType::ViewSwitcher(w) => w.event(ctx, event, data, env),
+            WidgetType::ZStack(w) => w.event(ctx, event, data, env),
             WidgetType::None => {}
         };
     }
@@ -71,25 +112,85 @@ impl<D: Data> Widget<D> for BoxedWidget<D> { ////
             }
     */
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &D, env: &Env) {
-        match &mut self.get_widgets()[self.0.0 as usize] {
+        match self.get_widget() {
             WidgetType::Align(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::Arc(w)     => w.lifecycle(ctx, event, data, env),
+            WidgetType::Checkbox(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Container(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::DatePicker(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Either(w)   => w.lifecycle(ctx, event, data, env),
             //  WidgetType::Button(w)  => w.lifecycle(ctx, event, data, env),
             WidgetType::Flex(w)    => w.lifecycle(ctx, event, data, env),
+            WidgetType::Gauge(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::Grid(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::Image(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::Keyboard(w) => w.lifecycle(ctx, event, data, env),
             WidgetType::Label(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::List(w)    => w.lifecycle(ctx, event, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.lifecycle(ctx, event, data, env),
             WidgetType::Padding(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Painter(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::ProgressBar(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Radio(w)   => w.lifecycle(ctx, event, data, env),
+            WidgetType::RadioGroup(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Roller(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Scroll(w)  => w.lifecycle(ctx, event, data, env),
             WidgetType::SizedBox(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Slider(w)  => w.lifecycle(ctx, event, data, env),
+            WidgetType::Spinner(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Split(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Stepper(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::Svg(w)     => w.lifecycle(ctx, event, data, env),
+            WidgetType::Switch(w)  => w.lifecycle(ctx, event, data, env),
+            WidgetType::TextBox(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::TimePicker(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::ViewSwitcher(w) => w.lifecycle(ctx, event, data, env),
+            WidgetType::ZStack(w) => w.lifecycle(ctx, event, data, env),
             WidgetType::None => {}
         };
+        if let LifeCycle::WidgetRemoved = event {
+            //  The `WidgetPod` frees the `WidgetId`
+            self.remove_widget();
+        }
     }
     
     fn update(&mut self, ctx: &mut UpdateCtx, old_data: &D, data: &D, env: &Env) {
-        match &mut self.get_widgets()[self.0.0 as usize] {
+        match self.get_widget() {
             WidgetType::Align(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::Arc(w)     => w.update(ctx, old_data, data, env),
+            WidgetType::Checkbox(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Container(w) => w.update(ctx, old_data, data, env),
+            WidgetType::DatePicker(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Either(w)   => w.update(ctx, old_data, data, env),
             //  WidgetType::Button(w)  => w.update(ctx, old_data, data, env),
             WidgetType::Flex(w)    => w.update(ctx, old_data, data, env),
+            WidgetType::Gauge(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::Grid(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::Image(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::Keyboard(w) => w.update(ctx, old_data, data, env),
             WidgetType::Label(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::List(w)    => w.update(ctx, old_data, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.update(ctx, old_data, data, env),
             WidgetType::Padding(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Painter(w) => w.update(ctx, old_data, data, env),
+            WidgetType::ProgressBar(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Radio(w)   => w.update(ctx, old_data, data, env),
+            WidgetType::RadioGroup(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Roller(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Scroll(w)  => w.update(ctx, old_data, data, env),
             WidgetType::SizedBox(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Slider(w)  => w.update(ctx, old_data, data, env),
+            WidgetType::Spinner(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Split(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Stepper(w) => w.update(ctx, old_data, data, env),
+            WidgetType::Svg(w)     => w.update(ctx, old_data, data, env),
+            WidgetType::Switch(w)  => w.update(ctx, old_data, data, env),
+            WidgetType::TextBox(w) => w.update(ctx, old_data, data, env),
+            WidgetType::TimePicker(w) => w.update(ctx, old_data, data, env),
+            WidgetType::ViewSwitcher(w) => w.update(ctx, old_data, data, env),
+            WidgetType::ZStack(w) => w.update(ctx, old_data, data, env),
             WidgetType::None => {}
         };
     }
@@ -101,13 +202,41 @@ impl<D: Data> Widget<D> for BoxedWidget<D> { ////
             }
     */
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &D, env: &Env) -> Size {
-        match &mut self.get_widgets()[self.0.0 as usize] {
+        match self.get_widget() {
             WidgetType::Align(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::Arc(w)     => w.layout(ctx, bc, data, env),
+            WidgetType::Checkbox(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Container(w) => w.layout(ctx, bc, data, env),
+            WidgetType::DatePicker(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Either(w)   => w.layout(ctx, bc, data, env),
             //  WidgetType::Button(w)  => w.layout(ctx, bc, data, env),
             WidgetType::Flex(w)    => w.layout(ctx, bc, data, env),
+            WidgetType::Gauge(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::Grid(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::Image(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::Keyboard(w) => w.layout(ctx, bc, data, env),
             WidgetType::Label(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::List(w)    => w.layout(ctx, bc, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.layout(ctx, bc, data, env),
             WidgetType::Padding(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Painter(w) => w.layout(ctx, bc, data, env),
+            WidgetType::ProgressBar(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Radio(w)   => w.layout(ctx, bc, data, env),
+            WidgetType::RadioGroup(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Roller(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Scroll(w)  => w.layout(ctx, bc, data, env),
             WidgetType::SizedBox(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Slider(w)  => w.layout(ctx, bc, data, env),
+            WidgetType::Spinner(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Split(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Stepper(w) => w.layout(ctx, bc, data, env),
+            WidgetType::Svg(w)     => w.layout(ctx, bc, data, env),
+            WidgetType::Switch(w)  => w.layout(ctx, bc, data, env),
+            WidgetType::TextBox(w) => w.layout(ctx, bc, data, env),
+            WidgetType::TimePicker(w) => w.layout(ctx, bc, data, env),
+            WidgetType::ViewSwitcher(w) => w.layout(ctx, bc, data, env),
+            WidgetType::ZStack(w) => w.layout(ctx, bc, data, env),
             WidgetType::None => Size::ZERO
         }
     }
@@ -119,13 +248,81 @@ impl<D: Data> Widget<D> for BoxedWidget<D> { ////
             }        
     */
     fn paint(&mut self, ctx: &mut PaintCtx, data: &D, env: &Env) {
-        match &mut self.get_widgets()[self.0.0 as usize] {
+        match self.get_widget() {
             WidgetType::Align(w)   => w.paint(ctx, data, env),
+            WidgetType::Arc(w)     => w.paint(ctx, data, env),
+            WidgetType::Checkbox(w) => w.paint(ctx, data, env),
+            WidgetType::Container(w) => w.paint(ctx, data, env),
+            WidgetType::DatePicker(w) => w.paint(ctx, data, env),
+            WidgetType::Either(w)   => w.paint(ctx, data, env),
             //  WidgetType::Button(w)  => w.paint(ctx, data, env),
             WidgetType::Flex(w)    => w.paint(ctx, data, env),
+            WidgetType::Gauge(w)   => w.paint(ctx, data, env),
+            WidgetType::Grid(w)   => w.paint(ctx, data, env),
+            WidgetType::Image(w)   => w.paint(ctx, data, env),
+            WidgetType::Keyboard(w) => w.paint(ctx, data, env),
             WidgetType::Label(w)   => w.paint(ctx, data, env),
+            WidgetType::List(w)    => w.paint(ctx, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.paint(ctx, data, env),
             WidgetType::Padding(w) => w.paint(ctx, data, env),
+            WidgetType::Painter(w) => w.paint(ctx, data, env),
+            WidgetType::ProgressBar(w) => w.paint(ctx, data, env),
+            WidgetType::Radio(w)   => w.paint(ctx, data, env),
+            WidgetType::RadioGroup(w) => w.paint(ctx, data, env),
+            WidgetType::Roller(w) => w.paint(ctx, data, env),
+            WidgetType::Scroll(w)  => w.paint(ctx, data, env),
             WidgetType::SizedBox(w) => w.paint(ctx, data, env),
+            WidgetType::Slider(w)  => w.paint(ctx, data, env),
+            WidgetType::Spinner(w) => w.paint(ctx, data, env),
+            WidgetType::Split(w) => w.paint(ctx, data, env),
+            WidgetType::Stepper(w) => w.paint(ctx, data, env),
+            WidgetType::Svg(w)     => w.paint(ctx, data, env),
+            WidgetType::Switch(w)  => w.paint(ctx, data, env),
+            WidgetType::TextBox(w) => w.paint(ctx, data, env),
+            WidgetType::TimePicker(w) => w.paint(ctx, data, env),
+            WidgetType::ViewSwitcher(w) => w.paint(ctx, data, env),
+            WidgetType::ZStack(w) => w.paint(ctx, data, env),
+            WidgetType::None => {}
+        };
+    }
+    
+    fn paint_focus_ring(&mut self, ctx: &mut PaintCtx, data: &D, env: &Env) {
+        match self.get_widget() {
+            WidgetType::Align(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Arc(w)     => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Checkbox(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Container(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::DatePicker(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Either(w)   => w.paint_focus_ring(ctx, data, env),
+            //  WidgetType::Button(w)  => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Flex(w)    => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Gauge(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Grid(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Image(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Keyboard(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Label(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::List(w)    => w.paint_focus_ring(ctx, data, env),
+            #[cfg(test)]
+            WidgetType::Modular(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Padding(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Painter(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::ProgressBar(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Radio(w)   => w.paint_focus_ring(ctx, data, env),
+            WidgetType::RadioGroup(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Roller(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Scroll(w)  => w.paint_focus_ring(ctx, data, env),
+            WidgetType::SizedBox(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Slider(w)  => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Spinner(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Split(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Stepper(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Svg(w)     => w.paint_focus_ring(ctx, data, env),
+            WidgetType::Switch(w)  => w.paint_focus_ring(ctx, data, env),
+            WidgetType::TextBox(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::TimePicker(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::ViewSwitcher(w) => w.paint_focus_ring(ctx, data, env),
+            WidgetType::ZStack(w) => w.paint_focus_ring(ctx, data, env),
             WidgetType::None => {}
         };
     }
@@ -155,11 +352,40 @@ impl<D: Data + 'static + Default> Default for WidgetType<D> {
 pub enum WidgetType<D: Clone /* Data + 'static + Default */> {
     None,
     Align(Align<D>),
+    Arc(Arc<D>),
+    Checkbox(Checkbox<D>),
+    Container(Container<D>),
+    DatePicker(DatePicker<D>),
+    Either(Either<D>),
     //  Button(Button<D>),
     Flex(Flex<D>),
+    Gauge(Gauge<D>),
+    Grid(Grid<D>),
+    Image(Image),
+    Keyboard(Keyboard<D>),
     Label(Label<D>),
+    List(List<D>),
+    /// Throwaway widgets for the tests
+    #[cfg(test)]
+    Modular(crate::tests::helpers::ModularWidget<D>),
     Padding(Padding<D>),
+    Painter(Painter<D>),
+    ProgressBar(ProgressBar<D>),
+    Radio(Radio<D>),
+    RadioGroup(RadioGroup<D>),
+    Roller(Roller<D>),
+    Scroll(Scroll<D>),
     SizedBox(SizedBox<D>),
+    Slider(Slider<D>),
+    Spinner(Spinner<D>),
+    Split(Split<D>),
+    Stepper(Stepper<D>),
+    Svg(Svg),
+    Switch(Switch<D>),
+    TextBox(TextBox<D>),
+    TimePicker(TimePicker<D>),
+    ViewSwitcher(ViewSwitcher<D>),
+    ZStack(ZStack<D>),
     ////Spacer(Spacer<D>), ////TODO
 }
 
@@ -176,6 +402,14 @@ static mut ALL_WIDGETS_STATE: [ druid::WidgetType<State>; druid::MAX_WIDGETS ] =
     druid::WidgetType::None, druid::WidgetType::None, druid::WidgetType::None, druid::WidgetType::None, druid::WidgetType::None,
 ];
 
+/// ALL_WIDGET_IDS[i] is the WidgetId of the Widget in ALL_WIDGETS_STATE[i], None if the slot is free
+static mut ALL_WIDGET_IDS_STATE: [ Option<druid::WidgetId>; druid::MAX_WIDGETS ] = [ None; druid::MAX_WIDGETS ];
+
+/// Return the slot in ALL_WIDGETS_STATE for the WidgetId
+fn find_widget_state(id: Option<druid::WidgetId>) -> Option<usize> {
+    unsafe { ALL_WIDGET_IDS_STATE.iter().position(|slot| *slot == id) }
+}
+
 /*
 /// ALL_WINDOWS[i] is the WindowBox for the Window with window ID i. i=0 is not used.
 static mut ALL_WINDOWS_STATE: [ druid::WindowBox<State>; druid::MAX_WINDOWS ] = [
@@ -194,15 +428,76 @@ static mut ALL_HANDLERS_STATE: [ druid::DruidHandler<State>; druid::MAX_WINDOWS
 
 /// Specialised Trait to reference Widgets statically on embedded platforms
 impl druid::StaticWidgets<State> for druid::BoxedWidget<State> {
-    /// Fetch the static Widgets for the Data type
-    fn get_widgets(&self) -> &'static mut [ druid::WidgetType<State> ] {
-        unsafe { &mut ALL_WIDGETS_STATE }
+    /// Fetch the static Widget in the box
+    fn get_widget(&self) -> &'static mut druid::WidgetType<State> {
+        let slot = find_widget_state(Some(self.0))
+            .unwrap_or_else(|| panic!("unknown widget id {}", self.0.0));
+        unsafe { &mut ALL_WIDGETS_STATE[slot] }
     }
     /// Add a Widget for the Data type
     fn add_widget(&self, widget: druid::WidgetType<State>) {
-        assert!((self.0.0 as usize)< druid::MAX_WIDGETS, "too many widgets");
-        unsafe { ALL_WIDGETS_STATE[self.0.0 as usize] = widget; }        
+        debug_assert!(
+            find_widget_state(Some(self.0)).is_none(),
+            "duplicate widget id {}", self.0.0
+        );
+        let slot = find_widget_state(None).expect("too many widgets");
+        unsafe {
+            ALL_WIDGET_IDS_STATE[slot] = Some(self.0);
+            ALL_WIDGETS_STATE[slot] = widget;
+        }
     }    
+    /// Remove the Widget for the Data type
+    fn remove_widget(&self) {
+        if let Some(slot) = find_widget_state(Some(self.0)) {
+            unsafe {
+                ALL_WIDGET_IDS_STATE[slot] = None;
+                ALL_WIDGETS_STATE[slot] = druid::WidgetType::None;
+            }
+        }
+    }
+}
+
+/// Lock on the static Widgets, held by each test that boxes Widgets for the Data type,
+/// since tests run in parallel. The Widgets are removed and their WidgetIds freed
+/// when the lock is acquired and again when it's dropped, even if the test panics.
+#[cfg(test)]
+pub(crate) struct WidgetsLock(());
+
+#[cfg(test)]
+static WIDGETS_LOCKED: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(false);
+
+#[cfg(test)]
+impl WidgetsLock {
+    /// Wait for the other tests to drop the lock, then clear the static Widgets
+    pub(crate) fn acquire() -> Self {
+        use ::core::sync::atomic::Ordering;
+        while WIDGETS_LOCKED.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            ::core::hint::spin_loop();
+        }
+        clear_widgets_state();
+        WidgetsLock(())
+    }
+}
+
+#[cfg(test)]
+impl Drop for WidgetsLock {
+    fn drop(&mut self) {
+        clear_widgets_state();
+        WIDGETS_LOCKED.store(false, ::core::sync::atomic::Ordering::Release);
+    }
+}
+
+/// Remove all static Widgets and free their WidgetIds
+#[cfg(test)]
+fn clear_widgets_state() {
+    for slot in 0..druid::MAX_WIDGETS {
+        unsafe {
+            if let Some(id) = ALL_WIDGET_IDS_STATE[slot].take() {
+                ALL_WIDGETS_STATE[slot] = druid::WidgetType::None;
+                id.free();
+            }
+        }
+    }
 }
 
 /*
@@ -293,3 +588,62 @@ impl druid::GlobalWindows<State> for druid::AppState<State> {
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CounterType, Vec};
+
+    #[test]
+    fn removed_widgets_free_their_slots() {
+        let _lock = WidgetsLock::acquire();
+        let boxed: Vec<BoxedWidget<State>> = (0..MAX_WIDGETS as CounterType)
+            .map(|i| BoxedWidget::new_by_id(WidgetId::reserved(i)))
+            .collect();
+        for b in &boxed {
+            b.add_widget(WidgetType::Spinner(Spinner::new()));
+        }
+        boxed[3].clone().remove();
+        let extra = BoxedWidget::<State>::new_by_id(WidgetId::reserved(MAX_WIDGETS as CounterType));
+        extra.add_widget(WidgetType::Label(Label::new("extra")));
+        assert!(matches!(extra.get_widget(), WidgetType::Label(_)));
+        assert!(matches!(boxed[4].get_widget(), WidgetType::Spinner(_)));
+    }
+
+    #[test]
+    fn rebuilt_screens_free_their_widgets() {
+        let _lock = WidgetsLock::acquire();
+        let env = crate::theme::init();
+        // Many more screens than there are ids
+        for _ in 0..300 {
+            let root = Flex::row()
+                .with_child(Label::new("shown"))
+                .with_child(Either::new(|_, _| true, Label::new("true"), Label::new("false")));
+            let mut window = crate::Window::new(crate::WindowId::next(), root);
+            window.event(crate::Event::WindowConnected, &mut (), &env);
+            window.remove(&(), &env);
+            // Slots are taken from the first, so none is left behind, not even the hidden branch's
+            assert_eq!(find_widget_state(None), Some(0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown widget id")]
+    fn removed_widgets_are_gone() {
+        let _lock = WidgetsLock::acquire();
+        let boxed = BoxedWidget::<State>::new_by_id(WidgetId::reserved(8));
+        boxed.add_widget(WidgetType::Spinner(Spinner::new()));
+        boxed.remove_widget();
+        boxed.get_widget();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "duplicate widget id")]
+    fn duplicate_ids_are_caught() {
+        let _lock = WidgetsLock::acquire();
+        let boxed = BoxedWidget::<State>::new_by_id(WidgetId::reserved(9));
+        boxed.add_widget(WidgetType::Spinner(Spinner::new()));
+        boxed.add_widget(WidgetType::Spinner(Spinner::new()));
+    }
+}
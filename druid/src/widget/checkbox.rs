@@ -0,0 +1,254 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A checkbox widget.
+
+use crate::{Point, Rect, Size}; ////
+////use crate::kurbo::{BezPath, Point, Rect, Size};
+////use crate::piet::{LineCap, LineJoin, LinearGradient, RenderContext, StrokeStyle, UnitPoint};
+use crate::theme;
+use crate::widget::{Label, LabelText};
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx, ////
+    ////BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    Widget, WidgetPod, ////
+    ////Widget, WidgetExt, WidgetPod,
+};
+use crate::{LinearGradient, PolyLine, ScreenCoord, UnitPoint, WidgetId, WidgetType}; ////
+
+/// A checkbox that toggles a `bool`.
+#[derive(Clone)] ////
+pub struct Checkbox<T> { ////
+////pub struct Checkbox {
+    id: WidgetId, ////
+    child_label: WidgetPod<T, Label<T>>, ////
+    ////child_label: WidgetPod<bool, Box<dyn Widget<bool>>>,
+    /// Reads the state of the checkbox.
+    get: fn(&T) -> bool, ////
+    /// Writes the state of the checkbox.
+    set: fn(&mut T, bool), ////
+}
+
+impl Checkbox<bool> { ////
+////impl Checkbox {
+    /// Create a new `Checkbox` with a label.
+    pub fn new(label: impl Into<LabelText<bool>>) -> Checkbox<bool> { ////
+    ////pub fn new(label: impl Into<LabelText<bool>>) -> Checkbox {
+        Checkbox {
+            id: WidgetId::next(), ////
+            child_label: WidgetPod::new(Label::new(label)), ////
+            ////child_label: WidgetPod::new(Label::new(label).boxed()),
+            get: |data| *data, ////
+            set: |data, value| *data = value, ////
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Checkbox<T> { ////
+////impl Widget<bool> for Checkbox {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) { ////
+    ////fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut bool, _env: &Env) {
+        match event {
+            Event::MouseDown(_) => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    if ctx.is_hot() {
+                        let checked = (self.get)(data); ////
+                        (self.set)(data, !checked); ////
+                        ////if *data {
+                            ////*data = false;
+                        ////} else {
+                            ////*data = true;
+                        ////}
+                    }
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) { ////
+    ////fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &bool, _env: &Env) {
+        self.child_label.lifecycle(ctx, event, data, env); ////
+        match event { ////
+            LifeCycle::WidgetAdded => ctx.register_for_focus(), ////
+            LifeCycle::HotChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(), ////
+            _ => (), ////
+        } ////
+        ////if let LifeCycle::HotChanged(_) = event {
+            ////ctx.request_paint();
+        ////}
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &bool, _data: &bool, _env: &Env) {
+        self.child_label.update(ctx, data, env); ////
+        ctx.request_paint();
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size { ////
+    ////fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &bool, env: &Env) -> Size {
+        bc.debug_check("Checkbox");
+
+        ////let label_size = self.child_label.layout(ctx, &bc, data, env);
+        let padding: ScreenCoord = 8; ////
+        ////let padding = 8.0;
+        let label_x_offset = theme::BASIC_WIDGET_HEIGHT.resolve(env) + padding; ////
+        ////let label_x_offset = env.get(theme::BASIC_WIDGET_HEIGHT) + padding;
+        // The label takes the width beside the box, so that its rect stays on the screen ////
+        let label_size = self.child_label.layout(ctx, &bc.shrink((label_x_offset, 0)), data, env); ////
+        let origin = Point::new(label_x_offset, 0); ////
+        ////let origin = Point::new(label_x_offset, 0.0);
+
+        self.child_label.set_layout_rect(
+            ctx,
+            data,
+            env,
+            Rect::from_origin_size(origin, label_size),
+        );
+
+        bc.constrain(Size::new(
+            label_x_offset.saturating_add(label_size.width), ////
+            ////label_x_offset + label_size.width,
+            theme::BASIC_WIDGET_HEIGHT.resolve(env).max(label_size.height), ////
+            ////env.get(theme::BASIC_WIDGET_HEIGHT).max(label_size.height),
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &bool, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let cb = lv_checkbox_create(lv_scr_act(), NULL);        //  Add a checkbox to the current screen
+        lv_checkbox_set_text(cb, label_text);                   //  Set the label from the child label
+        lv_checkbox_set_checked(cb, (self.get)(data));          //  Check or uncheck the box
+        if ctx.is_focused() { lv_obj_add_state(cb, LV_STATE_FOCUSED); }  //  Show the focus highlight
+        */
+        let size = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let border_width = 1; ////
+        ////let border_width = 1.;
+
+        let rect = Size::new(size, size)
+            .to_rect()
+            ////.inset(-border_width / 2.)
+            .to_rounded_rect(2); ////
+            ////.to_rounded_rect(2.);
+
+        //Paint the background
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::BACKGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::BACKGROUND_LIGHT),
+                theme::BACKGROUND_DARK.resolve(env), ////
+                ////env.get(theme::BACKGROUND_DARK),
+            ),
+        );
+
+        ctx.fill(rect, &background_gradient);
+
+        let border_color = if ctx.is_hot() {
+            theme::BORDER_LIGHT.resolve(env) ////
+            ////env.get(theme::BORDER_LIGHT)
+        } else {
+            theme::BORDER_DARK.resolve(env) ////
+            ////env.get(theme::BORDER_DARK)
+        };
+
+        ctx.stroke(rect, &border_color, border_width);
+
+        if (self.get)(data) { ////
+        ////if *data {
+            // Paint the checkmark
+            //  The checkmark was drawn for a box of 18 pixels, so it's scaled to the box
+            let scale = |v: ScreenCoord| (v as u16 * size as u16 / 18) as ScreenCoord; ////
+            let path = [ ////
+                Point::new(scale(4), scale(9)), ////
+                Point::new(scale(8), scale(13)), ////
+                Point::new(scale(14), scale(5)), ////
+            ]; ////
+            ctx.stroke(PolyLine::new(&path), &theme::LABEL_COLOR.resolve(env), scale(2)); ////
+            ////let mut path = BezPath::new();
+            ////path.move_to((4.0, 9.0));
+            ////path.line_to((8.0, 13.0));
+            ////path.line_to((14.0, 5.0));
+
+            ////let mut style = StrokeStyle::new();
+            ////style.set_line_cap(LineCap::Round);
+            ////style.set_line_join(LineJoin::Round);
+
+            ////ctx.stroke_styled(path, &env.get(theme::LABEL_COLOR), 2., &style);
+        }
+
+        // Paint the text label
+        self.child_label.paint(ctx, data, env);
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Checkbox(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer};
+    use crate::tests::harness::Harness;
+    use crate::MouseEvent;
+
+    #[test]
+    fn tap_toggles() {
+        Harness::create_simple(false, || Checkbox::new("Check"), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let mouse = MouseEvent::new(Point::new(4, 4));
+            harness.event(Event::MouseDown(mouse.clone()));
+            harness.event(Event::MouseUp(mouse));
+            assert!(*harness.data());
+            harness.inspect_state(|state| assert!(!state.is_active));
+        });
+    }
+
+    /// Lay out and paint a checkbox that is `checked` over the whole framebuffer.
+    fn paint(fb: &Framebuffer, checked: bool) {
+        Harness::create_with_render(checked, || Checkbox::new("Check"), fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+    }
+
+    #[test]
+    fn checkmark_is_painted_when_checked() {
+        let fb = Framebuffer::lock();
+        paint(&fb, false);
+        // A bordered box with rounded corners
+        assert_eq!(fb.row(0)[..2], [0, 0x3a]);
+        assert!(fb.row(26)[16] < 0x3a);
+
+        // The checkmark bends at (16, 26) in a box of 36 pixels
+        fb.clear();
+        paint(&fb, true);
+        assert_eq!(fb.row(26)[16], 0xff);
+    }
+}
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{Affine, Size};
-use crate::{ScreenFactor}; ////
+use crate::{Data, ScreenFactor}; ////
 
 // These are based on https://api.flutter.dev/flutter/painting/BoxFit-class.html
 #[derive(Clone, Copy, PartialEq)]
@@ -46,8 +46,10 @@ impl FillStrat {
     /// This takes some properties of a widget and a fill strategy and returns an affine matrix
     /// used to position and scale the image in the widget.
     pub fn affine_to_fill(self, parent: Size, fit_box: Size) -> Affine {
-        let raw_scalex = parent.width / fit_box.width;
-        let raw_scaley = parent.height / fit_box.height;
+        let raw_scalex = parent.width as ScreenFactor / fit_box.width as ScreenFactor; ////
+        let raw_scaley = parent.height as ScreenFactor / fit_box.height as ScreenFactor; ////
+        ////let raw_scalex = parent.width / fit_box.width;
+        ////let raw_scaley = parent.height / fit_box.height;
 
         let (scalex, scaley) = match self {
             FillStrat::Contain => {
@@ -62,20 +64,77 @@ impl FillStrat {
             FillStrat::FitHeight => (raw_scaley, raw_scaley),
             FillStrat::FitWidth => (raw_scalex, raw_scalex),
             FillStrat::ScaleDown => {
-                let scale = raw_scalex.min(raw_scaley).min(1); ////
+                let scale = raw_scalex.min(raw_scaley).min(1.0);
                 ////let scale = raw_scalex.min(raw_scaley).min(1.0);
                 (scale, scale)
             }
-            FillStrat::None => (1, 1), ////
-            ////FillStrat::None => (1.0, 1.0),
+            FillStrat::None => (1.0, 1.0),
         };
 
-        let origin_x = (parent.width - (fit_box.width * scalex)) / 2; ////
+        let origin_x = (parent.width as ScreenFactor - (fit_box.width as ScreenFactor * scalex)) / 2.0; ////
         ////let origin_x = (parent.width - (fit_box.width * scalex)) / 2.0;
-        let origin_y = (parent.height - (fit_box.height * scaley)) / 2; ////
+        let origin_y = (parent.height as ScreenFactor - (fit_box.height as ScreenFactor * scaley)) / 2.0; ////
         ////let origin_y = (parent.height - (fit_box.height * scaley)) / 2.0;
 
-        Affine::new([scalex as ScreenFactor, 0., 0., scaley as ScreenFactor, origin_x as ScreenFactor, origin_y as ScreenFactor]) ////
+        Affine::new([scalex, 0., 0., scaley, origin_x, origin_y])
         ////Affine::new([scalex, 0., 0., scaley, origin_x, origin_y])
     }
 }
+
+/// A numeric value that can be edited with a [`Slider`] or [`Stepper`].
+///
+/// Values are converted to `ScreenFactor` while they are edited, and integer
+/// values are rounded to the nearest integer when converted back.
+///
+/// [`Slider`]: struct.Slider.html
+/// [`Stepper`]: struct.Stepper.html
+pub trait Numeric: Data + Copy {
+    /// Convert the value to a `ScreenFactor`.
+    fn to_factor(self) -> ScreenFactor;
+    /// Convert a `ScreenFactor` back to the value, saturating at the bounds of the type.
+    fn from_factor(value: ScreenFactor) -> Self;
+}
+
+macro_rules! impl_numeric_float {
+    ($t:ty) => {
+        impl Numeric for $t {
+            fn to_factor(self) -> ScreenFactor {
+                self as ScreenFactor
+            }
+            fn from_factor(value: ScreenFactor) -> Self {
+                value as $t
+            }
+        }
+    };
+}
+
+macro_rules! impl_numeric_int {
+    ($t:ty) => {
+        impl Numeric for $t {
+            fn to_factor(self) -> ScreenFactor {
+                self as ScreenFactor
+            }
+            fn from_factor(value: ScreenFactor) -> Self {
+                round(value) as $t
+            }
+        }
+    };
+}
+
+impl_numeric_float!(f32);
+impl_numeric_float!(f64);
+impl_numeric_int!(i8);
+impl_numeric_int!(i16);
+impl_numeric_int!(i32);
+impl_numeric_int!(u8);
+impl_numeric_int!(u16);
+impl_numeric_int!(u32);
+
+/// Round half away from zero, as `f32::round` is not in `core`.
+pub(crate) fn round(value: ScreenFactor) -> ScreenFactor {
+    if value >= 0. {
+        (value + 0.5) as i32 as ScreenFactor
+    } else {
+        (value - 0.5) as i32 as ScreenFactor
+    }
+}
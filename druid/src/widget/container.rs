@@ -0,0 +1,310 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A widget that provides simple visual styling options to a child.
+
+use super::BackgroundBrush;
+use crate::{Point, Rect, Size}; ////
+////use crate::shell::kurbo::{Point, Rect, Size};
+use crate::{
+    BoxConstraints, Color, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintBrush, PaintCtx, UpdateCtx, Widget, WidgetPod, ////
+    ////LifeCycleCtx, PaintCtx, RenderContext, UpdateCtx, Widget, WidgetPod,
+};
+use crate::{BoxedWidget, ScreenCoord, WidgetId, WidgetType}; ////
+
+#[derive(Clone)] ////
+struct BorderStyle {
+    width: KeyOrValue<ScreenCoord>, ////
+    ////width: KeyOrValue<f64>,
+    color: KeyOrValue<Color>,
+}
+
+/// A widget that provides simple visual styling options to a child.
+#[derive(Clone)] ////
+pub struct Container<T> {
+    id: WidgetId, ////
+    background: Option<BackgroundBrush<T>>,
+    border: Option<BorderStyle>,
+    corner_radius: KeyOrValue<ScreenCoord>, ////
+    ////corner_radius: KeyOrValue<f64>,
+
+    inner: WidgetPod<T, BoxedWidget<T>>, ////
+    ////inner: WidgetPod<T, Box<dyn Widget<T>>>,
+}
+
+impl<T: Data> Container<T> {
+    /// Create Container with a child
+    pub fn new(inner: impl Widget<T> + 'static + Clone) -> Self { ////
+    ////pub fn new(inner: impl Widget<T> + 'static) -> Self {
+        Self {
+            id: WidgetId::next(), ////
+            background: None,
+            border: None,
+            corner_radius: 0.into(), ////
+            ////corner_radius: 0.0.into(),
+            inner: WidgetPod::new(inner).boxed(),
+        }
+    }
+
+    /// Builder-style method for setting the background for this widget.
+    ///
+    /// This can be passed a [`Color`], a [`LinearGradient`], or a `KeyOrValue<Color>`
+    /// from the [`theme`].
+    ///
+    /// [`Color`]: ../enum.Color.html
+    /// [`LinearGradient`]: ../piet/struct.LinearGradient.html
+    /// [`theme`]: ../theme/index.html
+    pub fn background(mut self, brush: impl Into<BackgroundBrush<T>>) -> Self {
+        self.set_background(brush);
+        self
+    }
+
+    /// Set the background for this widget.
+    ///
+    /// This can be passed a [`Color`], a [`LinearGradient`], or a `KeyOrValue<Color>`
+    /// from the [`theme`].
+    ///
+    /// [`Color`]: ../enum.Color.html
+    /// [`LinearGradient`]: ../piet/struct.LinearGradient.html
+    /// [`theme`]: ../theme/index.html
+    pub fn set_background(&mut self, brush: impl Into<BackgroundBrush<T>>) {
+        self.background = Some(brush.into());
+    }
+
+    /// Builder-style method for painting a border around the widget with a color and width.
+    ///
+    /// Arguments can be either concrete values, or a `KeyOrValue` of the respective
+    /// type.
+    pub fn border(
+        mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<ScreenCoord>>, ////
+        ////width: impl Into<KeyOrValue<f64>>,
+    ) -> Self {
+        self.set_border(color, width);
+        self
+    }
+
+    /// Paint a border around the widget with a color and width.
+    ///
+    /// Arguments can be either concrete values, or a `KeyOrValue` of the respective
+    /// type.
+    pub fn set_border(
+        &mut self,
+        color: impl Into<KeyOrValue<Color>>,
+        width: impl Into<KeyOrValue<ScreenCoord>>, ////
+        ////width: impl Into<KeyOrValue<f64>>,
+    ) {
+        self.border = Some(BorderStyle {
+            color: color.into(),
+            width: width.into(),
+        });
+    }
+
+    /// Builder style method for rounding off corners of this container by setting a corner radius
+    pub fn rounded(mut self, radius: impl Into<KeyOrValue<ScreenCoord>>) -> Self { ////
+    ////pub fn rounded(mut self, radius: impl Into<KeyOrValue<f64>>) -> Self {
+        self.set_rounded(radius);
+        self
+    }
+
+    /// Round off corners of this container by setting a corner radius
+    pub fn set_rounded(&mut self, radius: impl Into<KeyOrValue<ScreenCoord>>) { ////
+    ////pub fn set_rounded(&mut self, radius: impl Into<KeyOrValue<f64>>) {
+        self.corner_radius = radius.into();
+    }
+}
+
+impl<T: Data> Widget<T> for Container<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        self.inner.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.inner.lifecycle(ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if let Some(BackgroundBrush::Painter(p)) = self.background.as_mut() {
+            p.update(ctx, old_data, data, env);
+        }
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Container");
+
+        // Shrink constraints by border offset
+        let border_width = match &self.border {
+            Some(border) => border.width.resolve(env),
+            None => 0, ////
+            ////None => 0.0,
+        };
+        let child_bc = bc.shrink((border_width.saturating_mul(2), border_width.saturating_mul(2))); ////
+        ////let child_bc = bc.shrink((2.0 * border_width, 2.0 * border_width));
+        let size = self.inner.layout(ctx, &child_bc, data, env);
+        let origin = Point::new(border_width, border_width);
+        self.inner
+            .set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, size));
+
+        let my_size = Size::new(
+            size.width.saturating_add(border_width.saturating_mul(2)), ////
+            size.height.saturating_add(border_width.saturating_mul(2)), ////
+            ////size.width + 2.0 * border_width,
+            ////size.height + 2.0 * border_width,
+        );
+
+        let my_insets = self.inner.compute_parent_paint_insets(my_size);
+        ctx.set_paint_insets(my_insets);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let corner_radius = self.corner_radius.resolve(env);
+
+        //// TODO: Render with LVGL
+        /*
+        let cont = lv_cont_create(lv_scr_act(), NULL);                          //  Add a container to the current screen
+        lv_style_init(&mut style);                                              //  Style the container
+        lv_style_set_bg_color(&mut style, LV_STATE_DEFAULT, stops.0);           //  Background color, or top of the gradient
+        lv_style_set_bg_grad_color(&mut style, LV_STATE_DEFAULT, stops.1);      //  Bottom of the gradient
+        lv_style_set_bg_grad_dir(&mut style, LV_STATE_DEFAULT, LV_GRAD_DIR_VER);
+        lv_style_set_radius(&mut style, LV_STATE_DEFAULT, corner_radius);       //  Rounded corners
+        lv_style_set_border_width(&mut style, LV_STATE_DEFAULT, border_width);  //  Border
+        lv_style_set_border_color(&mut style, LV_STATE_DEFAULT, border_color);
+        lv_obj_add_style(cont, LV_CONT_PART_MAIN, &mut style);
+        */
+        if let Some(background) = self.background.as_mut() {
+            let panel = ctx.size().to_rect().to_rounded_rect(corner_radius); ////
+            ////let panel = ctx.size().to_rounded_rect(corner_radius);
+
+            ctx.with_save(|ctx| {
+                match background.to_brush(env) { ////
+                    Some(brush) => ctx.fill(panel, &brush), ////
+                    None => { ////
+                        ctx.clip(panel.rect()); ////
+                        ////ctx.clip(panel);
+                        background.paint(ctx, data, env);
+                    } ////
+                } ////
+            });
+        }
+
+        if let Some(border) = &self.border {
+            let border_width = border.width.resolve(env);
+            let border_rect = ctx
+                .size()
+                .to_rect()
+                ////.inset(border_width / -2.0)
+                .to_rounded_rect(corner_radius);
+            ctx.stroke(border_rect, &border.color.resolve(env), border_width);
+        };
+
+        // Anti-aliased children blend into a plain background
+        if let Some(PaintBrush::Color(color)) = self.background.as_ref().and_then(|background| background.to_brush(env)) { ////
+            ctx.render_ctx.set_background(color); ////
+        } ////
+
+        self.inner.paint(ctx, data, env);
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Container(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+    use crate::tests::harness::Harness;
+    use crate::widget::{Padding, Painter, SizedBox};
+    use ::core::cell::Cell;
+
+    const BACKGROUND: Color = Color::rgb8(0x40, 0, 0);
+    const BORDER: Color = Color::rgb8(0xff, 0, 0);
+
+    /// Lay out and paint `container` over the whole framebuffer.
+    fn paint(fb: &Framebuffer, container: impl FnOnce() -> Container<()>) {
+        Harness::create_with_render((), container, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+    }
+
+    #[test]
+    fn background_fills_the_container() {
+        let fb = Framebuffer::lock();
+        paint(&fb, || Container::new(SizedBox::empty()).background(BACKGROUND));
+        assert!((0..SIZE).all(|y| fb.row(y) == [0x40; SIZE]));
+    }
+
+    #[test]
+    fn border_is_stroked_inside_the_background() {
+        let fb = Framebuffer::lock();
+        paint(&fb, || Container::new(SizedBox::empty()).background(BACKGROUND).border(BORDER, 2));
+        assert_eq!(fb.row(0), [0xff; SIZE]);
+        assert_eq!(fb.row(SIZE - 1), [0xff; SIZE]);
+        assert_eq!(fb.row(8)[..4], [0xff, 0xff, 0x40, 0x40]);
+        assert_eq!(fb.row(8)[SIZE - 3..], [0x40, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn wide_border_leaves_no_room_for_the_child() {
+        let child = Cell::new(None);
+        let container = || {
+            let sized = SizedBox::empty();
+            child.set(sized.id());
+            Container::new(sized).border(BORDER, 200)
+        };
+        Harness::create_simple((), container, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let state = harness.get_state(child.get().unwrap());
+            assert_eq!(state.layout_rect(), Rect::new(200, 200, 200, 200));
+        });
+    }
+
+    #[test]
+    fn rounded_corners_are_left_unpainted() {
+        let fb = Framebuffer::lock();
+        paint(&fb, || Container::new(SizedBox::empty()).background(BACKGROUND).rounded(4));
+        assert_eq!(fb.row(0)[..4], [0, 0, 0x40, 0x40]);
+        assert_eq!(fb.row(0)[SIZE - 2..], [0, 0]);
+        assert_eq!(fb.row(8), [0x40; SIZE]);
+        assert_eq!(fb.row(SIZE - 1)[..2], [0, 0]);
+    }
+
+    #[test]
+    fn painter_background_clip_ends_with_the_background() {
+        // The background clips itself to a corner, which mustn't hide the padded child
+        let fb = Framebuffer::lock();
+        paint(&fb, || {
+            let background = Painter::new(|ctx, _, _| {
+                ctx.clip(Rect::new(0, 0, 2, 2));
+                ctx.fill(ctx.size().to_rect(), &BACKGROUND);
+            });
+            let child = Painter::new(|ctx, _, _| ctx.fill(ctx.size().to_rect(), &BORDER));
+            Container::new(Padding::new(4, child)).background(background)
+        });
+        assert_eq!(fb.row(0)[..4], [0x40, 0x40, 0, 0]);
+        assert_eq!(fb.row(2)[..4], [0, 0, 0, 0]);
+        assert_eq!(fb.row(8)[8], 0xff);
+    }
+}
@@ -0,0 +1,200 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A widget that switches dynamically between two child views.
+
+use crate::{Point, Rect, Size}; ////
+////use crate::kurbo::{Point, Rect, Size};
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    UpdateCtx, Widget, WidgetPod,
+};
+use crate::{BoxedWidget, WidgetId, WidgetType}; ////
+
+/// A widget that switches between two possible child views.
+///
+/// Both branches are registered in the static `Widgets` up front. Only the
+/// current branch is sent events and lifecycle events, laid out and painted,
+/// so a branch receives `WidgetAdded` when it's first shown, and a hidden
+/// branch is left out of the focus chain. Both branches are sent `WidgetRemoved`.
+#[derive(Clone)] ////
+pub struct Either<T> {
+    id: WidgetId, ////
+    closure: fn(&T, &Env) -> bool, ////
+    ////closure: Box<dyn Fn(&T, &Env) -> bool>,
+    true_branch: WidgetPod<T, BoxedWidget<T>>, ////
+    false_branch: WidgetPod<T, BoxedWidget<T>>, ////
+    ////true_branch: WidgetPod<T, Box<dyn Widget<T>>>,
+    ////false_branch: WidgetPod<T, Box<dyn Widget<T>>>,
+    current: bool,
+}
+
+impl<T: Data> Either<T> { ////
+////impl<T> Either<T> {
+    /// Create a new widget that switches between two views.
+    ///
+    /// The given closure is evaluated on data change. If its value is `true`, then
+    /// the `true_branch` widget is shown, otherwise `false_branch`.
+    pub fn new(
+        closure: fn(&T, &Env) -> bool, ////
+        true_branch: impl Widget<T> + 'static + Clone, ////
+        false_branch: impl Widget<T> + 'static + Clone, ////
+        ////closure: impl Fn(&T, &Env) -> bool + 'static,
+        ////true_branch: impl Widget<T> + 'static,
+        ////false_branch: impl Widget<T> + 'static,
+    ) -> Either<T> {
+        Either {
+            id: WidgetId::next(), ////
+            closure, ////
+            ////closure: Box::new(closure),
+            true_branch: WidgetPod::new(true_branch).boxed(),
+            false_branch: WidgetPod::new(false_branch).boxed(),
+            current: false,
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Either<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if self.current {
+            self.true_branch.event(ctx, event, data, env)
+        } else {
+            self.false_branch.event(ctx, event, data, env)
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.current = (self.closure)(data, env);
+        }
+        if let LifeCycle::WidgetRemoved = event { ////
+            self.true_branch.lifecycle(ctx, event, data, env);
+            self.false_branch.lifecycle(ctx, event, data, env);
+        } else if self.current {
+            self.true_branch.lifecycle(ctx, event, data, env);
+        } else {
+            self.false_branch.lifecycle(ctx, event, data, env);
+        }
+        ////self.true_branch.lifecycle(ctx, event, data, env);
+        ////self.false_branch.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let current = (self.closure)(data, env);
+        if current != self.current {
+            self.current = current;
+            // The branch shown now is added if it's new, and the focus chain is rebuilt
+            ctx.children_changed(); ////
+            ctx.request_paint(); ////
+            ////ctx.request_layout();
+        }
+        let branch = if self.current { &mut self.true_branch } else { &mut self.false_branch }; ////
+        // A branch that's not added yet is updated after `WidgetAdded`
+        if branch.is_initialized() { ////
+            branch.update(ctx, data, env);
+        }
+        ////if self.current {
+            ////self.true_branch.update(ctx, data, env);
+        ////} else {
+            ////self.false_branch.update(ctx, data, env);
+        ////}
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        if self.current {
+            let size = self.true_branch.layout(ctx, bc, data, env);
+            self.true_branch.set_layout_rect(
+                ctx,
+                data,
+                env,
+                Rect::from_origin_size(Point::ORIGIN, size),
+            );
+            ctx.set_paint_insets(self.true_branch.paint_insets());
+            size
+        } else {
+            let size = self.false_branch.layout(ctx, bc, data, env);
+            self.false_branch.set_layout_rect(
+                ctx,
+                data,
+                env,
+                Rect::from_origin_size(Point::ORIGIN, size),
+            );
+            ctx.set_paint_insets(self.false_branch.paint_insets()); ////
+            ////ctx.set_paint_insets(self.true_branch.paint_insets());
+            size
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        lv_obj_set_hidden(true_branch, !self.current);          //  Both branches are created once,
+        lv_obj_set_hidden(false_branch, self.current);          //  only the current one is shown
+        */
+        if self.current {
+            self.true_branch.paint_raw(ctx, data, env);
+        } else {
+            self.false_branch.paint_raw(ctx, data, env);
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Either(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::harness::Harness;
+    use crate::widget::RadioGroup;
+    use ::core::cell::Cell;
+    use ::core::sync::atomic::{AtomicBool, Ordering};
+
+    /// The branch to show, as the data of the tests can't change.
+    static SHOW_TRUE: AtomicBool = AtomicBool::new(true);
+
+    #[test]
+    fn switching_adds_and_lays_out_the_branch() {
+        let ids = Cell::new(None);
+        let widget = || {
+            let (yes, no) = (RadioGroup::new([("yes", ())]), RadioGroup::new([("no", ())]));
+            ids.set(Some((yes.id().unwrap(), no.id().unwrap())));
+            Either::new(|_, _| SHOW_TRUE.load(Ordering::Relaxed), yes, no)
+        };
+        SHOW_TRUE.store(true, Ordering::Relaxed);
+        Harness::create_simple((), widget, |harness| {
+            let (yes, no) = ids.get().unwrap();
+            harness.send_initial_events();
+            harness.just_layout();
+            // The hidden branch is not added, and can't take focus
+            assert_eq!(harness.window().focus_chain(), &[yes]);
+            assert!(harness.try_get_state(no).is_none());
+
+            SHOW_TRUE.store(false, Ordering::Relaxed);
+            harness.force_update();
+            assert_eq!(harness.window().focus_chain(), &[no]);
+            harness.just_layout();
+            assert!(harness.get_state(no).layout_rect().height() > 0);
+
+            // Switching back keeps the branch that was added first
+            SHOW_TRUE.store(true, Ordering::Relaxed);
+            harness.force_update();
+            assert_eq!(harness.window().focus_chain(), &[yes]);
+        });
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A gauge widget with hands and tick marks, for dials and analog watch faces.
+
+use crate::piet::polar;
+use crate::theme;
+use crate::{
+    BoxConstraints, Circle, Color, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle,
+    LifeCycleCtx, Line, PaintCtx, Point, Size, UpdateCtx, Widget,
+};
+use crate::{ScreenCoord, ScreenFactor, Vec, WidgetId, WidgetType};
+
+/// A dial with tick marks and one or more hands, like LVGL's `lv_gauge`.
+///
+/// Angles are in degrees clockwise from 3 o'clock, as for `lv_arc`. The scale
+/// starts at `start_angle` and covers `sweep` degrees, from the start to the end
+/// of the `range`. Every hand shows a value of the data, so one gauge can show
+/// both the current and the target speed, or the hours, minutes and seconds.
+///
+/// ```
+/// use druid::widget::{Gauge, Hand};
+///
+/// /// Steps walked today, out of a goal of 10,000
+/// let steps: Gauge<u32> = Gauge::new()
+///     .with_range(0., 10.)
+///     .with_ticks(21, 6)
+///     .with_hand(Hand::new(|steps: &u32| *steps as f32 / 1000.));
+/// ```
+#[derive(Clone)]
+pub struct Gauge<T> {
+    id: WidgetId,
+    range: (ScreenFactor, ScreenFactor),
+    start_angle: u16,
+    sweep: u16,
+    /// Number of tick marks, including both ends.
+    ticks: u8,
+    /// Number of numerals, drawn at evenly spaced major ticks.
+    numerals: u8,
+    tick_color: KeyOrValue<Color>,
+    hands: Vec<Hand<T>>,
+    /// Seconds since the data last changed, for sweeping hands.
+    elapsed: ScreenFactor,
+}
+
+/// A hand of a [`Gauge`], pointing at a value of the data.
+///
+/// [`Gauge`]: struct.Gauge.html
+#[derive(Clone)]
+pub struct Hand<T> {
+    value: fn(&T) -> ScreenFactor,
+    /// Length as a percentage of the gauge radius.
+    length: u8,
+    width: ScreenCoord,
+    color: KeyOrValue<Color>,
+    /// Units per second that the hand moves between data updates.
+    sweep_rate: ScreenFactor,
+}
+
+/// A watch face: a [`Gauge`] created with [`Gauge::clock`].
+///
+/// [`Gauge`]: struct.Gauge.html
+/// [`Gauge::clock`]: struct.Gauge.html#method.clock
+pub type AnalogClock<T> = Gauge<T>;
+
+/// Data that holds a time of day, for an [`AnalogClock`].
+///
+/// [`AnalogClock`]: type.AnalogClock.html
+pub trait ClockTime {
+    /// Hour, from 0 to 23.
+    fn hour(&self) -> u8;
+    /// Minute, from 0 to 59.
+    fn minute(&self) -> u8;
+    /// Second, from 0 to 59.
+    fn second(&self) -> u8;
+}
+
+/// A time of day, for data that is just the time.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl Time {
+    /// Create a time from the hour (0 to 23), minute and second.
+    pub fn new(hour: u8, minute: u8, second: u8) -> Self {
+        Time { hour, minute, second }
+    }
+}
+
+impl Data for Time {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl ClockTime for Time {
+    fn hour(&self) -> u8 { self.hour }
+    fn minute(&self) -> u8 { self.minute }
+    fn second(&self) -> u8 { self.second }
+}
+
+/// Length of tick marks, and major tick marks at the numerals.
+const TICK_LENGTH: ScreenFactor = 4.;
+const MAJOR_TICK_LENGTH: ScreenFactor = 8.;
+/// Longest that a sweeping hand moves on its own, if the data stops changing.
+const MAX_SWEEP_TIME: ScreenFactor = 1.;
+
+impl<T: Data> Gauge<T> {
+    /// Create a gauge with LVGL's default scale: 0 to 100 over 220 degrees,
+    /// opening at the bottom, with 21 ticks and 6 numerals.
+    pub fn new() -> Self {
+        Gauge {
+            id: WidgetId::next(),
+            range: (0., 100.),
+            start_angle: 160,
+            sweep: 220,
+            ticks: 21,
+            numerals: 6,
+            tick_color: theme::LABEL_COLOR,
+            hands: Vec::new(),
+            elapsed: 0.,
+        }
+    }
+
+    /// Builder-style method to set the values at the start and end of the scale.
+    pub fn with_range(mut self, min: ScreenFactor, max: ScreenFactor) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    /// Builder-style method to set the angle where the scale starts, and the angle it covers.
+    ///
+    /// A `sweep` of 360 makes a full circle, where the end of the range meets the start.
+    pub fn with_angles(mut self, start_angle: u16, sweep: u16) -> Self {
+        self.start_angle = start_angle;
+        self.sweep = sweep.min(360);
+        self
+    }
+
+    /// Builder-style method to set the number of tick marks and numerals.
+    pub fn with_ticks(mut self, ticks: u8, numerals: u8) -> Self {
+        self.ticks = ticks;
+        self.numerals = numerals;
+        self
+    }
+
+    /// Builder-style method to set the color of the tick marks.
+    pub fn with_tick_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.tick_color = color.into();
+        self
+    }
+
+    /// Builder-style method to add a hand. Hands are painted in the order they are added.
+    pub fn with_hand(mut self, hand: Hand<T>) -> Self {
+        if self.hands.push(hand).is_err() {
+            log::error!("Too many hands for gauge");
+        }
+        self
+    }
+
+    /// The angle of `value` on the scale.
+    fn angle_of(&self, value: ScreenFactor) -> ScreenFactor {
+        let (min, max) = self.range;
+        let mut fraction = if max == min { 0. } else { (value - min) / (max - min) };
+        if self.sweep == 360 {
+            fraction %= 1.;
+        } else {
+            fraction = fraction.clamp(0., 1.);
+        }
+        self.start_angle as ScreenFactor + fraction * self.sweep as ScreenFactor
+    }
+
+    /// The angles of the tick marks, and whether each is a major tick at a numeral.
+    fn tick_angles(&self) -> impl Iterator<Item = (ScreenFactor, bool)> + '_ {
+        // a full circle doesn't draw its last tick over the first
+        let steps = if self.sweep == 360 { self.ticks } else { self.ticks.saturating_sub(1) }.max(1);
+        let numeral_steps = if self.sweep == 360 { self.numerals } else { self.numerals.saturating_sub(1) };
+        let major_every = if numeral_steps == 0 { 0 } else { (steps / numeral_steps).max(1) };
+        (0..self.ticks).map(move |tick| {
+            let angle = self.start_angle as ScreenFactor
+                + self.sweep as ScreenFactor * tick as ScreenFactor / steps as ScreenFactor;
+            (angle, major_every > 0 && tick % major_every == 0)
+        })
+    }
+
+    fn has_sweeping_hand(&self) -> bool {
+        self.hands.iter().any(|hand| hand.sweep_rate > 0.)
+    }
+}
+
+impl<T: Data + ClockTime> Gauge<T> {
+    /// Create an analog clock face, with 60 ticks and 12 numerals, and hour,
+    /// minute and second hands. The second hand sweeps smoothly between the
+    /// updates to the time.
+    pub fn clock() -> Self {
+        Gauge::new()
+            .with_range(0., 60.)
+            .with_angles(270, 360)
+            .with_ticks(60, 12)
+            .with_hand(Hand::new(hour_value::<T>).length(50).width(4))
+            .with_hand(Hand::new(minute_value::<T>).length(80).width(3))
+            .with_hand(
+                Hand::new(second_value::<T>)
+                    .length(90)
+                    .width(1)
+                    .color(Color::rgb8(0xff, 0, 0))
+                    .sweep_rate(1.),
+            )
+    }
+}
+
+/// Position of the hour hand, on the scale of 60 minutes.
+fn hour_value<T: ClockTime>(time: &T) -> ScreenFactor {
+    (time.hour() % 12) as ScreenFactor * 5. + time.minute() as ScreenFactor / 12.
+}
+
+fn minute_value<T: ClockTime>(time: &T) -> ScreenFactor {
+    time.minute() as ScreenFactor + time.second() as ScreenFactor / 60.
+}
+
+fn second_value<T: ClockTime>(time: &T) -> ScreenFactor {
+    time.second() as ScreenFactor
+}
+
+impl<T> Hand<T> {
+    /// Create a hand that points at `value`.
+    pub fn new(value: fn(&T) -> ScreenFactor) -> Self {
+        Hand {
+            value,
+            length: 80,
+            width: 2,
+            color: theme::LABEL_COLOR,
+            sweep_rate: 0.,
+        }
+    }
+
+    /// Builder-style method to set the length, as a percentage of the gauge radius.
+    pub fn length(mut self, percent: u8) -> Self {
+        self.length = percent.min(100);
+        self
+    }
+
+    /// Builder-style method to set the width in pixels.
+    pub fn width(mut self, width: ScreenCoord) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Builder-style method to set the color.
+    pub fn color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Builder-style method to make the hand move on its own at `rate` units per second,
+    /// between the data updates, for up to a second. Use this for a smooth second hand.
+    pub fn sweep_rate(mut self, rate: ScreenFactor) -> Self {
+        self.sweep_rate = rate;
+        self
+    }
+}
+
+impl<T: Data> Widget<T> for Gauge<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded if self.has_sweeping_hand() => ctx.request_anim_frame(),
+            LifeCycle::AnimFrame(interval) if self.elapsed < MAX_SWEEP_TIME => {
+                self.elapsed = (self.elapsed + *interval as ScreenFactor * 1e-9).min(MAX_SWEEP_TIME);
+                ctx.request_paint();
+                ctx.request_anim_frame();
+            }
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            self.elapsed = 0.;
+            ctx.request_paint();
+            if self.has_sweeping_hand() {
+                ctx.request_anim_frame();
+            }
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        bc.debug_check("Gauge");
+
+        // square, as large as allowed
+        let size = if bc.is_width_bounded() && bc.is_height_bounded() {
+            let max = bc.max();
+            max.width.min(max.height)
+        } else {
+            theme::WIDE_WIDGET_WIDTH.resolve(env)
+        };
+        bc.constrain(Size::new(size, size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let center = Point::new(size.width / 2, size.height / 2);
+        let radius = (size.width.min(size.height) / 2) as ScreenFactor;
+
+        //// TODO: Render with LVGL
+        /*
+        let gauge = lv_gauge_create(lv_scr_act(), NULL);                 //  Add a gauge to the current screen
+        lv_obj_set_size(gauge, size.width, size.height);
+        lv_gauge_set_range(gauge, min, max);
+        lv_gauge_set_scale(gauge, sweep, ticks, numerals);               //  Numerals are drawn as labels by LVGL
+        lv_gauge_set_angle_offset(gauge, start_angle + sweep / 2 - 90);  //  LVGL centres the scale at 6 o'clock
+        lv_gauge_set_needle_count(gauge, hands.len(), hand_colors);
+        lv_gauge_set_value(gauge, i, value);                             //  For each hand; LVGL values are integers,
+                                                                         //  so sweeping hands animate with lv_anim_t
+        //  Without numerals, lv_linemeter_create(...) and lv_linemeter_set_scale(linemeter, sweep, ticks)
+        //  draw just the tick marks, and the hands are lv_line objects.
+        */
+
+        // Numerals need text rendering, so the software renderer marks them with major ticks
+        let tick_color = self.tick_color.resolve(env);
+        for (angle, major) in self.tick_angles() {
+            let length = if major { MAJOR_TICK_LENGTH } else { TICK_LENGTH };
+            let outer = polar(center, radius - 1., angle);
+            let inner = polar(center, radius - length, angle);
+            ctx.stroke(Line::new(inner, outer), &tick_color, if major { 2 } else { 1 });
+        }
+
+        for hand in self.hands.iter() {
+            let value = (hand.value)(data) + hand.sweep_rate * self.elapsed;
+            let tip = polar(center, radius * hand.length as ScreenFactor / 100., self.angle_of(value));
+            ctx.stroke(Line::new(center, tip), &hand.color.resolve(env), hand.width);
+        }
+        ctx.fill(Circle::new(center, 3), &tick_color);
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::Gauge(self)
+    }
+}
+
+impl<T: Data> Default for Gauge<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::lifecycle_ctx;
+
+    fn close(a: ScreenFactor, b: ScreenFactor) -> bool {
+        (a - b) * (a - b) < 1e-4
+    }
+
+    #[test]
+    fn clock_hands() {
+        let clock: AnalogClock<Time> = Gauge::clock();
+        let time = Time::new(15, 30, 45);
+        // 12 o'clock is 270 degrees, and the angles carry on round past 360
+        let angles: Vec<ScreenFactor> = clock.hands.iter().map(|hand| clock.angle_of((hand.value)(&time))).collect();
+        assert!(close(angles[0], 270. + 105.)); // half past three
+        assert!(close(angles[1], 270. + 184.5));
+        assert!(close(angles[2], 270. + 270.));
+
+        let ticks: Vec<(ScreenFactor, bool)> = clock.tick_angles().take(6).collect();
+        assert_eq!(ticks[1], (276., false));
+        assert_eq!(ticks[5], (300., true));
+    }
+
+    #[test]
+    fn second_hand_sweeps() {
+        let mut clock: AnalogClock<Time> = Gauge::clock();
+        let env = theme::init();
+        let mut ctx = lifecycle_ctx();
+        let frame = LifeCycle::AnimFrame(250_000_000);
+        clock.lifecycle(&mut ctx, &frame, &Time::default(), &env);
+        assert_eq!(clock.elapsed, 0.25);
+        for _ in 0..8 {
+            clock.lifecycle(&mut ctx, &frame, &Time::default(), &env);
+        }
+        // the hand waits for the next second once it gets there
+        assert_eq!(clock.elapsed, MAX_SWEEP_TIME);
+
+        // a gauge without sweeping hands only moves with the data
+        let gauge = Gauge::<u8>::new().with_range(0., 10.);
+        assert_eq!(gauge.angle_of(5.), 270.);
+        assert_eq!(gauge.angle_of(20.), 380.);
+    }
+}
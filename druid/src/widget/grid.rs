@@ -0,0 +1,300 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A widget that arranges its children in a grid of rows and columns.
+
+use crate::{
+    BoxConstraints, BoxedWidget, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Point, Rect, ScreenCoord, Size, UnitPoint, UpdateCtx, Widget, WidgetId, WidgetPod,
+    WidgetType,
+};
+
+/// Max number of children in a `Grid`, a 3 x 3 launcher screen within `MAX_WIDGETS`.
+type GridLength = heapless::consts::U9;
+
+/// A container that arranges its children in cells of equal size.
+///
+/// The number of rows and columns is fixed when the grid is created. Each child
+/// is centered in its cell, and may span several rows or columns. When the grid
+/// is bounded, the cells share the available space; otherwise they are as big
+/// as the biggest child.
+///
+/// A grid holds at most 9 children, which count against the [`MAX_WIDGETS`]
+/// of the static `Widgets`; further children are ignored.
+///
+/// [`MAX_WIDGETS`]: ../constant.MAX_WIDGETS.html
+#[derive(Clone)]
+pub struct Grid<T> {
+    id: WidgetId,
+    rows: u8,
+    columns: u8,
+    row_gap: ScreenCoord,
+    column_gap: ScreenCoord,
+    children: heapless::Vec<GridChild<T>, GridLength>,
+}
+
+#[derive(Clone)]
+struct GridChild<T> {
+    widget: WidgetPod<T, BoxedWidget<T>>,
+    cell: Cell,
+}
+
+/// The cells covered by a child.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cell {
+    row: u8,
+    column: u8,
+    row_span: u8,
+    column_span: u8,
+}
+
+impl<T: Data + Clone> Grid<T> {
+    /// Create a new, empty grid with the given number of rows and columns.
+    pub fn new(rows: u8, columns: u8) -> Self {
+        Grid {
+            id: WidgetId::next(),
+            rows: rows.max(1),
+            columns: columns.max(1),
+            row_gap: 0,
+            column_gap: 0,
+            children: heapless::Vec::new(),
+        }
+    }
+
+    /// Builder-style method to set the space between rows and between columns.
+    pub fn with_gap(self, gap: ScreenCoord) -> Self {
+        self.with_row_gap(gap).with_column_gap(gap)
+    }
+
+    /// Builder-style method to set the space between rows.
+    pub fn with_row_gap(mut self, gap: ScreenCoord) -> Self {
+        self.row_gap = gap;
+        self
+    }
+
+    /// Builder-style method to set the space between columns.
+    pub fn with_column_gap(mut self, gap: ScreenCoord) -> Self {
+        self.column_gap = gap;
+        self
+    }
+
+    /// Builder-style method to add a child in the cell at `row` and `column`, counting from 0.
+    pub fn with_child(self, child: impl Widget<T> + 'static + Clone, row: u8, column: u8) -> Self {
+        self.with_spanning_child(child, row, column, 1, 1)
+    }
+
+    /// Builder-style method to add a child that spans `row_span` rows and
+    /// `column_span` columns, starting at `row` and `column`.
+    pub fn with_spanning_child(
+        mut self,
+        child: impl Widget<T> + 'static + Clone,
+        row: u8,
+        column: u8,
+        row_span: u8,
+        column_span: u8,
+    ) -> Self {
+        self.add_spanning_child(child, row, column, row_span, column_span);
+        self
+    }
+
+    /// Add a child in the cell at `row` and `column`, counting from 0.
+    pub fn add_child(&mut self, child: impl Widget<T> + 'static + Clone, row: u8, column: u8) {
+        self.add_spanning_child(child, row, column, 1, 1);
+    }
+
+    /// Add a child that spans `row_span` rows and `column_span` columns, starting at `row` and `column`.
+    ///
+    /// Cells outside the grid are clipped to its last row and column.
+    pub fn add_spanning_child(
+        &mut self,
+        child: impl Widget<T> + 'static + Clone,
+        row: u8,
+        column: u8,
+        row_span: u8,
+        column_span: u8,
+    ) {
+        let cell = self.clip_cell(row, column, row_span, column_span);
+        let child = GridChild {
+            widget: WidgetPod::new(BoxedWidget::new(child)),
+            cell,
+        };
+        if self.children.push(child).is_err() {
+            log::warn!("Grid has too many children");
+        }
+    }
+
+    /// Keep the cells within the rows and columns of the grid.
+    fn clip_cell(&self, row: u8, column: u8, row_span: u8, column_span: u8) -> Cell {
+        if row >= self.rows || column >= self.columns {
+            log::warn!("Grid child is outside the grid");
+        }
+        let row = row.min(self.rows - 1);
+        let column = column.min(self.columns - 1);
+        Cell {
+            row,
+            column,
+            row_span: row_span.max(1).min(self.rows - row),
+            column_span: column_span.max(1).min(self.columns - column),
+        }
+    }
+
+    /// The rect covered by `cell`, when each cell has the size `cell_size`.
+    fn cell_rect(&self, cell: Cell, cell_size: Size) -> Rect {
+        let (x0, width) = track(cell.column, cell.column_span, cell_size.width, self.column_gap);
+        let (y0, height) = track(cell.row, cell.row_span, cell_size.height, self.row_gap);
+        Rect::from_origin_size(Point::new(x0, y0), Size::new(width, height))
+    }
+
+    /// The size of the whole grid, when each cell has the size `cell_size`.
+    fn grid_size(&self, cell_size: Size) -> Size {
+        let (_, width) = track(0, self.columns, cell_size.width, self.column_gap);
+        let (_, height) = track(0, self.rows, cell_size.height, self.row_gap);
+        Size::new(width, height)
+    }
+}
+
+/// The extent of each of `count` cells that share `total`, with `gap` between them.
+fn shared_extent(total: ScreenCoord, count: u8, gap: ScreenCoord) -> ScreenCoord {
+    let gaps = gap as usize * (count as usize - 1);
+    (total as usize).saturating_sub(gaps) as ScreenCoord / count
+}
+
+/// The smallest extent of each of `count` cells that cover `total`, with `gap` between them.
+fn cell_extent(total: ScreenCoord, count: u8, gap: ScreenCoord) -> ScreenCoord {
+    let gaps = gap as usize * (count as usize - 1);
+    let count = count as usize;
+    // Rounded up, but never more than `total`, so it stays a `ScreenCoord`
+    (total as usize).saturating_sub(gaps).div_ceil(count) as ScreenCoord
+}
+
+/// The offset and length of `span` cells of `extent`, starting from cell `start`.
+fn track(start: u8, span: u8, extent: ScreenCoord, gap: ScreenCoord) -> (ScreenCoord, ScreenCoord) {
+    let pitch = extent as usize + gap as usize;
+    let offset = start as usize * pitch;
+    let length = span as usize * pitch - gap as usize;
+    // A `ScreenCoord` can't go past the edge of the screen, so the track stops there
+    let offset = offset.min(ScreenCoord::MAX as usize);
+    let length = length.min(ScreenCoord::MAX as usize - offset);
+    (offset as ScreenCoord, length as ScreenCoord)
+}
+
+impl<T: Data> Widget<T> for Grid<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Grid");
+        //// TODO: Lay out with LVGL 8
+        /*
+        lv_obj_set_grid_dsc_array(grid, col_dsc, row_dsc);              //  LV_GRID_FR(1) for each column and row
+        lv_obj_set_style_pad_row(grid, row_gap, 0);
+        lv_obj_set_style_pad_column(grid, column_gap, 0);
+        lv_obj_set_grid_cell(child, LV_GRID_ALIGN_CENTER, column, column_span, LV_GRID_ALIGN_CENTER, row, row_span);
+        */
+
+        // Unbounded cells are as big as the biggest child needs
+        let mut cell_size = Size::ZERO;
+        if !bc.is_width_bounded() || !bc.is_height_bounded() {
+            for child in &mut self.children {
+                let size = child.widget.layout(ctx, &bc.loosen(), data, env);
+                let cell = child.cell;
+                cell_size.width = cell_size.width.max(cell_extent(size.width, cell.column_span, self.column_gap));
+                cell_size.height = cell_size.height.max(cell_extent(size.height, cell.row_span, self.row_gap));
+            }
+        }
+        if bc.is_width_bounded() {
+            cell_size.width = shared_extent(bc.max().width, self.columns, self.column_gap);
+        }
+        if bc.is_height_bounded() {
+            cell_size.height = shared_extent(bc.max().height, self.rows, self.row_gap);
+        }
+
+        for index in 0..self.children.len() {
+            let rect = self.cell_rect(self.children[index].cell, cell_size);
+            let child = &mut self.children[index].widget;
+            let child_bc = BoxConstraints::new(Size::ZERO, rect.size());
+            let size = child.layout(ctx, &child_bc, data, env);
+            let extra = Rect::new(0, 0, rect.width().saturating_sub(size.width), rect.height().saturating_sub(size.height));
+            let origin = UnitPoint::CENTER.resolve(extra) + rect.origin().to_vec2();
+            child.set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, size));
+        }
+        bc.constrain(self.grid_size(cell_size))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.paint(ctx, data, env);
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::Grid(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cells_share_the_space() {
+        assert_eq!(shared_extent(240, 3, 0), 80);
+        assert_eq!(shared_extent(240, 4, 8), 54);
+        assert_eq!(shared_extent(10, 4, 8), 0);
+        assert_eq!(shared_extent(100, 3, 0), 33);
+
+        let grid = Grid::<u32>::new(4, 4).with_gap(8);
+        let cell_size = Size::new(54, 54);
+        assert_eq!(grid.cell_rect(grid.clip_cell(0, 0, 1, 1), cell_size), Rect::new(0, 0, 54, 54));
+        assert_eq!(grid.cell_rect(grid.clip_cell(1, 2, 1, 1), cell_size), Rect::new(124, 62, 178, 116));
+        assert_eq!(grid.cell_rect(grid.clip_cell(3, 0, 1, 2), cell_size), Rect::new(0, 186, 116, 240));
+        assert_eq!(grid.grid_size(cell_size), Size::new(240, 240));
+        assert_eq!(grid.cell_rect(grid.clip_cell(3, 3, 1, 1), Size::new(80, 80)), Rect::new(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn cells_cover_the_child() {
+        assert_eq!(cell_extent(80, 1, 0), 80);
+        assert_eq!(cell_extent(100, 3, 0), 34);
+        assert_eq!(cell_extent(116, 2, 8), 54);
+        assert_eq!(cell_extent(255, 1, 0), 255);
+        assert_eq!(cell_extent(255, 2, 0), 128);
+        assert_eq!(cell_extent(10, 4, 8), 0);
+    }
+
+    #[test]
+    fn spans_stay_inside_the_grid() {
+        let grid = Grid::<u32>::new(3, 3);
+        assert_eq!(grid.clip_cell(1, 1, 5, 0), Cell { row: 1, column: 1, row_span: 2, column_span: 1 });
+        assert_eq!(grid.clip_cell(7, 2, 1, 2), Cell { row: 2, column: 2, row_span: 1, column_span: 1 });
+    }
+}
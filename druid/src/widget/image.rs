@@ -0,0 +1,539 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An Image widget.
+//! Please consider using SVG and the SVG wideget as it scales much better.
+//!
+//! Images are stored in flash in the same layout as LVGL's `lv_img_dsc_t`,
+//! so they may be passed to `lv_img_set_src` without conversion.
+
+use core::fmt; ////
+////use std::fmt;
+////#[cfg(feature = "image")]
+////use std::{convert::AsRef, error::Error, path::Path};
+
+use crate::{
+    ////piet::{Image as PietImage, ImageFormat, InterpolationMode},
+    widget::common::FillStrat,
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Rect,
+    Size, UpdateCtx, Widget, ////
+    ////RenderContext, Size, UpdateCtx, Widget,
+};
+use crate::{Color, ScreenCoord, WidgetId, WidgetType}; ////
+
+/// A widget that renders a bitmap Image.
+///
+/// Contains data about how to fill the given space.
+/// Configuration options are provided via the builder pattern.
+///
+/// Note: when [scaling a bitmap image], such as supporting multiple
+/// screen sizes and resolutions, the nearest pixel is used so scaled
+/// images look pixelated. Prefer images at the size they are displayed.
+///
+/// (See also:
+/// [`ImageData`],
+/// [`FillStrat`]
+/// )
+///
+/// # Example
+///
+/// Create an image widget and configure it using builder methods
+/// ```
+/// use druid::widget::{Image, ImageData, FillStrat};
+///
+/// let image_data = ImageData::empty();
+/// let image_widget = Image::new(image_data)
+///     // set the fill strategy
+///     .fill_mode(FillStrat::Fill);
+/// ```
+/// Create an image widget and configure it using setters
+/// ```
+/// use druid::widget::{Image, ImageData, FillStrat};
+///
+/// let image_data = ImageData::empty();
+/// let mut image_widget = Image::new(image_data);
+/// // set the fill strategy
+/// image_widget.set_fill_mode(FillStrat::FitWidth);
+/// ```
+///
+/// [scaling a bitmap image]: ../struct.Scale.html#pixels-and-display-points
+/// [`ImageData`]: struct.ImageData.html
+/// [`FillStrat`]: ../widget/enum.FillStrat.html
+#[derive(Clone)] ////
+pub struct Image {
+    id: WidgetId, ////
+    image_data: ImageData,
+    ////paint_data: Option<PietImage>,
+    fill: FillStrat,
+    ////interpolation: InterpolationMode,
+}
+
+impl Image {
+    /// Create an image drawing widget from `ImageData`.
+    ///
+    /// By default, the Image will scale to fit its box constraints
+    /// ([`FillStrat::Contain`])
+    ///
+    /// [`FillStrat::Contain`]: ../widget/enum.FillStrat.html#variant.Contain
+    pub fn new(image_data: ImageData) -> Self {
+        Image {
+            id: WidgetId::next(), ////
+            image_data,
+            ////paint_data: None,
+            fill: FillStrat::default(),
+            ////interpolation: InterpolationMode::Bilinear,
+        }
+    }
+
+    /// A builder-style method for specifying the fill strategy.
+    pub fn fill_mode(mut self, mode: FillStrat) -> Self {
+        self.fill = mode;
+        self
+    }
+
+    /// Modify the widget's fill strategy.
+    pub fn set_fill_mode(&mut self, newfil: FillStrat) {
+        self.fill = newfil;
+        ////self.paint_data = None;
+    }
+
+    /* ////
+    /// A builder-style method for specifying the interpolation strategy.
+    pub fn interpolation_mode(mut self, interpolation: InterpolationMode) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Modify the widget's interpolation mode.
+    pub fn set_interpolation_mode(&mut self, interpolation: InterpolationMode) {
+        self.interpolation = interpolation;
+        self.paint_data = None;
+    }
+    */ ////
+}
+
+impl<T: Data> Widget<T> for Image {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
+
+    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {}
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T,
+        _env: &Env,
+    ) -> Size {
+        bc.debug_check("Image");
+
+        if bc.is_width_bounded() {
+            bc.max()
+        } else {
+            bc.constrain(self.image_data.size()) ////
+            ////bc.constrain(self.image_data.get_size())
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, _env: &Env) {
+        let offset_matrix = self
+            .fill
+            .affine_to_fill(ctx.size(), self.image_data.size()); ////
+            ////.affine_to_fill(ctx.size(), self.image_data.get_size());
+
+        //// TODO: Render with LVGL
+        /*
+        let img = lv_img_create(lv_scr_act(), NULL);                     //  Add an image to the current screen
+        lv_img_set_src(img, &img_dsc);                                   //  img_dsc.header is image_data.header(), img_dsc.data is image_data.data()
+        lv_img_set_zoom(img, scale * 256);                               //  256 means no zoom
+        lv_obj_set_pos(img, origin_x, origin_y);
+        */
+
+        // The ImageData's to_piet function does not clip to the image's size
+        // CairoRenderContext is very like druids but with some extra goodies like clip
+        if self.fill != FillStrat::Contain {
+            let clip_rect = Rect::ZERO.with_size(ctx.size());
+            ctx.clip(clip_rect);
+        }
+
+        ctx.with_save(|ctx| {
+            ctx.draw_image(&self.image_data, offset_matrix); ////
+            /* ////
+            let piet_image = {
+                let image_data = &self.image_data;
+                self.paint_data
+                    .get_or_insert_with(|| image_data.to_piet(ctx))
+            };
+            ctx.transform(offset_matrix);
+            ctx.draw_image(
+                piet_image,
+                self.image_data.get_size().to_rect(),
+                self.interpolation,
+            );
+            */ ////
+        });
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Image(self)
+    }
+}
+
+/// Pixel format of [`ImageData`], numbered like LVGL's `lv_img_cf_t`.
+///
+/// True colour pixels are RGB565, stored little-endian as for `LV_COLOR_DEPTH 16`.
+/// Indexed images start with a palette of `2^bpp` colours, each stored as
+/// `B, G, R, A` bytes, followed by rows of indices packed from the most
+/// significant bit, with every row starting on a new byte.
+///
+/// [`ImageData`]: struct.ImageData.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum ColorFormat {
+    /// `LV_IMG_CF_TRUE_COLOR`: 2 bytes per pixel.
+    TrueColor = 4,
+    /// `LV_IMG_CF_TRUE_COLOR_ALPHA`: 2 bytes per pixel followed by 1 byte of alpha.
+    TrueColorAlpha = 5,
+    /// `LV_IMG_CF_TRUE_COLOR_CHROMA_KEYED`: as `TrueColor`, but pure green is transparent.
+    TrueColorChromaKeyed = 6,
+    /// `LV_IMG_CF_INDEXED_1BIT`: 2 colour palette.
+    Indexed1Bit = 7,
+    /// `LV_IMG_CF_INDEXED_2BIT`: 4 colour palette.
+    Indexed2Bit = 8,
+    /// `LV_IMG_CF_INDEXED_4BIT`: 16 colour palette.
+    Indexed4Bit = 9,
+    /// `LV_IMG_CF_INDEXED_8BIT`: 256 colour palette.
+    Indexed8Bit = 10,
+}
+
+impl ColorFormat {
+    /// Number of bits for each pixel, not counting the palette.
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            ColorFormat::TrueColor | ColorFormat::TrueColorChromaKeyed => 16,
+            ColorFormat::TrueColorAlpha => 24,
+            ColorFormat::Indexed1Bit => 1,
+            ColorFormat::Indexed2Bit => 2,
+            ColorFormat::Indexed4Bit => 4,
+            ColorFormat::Indexed8Bit => 8,
+        }
+    }
+
+    /// Size of the palette in bytes, 0 for true colour formats.
+    pub fn palette_size(self) -> usize {
+        match self {
+            ColorFormat::TrueColor | ColorFormat::TrueColorAlpha | ColorFormat::TrueColorChromaKeyed => 0,
+            _ => 4 << self.bits_per_pixel(),
+        }
+    }
+}
+
+/// Image data in flash, in the layout of LVGL's `lv_img_dsc_t`.
+///
+/// Use the `druid-app` build script to convert PNG files into `ImageData`
+/// statics, or write the bytes by hand for small icons.
+///
+/// ```
+/// use druid::widget::{ColorFormat, ImageData};
+///
+/// /// A 2x2 checkerboard of white and black
+/// static CHECKER: ImageData = ImageData::new(ColorFormat::Indexed1Bit, 2, 2, &[
+///     0x00, 0x00, 0x00, 0xff,  0xff, 0xff, 0xff, 0xff,  //  Palette: black, white
+///     0b10_000000,  0b01_000000,                        //  Rows: white black, black white
+/// ]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ImageData {
+    format: ColorFormat, ////
+    width: ScreenCoord, ////
+    height: ScreenCoord, ////
+    data: &'static [u8], ////
+    ////pixels: Vec<u8>,
+    ////x_pixels: u32,
+    ////y_pixels: u32,
+    ////format: ImageFormat,
+}
+
+impl ImageData {
+    /// Create an image of `width` x `height` pixels from `data` in `format`.
+    pub const fn new(format: ColorFormat, width: ScreenCoord, height: ScreenCoord, data: &'static [u8]) -> Self { ////
+        ImageData { format, width, height, data } ////
+    } ////
+
+    /// Create an empty Image
+    pub const fn empty() -> Self { ////
+    ////pub fn empty() -> Self {
+        ImageData {
+            format: ColorFormat::TrueColor, ////
+            width: 0, ////
+            height: 0, ////
+            data: &[], ////
+            ////pixels: [].to_vec(),
+            ////x_pixels: 0,
+            ////y_pixels: 0,
+            ////format: ImageFormat::RgbaSeparate,
+        }
+    }
+
+    /// Get the size in pixels of the contained image.
+    pub fn size(&self) -> Size { ////
+        Size::new(self.width, self.height) ////
+    ////fn get_size(&self) -> Size {
+        ////Size::new(self.x_pixels as f64, self.y_pixels as f64)
+    }
+
+    /// The pixel format.
+    pub fn format(&self) -> ColorFormat { ////
+        self.format ////
+    } ////
+
+    /// The image bytes, for `lv_img_dsc_t.data`.
+    pub fn data(&self) -> &'static [u8] { ////
+        self.data ////
+    } ////
+
+    /// The packed `lv_img_header_t`: 5 bits of format, 3 zero bits,
+    /// 2 reserved bits, 11 bits of width and 11 bits of height.
+    pub fn header(&self) -> u32 { ////
+        self.format as u32 | (self.width as u32) << 10 | (self.height as u32) << 21 ////
+    } ////
+
+    /// The colour of the pixel at `(x, y)`, or `None` if it is transparent or outside the image.
+    pub fn pixel(&self, x: ScreenCoord, y: ScreenCoord) -> Option<Color> { ////
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let (x, y, width) = (x as usize, y as usize, self.width as usize);
+        let bpp = self.format.bits_per_pixel();
+        match self.format {
+            ColorFormat::TrueColor | ColorFormat::TrueColorChromaKeyed | ColorFormat::TrueColorAlpha => {
+                let start = (y * width + x) * bpp / 8;
+                let pixel = self.data.get(start..start + bpp / 8)?;
+                let rgb565 = u16::from_le_bytes([pixel[0], pixel[1]]);
+                match self.format {
+                    ColorFormat::TrueColorChromaKeyed if rgb565 == CHROMA_KEY => None,
+                    ColorFormat::TrueColorAlpha if pixel[2] < OPAQUE => None,
+                    _ => Some(from_rgb565(rgb565)),
+                }
+            }
+            _ => {
+                let stride = (width * bpp + 7) / 8;
+                let byte = self.data.get(self.format.palette_size() + y * stride + x * bpp / 8)?;
+                let shift = 8 - bpp - x * bpp % 8;
+                let index = ((*byte as usize) >> shift) & ((1 << bpp) - 1);
+                let entry = self.data.get(index * 4..index * 4 + 4)?;
+                if entry[3] < OPAQUE {
+                    return None;
+                }
+                Some(Color::rgb8(entry[2], entry[1], entry[0]))
+            }
+        }
+    } ////
+
+    /* ////
+    /// Convert ImageData into Piet draw instructions.
+    fn to_piet(&self, ctx: &mut PaintCtx) -> PietImage {
+        ctx.make_image(
+            self.get_size().width as usize,
+            self.get_size().height as usize,
+            &self.pixels,
+            self.format,
+        )
+        .unwrap()
+    }
+    */ ////
+}
+
+/// `LV_COLOR_TRANSP`, pure green, in RGB565.
+const CHROMA_KEY: u16 = 0x07e0;
+/// Pixels with less alpha than this are not drawn, as the renderer doesn't blend.
+const OPAQUE: u8 = 0x80;
+
+/// Expand an RGB565 pixel to a `Color`.
+fn from_rgb565(rgb565: u16) -> Color {
+    let r = (rgb565 >> 11) as u8 & 0x1f;
+    let g = (rgb565 >> 5) as u8 & 0x3f;
+    let b = rgb565 as u8 & 0x1f;
+    Color::rgb8(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2)
+}
+
+/* ////
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+impl ImageData {
+    /// Create an empty Image
+    pub fn empty() -> Self {
+        ImageData {
+            pixels: [].to_vec(),
+            x_pixels: 0,
+            y_pixels: 0,
+            format: ImageFormat::RgbaSeparate,
+        }
+    }
+
+    /// Get the size in pixels of the contained image.
+    fn get_size(&self) -> Size {
+        Size::new(self.x_pixels as f64, self.y_pixels as f64)
+    }
+
+    /// Convert ImageData into Piet draw instructions.
+    fn to_piet(&self, ctx: &mut PaintCtx) -> PietImage {
+        ctx.make_image(
+            self.get_size().width as usize,
+            self.get_size().height as usize,
+            &self.pixels,
+            self.format,
+        )
+        .unwrap()
+    }
+}
+
+#[cfg(feature = "image")]
+#[cfg_attr(docsrs, doc(cfg(feature = "image")))]
+impl ImageData {
+    /// Load an image from a DynamicImage from the image crate
+    pub fn from_dynamic_image(image_data: image::DynamicImage) -> ImageData {
+        use image::ColorType::*;
+        let has_alpha_channel = match image_data.color() {
+            La8 | Rgba8 | La16 | Rgba16 | Bgra8 => true,
+            _ => false,
+        };
+
+        if has_alpha_channel {
+            Self::from_dynamic_image_with_alpha(image_data)
+        } else {
+            Self::from_dynamic_image_without_alpha(image_data)
+        }
+    }
+
+    /// Load an image from a DynamicImage with alpha
+    pub fn from_dynamic_image_with_alpha(image_data: image::DynamicImage) -> ImageData {
+        let rgba_image = image_data.to_rgba();
+        let sizeofimage = rgba_image.dimensions();
+        ImageData {
+            pixels: rgba_image.to_vec(),
+            x_pixels: sizeofimage.0,
+            y_pixels: sizeofimage.1,
+            format: ImageFormat::RgbaSeparate,
+        }
+    }
+
+    /// Load an image from a DynamicImage without alpha
+    pub fn from_dynamic_image_without_alpha(image_data: image::DynamicImage) -> ImageData {
+        let rgb_image = image_data.to_rgb();
+        let sizeofimage = rgb_image.dimensions();
+        ImageData {
+            pixels: rgb_image.to_vec(),
+            x_pixels: sizeofimage.0,
+            y_pixels: sizeofimage.1,
+            format: ImageFormat::Rgb,
+        }
+    }
+
+    /// Attempt to load an image from raw bytes.
+    ///
+    /// If the image crate can't decode an image from the data an error will be returned.
+    pub fn from_data(raw_image: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image_data = image::load_from_memory(raw_image).map_err(|e| e)?;
+        Ok(ImageData::from_dynamic_image(image_data))
+    }
+
+    /// Attempt to load an image from the file at the provided path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let image_data = image::open(path).map_err(|e| e)?;
+        Ok(ImageData::from_dynamic_image(image_data))
+    }
+}
+*/ ////
+
+impl Default for ImageData {
+    fn default() -> Self {
+        ImageData::empty()
+    }
+}
+
+impl fmt::Debug for ImageData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ImageData")
+            .field("size", &self.data.len()) ////
+            .field("width", &self.width) ////
+            .field("height", &self.height) ////
+            ////.field("size", &self.pixels.len())
+            ////.field("width", &self.x_pixels)
+            ////.field("height", &self.y_pixels)
+            .field("format", &format_args!("{:?}", self.format))
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::piet::Piet;
+
+    const SIZE: usize = 8;
+    /// Pixels painted by `draw`, as 0 or the color's red channel.
+    static mut PIXELS: [[u8; SIZE]; SIZE] = [[0; SIZE]; SIZE];
+
+    fn draw(rect: Rect, color: Color) {
+        for y in rect.y0..rect.y1 {
+            for x in rect.x0..rect.x1 {
+                unsafe { PIXELS[y as usize][x as usize] = color.as_rgb8().0; }
+            }
+        }
+    }
+
+    fn row(y: usize) -> [u8; SIZE] {
+        unsafe { PIXELS[y] }
+    }
+
+    /// White and black in RGB565, then black and white
+    static TRUE_COLOR: ImageData = ImageData::new(ColorFormat::TrueColor, 2, 2, &[
+        0xff, 0xff, 0x00, 0x00,
+        0x00, 0x00, 0xff, 0xff,
+    ]);
+
+    #[test]
+    fn decode_pixels() {
+        assert_eq!(TRUE_COLOR.pixel(0, 0), Some(Color::rgb8(0xff, 0xff, 0xff)));
+        assert_eq!(TRUE_COLOR.pixel(0, 1), Some(Color::rgb8(0, 0, 0)));
+        assert_eq!(TRUE_COLOR.pixel(2, 0), None);
+        assert_eq!(TRUE_COLOR.header(), 4 | 2 << 10 | 2 << 21);
+
+        // 3 pixels of 2 bits: red, transparent, blue
+        let indexed = ImageData::new(ColorFormat::Indexed2Bit, 3, 1, &[
+            0, 0, 0xff, 0xff,  0, 0, 0, 0,  0xff, 0, 0, 0xff,  0, 0, 0, 0,
+            0b00_01_10_00,
+        ]);
+        assert_eq!(indexed.pixel(0, 0), Some(Color::rgb8(0xff, 0, 0)));
+        assert_eq!(indexed.pixel(1, 0), None);
+        assert_eq!(indexed.pixel(2, 0), Some(Color::rgb8(0, 0, 0xff)));
+    }
+
+    #[test]
+    fn tall_paint() {
+        // A square image in a tall widget is scaled to the width and centred.
+        let mut piet = Piet::with_display(Size::new(4, 8), Some(draw));
+        let offset_matrix = FillStrat::Contain.affine_to_fill(Size::new(4, 8), TRUE_COLOR.size());
+        piet.draw_image(&TRUE_COLOR, offset_matrix);
+        assert_eq!(row(1), [0; SIZE]);
+        assert_eq!(row(2)[..4], [0xff, 0xff, 0, 0]);
+        assert_eq!(row(5)[..4], [0, 0, 0xff, 0xff]);
+        assert_eq!(row(6), [0; SIZE]);
+    }
+}
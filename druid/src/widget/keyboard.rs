@@ -0,0 +1,323 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An on-screen keyboard widget.
+
+use crate::text::{EditAction, Editor, Movement};
+use crate::widget::textbox::TextAccess;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, ScreenCoord, Size, String, UpdateCtx, Widget, WidgetId, WidgetType,
+};
+use crate::theme;
+use heapless::ArrayLength;
+
+/// Key that switches between lower and upper case.
+const SHIFT: char = '\u{21E7}';
+/// Key that deletes the character before the caret.
+const BACKSPACE: char = '\u{232B}';
+/// Key that switches between letters and numbers.
+const MODE: char = '\u{21C6}';
+/// Key that moves the caret left.
+const LEFT: char = '\u{2190}';
+/// Key that moves the caret right.
+const RIGHT: char = '\u{2192}';
+/// The space bar, which is `SPACE_WIDTH` keys wide.
+const SPACE: char = ' ';
+const SPACE_WIDTH: u16 = 4;
+/// Number of keys in the widest row.
+const KEYS_PER_ROW: u16 = 10;
+
+const LOWER_ROWS: [&str; 4] = ["qwertyuiop", "asdfghjkl", "\u{21E7}zxcvbnm\u{232B}", "\u{21C6}\u{2190} \u{2192}."];
+const UPPER_ROWS: [&str; 4] = ["QWERTYUIOP", "ASDFGHJKL", "\u{21E7}ZXCVBNM\u{232B}", "\u{21C6}\u{2190} \u{2192}."];
+const NUMBER_ROWS: [&str; 4] = ["1234567890", "-/:;()&@\"", "+=_*,?!'\u{232B}", "\u{21C6}\u{2190} \u{2192}."];
+
+/// The keys shown by a `Keyboard`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyboardMode {
+    /// Lower case letters
+    Lower,
+    /// Upper case letters
+    Upper,
+    /// Numbers and punctuation
+    Number,
+}
+
+/// An on-screen keyboard, like `lv_keyboard`, for entering short strings
+/// such as a Wi-Fi name or a reply.
+///
+/// The keyboard edits a `heapless::String` at its own caret, which starts
+/// at the end of the text. To place the caret by touch, use
+/// `TextBox::with_keyboard` instead, which shows the keyboard below the text.
+#[derive(Clone)]
+pub struct Keyboard<T> {
+    id: WidgetId,
+    mode: KeyboardMode,
+    /// The key being touched, if any.
+    pressed: Option<char>,
+    editor: Editor,
+    access: TextAccess<T>,
+}
+
+impl<N: ArrayLength<u8> + 'static> Keyboard<heapless::String<N>> {
+    /// Create a new keyboard, showing lower case letters.
+    pub fn new() -> Self {
+        Keyboard::hosted(WidgetId::next(), TextAccess::string())
+    }
+}
+
+impl<T> Keyboard<T> {
+    /// Create a keyboard that is shown and edited by another widget, with that widget's id.
+    pub(crate) fn hosted(id: WidgetId, access: TextAccess<T>) -> Self {
+        Keyboard {
+            id,
+            mode: KeyboardMode::Lower,
+            pressed: None,
+            editor: Editor::new(),
+            access,
+        }
+    }
+
+    /// Builder-style method to set the keys shown at first.
+    pub fn with_mode(mut self, mode: KeyboardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The keys currently shown.
+    pub fn mode(&self) -> KeyboardMode {
+        self.mode
+    }
+
+    /// Height of the keyboard, with four rows of keys.
+    pub(crate) fn height(env: &Env) -> ScreenCoord {
+        theme::BASIC_WIDGET_HEIGHT.resolve(env).saturating_mul(4)
+    }
+
+    /// Returns true if a key is being touched.
+    pub(crate) fn is_pressed(&self) -> bool {
+        self.pressed.is_some()
+    }
+
+    fn rows(&self) -> &'static [&'static str; 4] {
+        match self.mode {
+            KeyboardMode::Lower => &LOWER_ROWS,
+            KeyboardMode::Upper => &UPPER_ROWS,
+            KeyboardMode::Number => &NUMBER_ROWS,
+        }
+    }
+
+    /// Call `f` with each key and its rect within `area`. Shorter rows are centred.
+    fn for_each_key(&self, area: Rect, mut f: impl FnMut(char, Rect)) {
+        let rows = self.rows();
+        let key_width = area.width() as u16 / KEYS_PER_ROW;
+        let key_height = area.height() as u16 / rows.len() as u16;
+        for (row, keys) in rows.iter().enumerate() {
+            let units: u16 = keys.chars().map(|key| if key == SPACE { SPACE_WIDTH } else { 1 }).sum();
+            let mut x = area.x0 as u16 + key_width * KEYS_PER_ROW.saturating_sub(units) / 2;
+            let y = area.y0 as u16 + key_height * row as u16;
+            for key in keys.chars() {
+                let width = if key == SPACE { key_width * SPACE_WIDTH } else { key_width };
+                f(key, Rect::new(x as ScreenCoord, y as ScreenCoord, (x + width) as ScreenCoord, (y + key_height) as ScreenCoord));
+                x += width;
+            }
+        }
+    }
+
+    /// Returns the key at `pos` within `area`, if any.
+    fn key_at(&self, area: Rect, pos: Point) -> Option<char> {
+        let mut found = None;
+        self.for_each_key(area, |key, rect| {
+            if rect.winding(pos) != 0 {
+                found = Some(key);
+            }
+        });
+        found
+    }
+
+    /// Returns the edit for `key`, after switching the keys shown if it's a mode key.
+    pub(crate) fn key_action(&mut self, key: char) -> Option<EditAction> {
+        match key {
+            SHIFT => {
+                self.mode = match self.mode {
+                    KeyboardMode::Lower => KeyboardMode::Upper,
+                    _ => KeyboardMode::Lower,
+                };
+                None
+            }
+            MODE => {
+                self.mode = match self.mode {
+                    KeyboardMode::Number => KeyboardMode::Lower,
+                    _ => KeyboardMode::Number,
+                };
+                None
+            }
+            BACKSPACE => Some(EditAction::Backspace),
+            LEFT => Some(EditAction::Move(Movement::Left)),
+            RIGHT => Some(EditAction::Move(Movement::Right)),
+            key => {
+                let mut text = String::new();
+                text.push(key).ok()?;
+                Some(EditAction::Insert(text))
+            }
+        }
+    }
+
+    /// Handle touches on the keys within `area`. Returns the edit when a key is released.
+    pub(crate) fn key_event(&mut self, ctx: &mut EventCtx, event: &Event, area: Rect) -> Option<EditAction> {
+        match event {
+            Event::MouseDown(mouse) if area.winding(mouse.pos) != 0 => {
+                self.pressed = self.key_at(area, mouse.pos);
+                ctx.set_active(true);
+                ctx.request_paint();
+                None
+            }
+            Event::MouseUp(mouse) if self.pressed.is_some() => {
+                let pressed = self.pressed.take();
+                ctx.request_paint();
+                if pressed == self.key_at(area, mouse.pos) {
+                    pressed.and_then(|key| self.key_action(key))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Paint the keys within `area`.
+    pub(crate) fn paint_keys(&self, ctx: &mut PaintCtx, area: Rect, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let kb = lv_keyboard_create(lv_scr_act(), NULL);                //  Add a keyboard to the current screen
+        lv_keyboard_set_textarea(kb, ta);                               //  Edit the text area of the TextBox
+        lv_keyboard_set_mode(kb, LV_KEYBOARD_MODE_TEXT_LOWER);          //  Or TEXT_UPPER, SPECIAL
+        lv_keyboard_set_cursor_manage(kb, true);                        //  Show the cursor of the text area
+        */
+        let key_color = theme::BACKGROUND_LIGHT.resolve(env);
+        let pressed_color = theme::PRIMARY_DARK.resolve(env);
+        let border_color = theme::BORDER_DARK.resolve(env);
+        let radius = theme::TEXTBOX_BORDER_RADIUS.resolve(env);
+        self.for_each_key(area, |key, rect| {
+            let rect = rect.to_rounded_rect(radius);
+            let color = if self.pressed == Some(key) { &pressed_color } else { &key_color };
+            ctx.fill(rect, color);
+            ctx.stroke(rect, &border_color, 1);
+            //// TODO: Draw the key label
+        });
+    }
+}
+
+impl<N: ArrayLength<u8> + 'static> Default for Keyboard<heapless::String<N>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for Keyboard<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        let mut text = self.access.bind(data);
+        self.editor.constrain_to(&text);
+        if let Event::MouseUp(_) = event {
+            ctx.set_active(false);
+        }
+        let area = ctx.size().to_rect();
+        if let Some(action) = self.key_event(ctx, event, area) {
+            self.editor.do_edit_action(&action, &mut text);
+        }
+    }
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, _env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            // Type at the end of the text
+            let len = (self.access.text)(data).len();
+            self.editor = Editor::with_caret(len);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
+        ctx.request_paint();
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            theme::WIDE_WIDGET_WIDTH.resolve(env)
+        };
+        bc.constrain(Size::new(width, Self::height(env)))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let area = ctx.size().to_rect();
+        self.paint_keys(ctx, area, env);
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::Keyboard(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use heapless::consts::U32;
+
+    fn area() -> Rect {
+        Rect::new(0, 100, 240, 240)
+    }
+
+    #[test]
+    fn keys_are_laid_out_in_rows() {
+        let keyboard = Keyboard::<heapless::String<U32>>::new();
+        // 24 x 35 pixels per key
+        assert_eq!(keyboard.key_at(area(), Point::new(1, 101)), Some('q'));
+        assert_eq!(keyboard.key_at(area(), Point::new(239, 134)), Some('p'));
+        // The second row is indented by half a key
+        assert_eq!(keyboard.key_at(area(), Point::new(5, 140)), None);
+        assert_eq!(keyboard.key_at(area(), Point::new(13, 140)), Some('a'));
+        assert_eq!(keyboard.key_at(area(), Point::new(220, 175)), Some(BACKSPACE));
+        assert_eq!(keyboard.key_at(area(), Point::new(120, 239)), Some(SPACE));
+        assert_eq!(keyboard.key_at(area(), Point::new(120, 99)), None);
+    }
+
+    #[test]
+    fn mode_keys_switch_keys() {
+        let mut keyboard = Keyboard::<heapless::String<U32>>::new();
+        assert_eq!(keyboard.key_action(SHIFT), None);
+        assert_eq!(keyboard.mode(), KeyboardMode::Upper);
+        assert_eq!(keyboard.key_at(area(), Point::new(1, 101)), Some('Q'));
+        assert_eq!(keyboard.key_action(MODE), None);
+        assert_eq!(keyboard.mode(), KeyboardMode::Number);
+        assert_eq!(keyboard.key_at(area(), Point::new(1, 101)), Some('1'));
+        keyboard.key_action(MODE);
+        assert_eq!(keyboard.mode(), KeyboardMode::Lower);
+    }
+
+    #[test]
+    fn keys_edit_at_caret() {
+        let mut keyboard = Keyboard::<heapless::String<U32>>::new();
+        let mut data = heapless::String::<U32>::from("hom");
+        let mut editor = Editor::with_caret(data.len());
+        for key in [LEFT, 'r', RIGHT, 'e', BACKSPACE, SPACE, 'e'].iter() {
+            if let Some(action) = keyboard.key_action(*key) {
+                editor.do_edit_action(&action, &mut data);
+            }
+        }
+        assert_eq!(data, "horm e");
+        assert_eq!(editor.cursor(), 6);
+    }
+}
@@ -0,0 +1,703 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simple list view widget.
+
+////use std::cmp::Ordering;
+////use std::sync::Arc;
+
+////#[cfg(feature = "im")]
+////use crate::im::Vector;
+
+use crate::{Point, Rect, Size}; ////
+////use crate::kurbo::{Point, Rect, Size};
+
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    UpdateCtx, Widget, WidgetPod,
+};
+use crate::{BoxedWidget, ScreenCoord, StaticWidgets, Vec, WidgetId, WidgetType}; ////
+use heapless::ArrayLength; ////
+
+/// A list widget for a variable-size collection of items.
+///
+/// The children are built up front, one for each slot of the collection
+/// (up to the capacity of a `Vec`), and are reused as items come and go.
+/// Slots beyond the current length of the data are kept idle: they are not
+/// laid out, painted or sent events until an item appears at their index.
+///
+/// Each child is a [`BoxedWidget`] of the item type, so the application must
+/// implement [`StaticWidgets`] for `BoxedWidget` of the item type, and the
+/// children count against the [`MAX_WIDGETS`] of its static `Widgets`,
+/// together with the other boxed widgets of that type.
+///
+/// [`BoxedWidget`]: ../struct.BoxedWidget.html
+/// [`StaticWidgets`]: ../trait.StaticWidgets.html
+/// [`MAX_WIDGETS`]: ../constant.MAX_WIDGETS.html
+#[derive(Clone)] ////
+pub struct List<T> {
+    id: WidgetId, ////
+    ////closure: Box<dyn Fn() -> Box<dyn Widget<T>>>,
+    children: Vec<WidgetPod<T, ListItem<T>>>, ////
+    ////children: Vec<WidgetPod<T, Box<dyn Widget<T>>>>,
+    /// Number of children that have received `WidgetAdded`.
+    added: usize, ////
+    /// Returns the number of items in the data.
+    data_len: fn(&T) -> usize, ////
+}
+
+/// One slot of a `List`, forwarding to the child widget for the item at `index`.
+///
+/// The child is stored in the static `Widgets` of the item type, which is
+/// erased here so that `List<T>` only depends on the collection type `T`.
+#[derive(Clone)] ////
+struct ListItem<T> {
+    index: usize,
+    child: WidgetId,
+    vtable: ItemVTable<T>,
+}
+
+/// Functions that slice the item out of the collection and call the child.
+#[derive(Clone)] ////
+struct ItemVTable<T> {
+    event: fn(WidgetId, usize, &mut EventCtx, &Event, &mut T, &Env),
+    lifecycle: fn(WidgetId, usize, &mut LifeCycleCtx, &LifeCycle, &T, &Env),
+    update: fn(WidgetId, usize, &mut UpdateCtx, &T, &T, &Env),
+    layout: fn(WidgetId, usize, &mut LayoutCtx, &BoxConstraints, &T, &Env) -> Size,
+    paint: fn(WidgetId, usize, &mut PaintCtx, &T, &Env),
+}
+
+impl<T: Data> List<T> {
+    /// Create a new list widget. Closure will be called once for each slot
+    /// in the pool of children, when the list is constructed.
+    ///
+    /// The pool has a slot for each item that fits in the collection, up to
+    /// the capacity of a `Vec`. Panics if the static `Widgets` of the item type
+    /// can't hold the pool.
+    pub fn new<C: Data, W: Widget<C> + 'static + Clone>(closure: impl Fn() -> W) -> Self ////
+    where
+        T: ListIter<C>,
+    ////pub fn new<W: Widget<T> + 'static>(closure: impl Fn() -> W + 'static) -> Self {
+    {
+        let mut children = Vec::new(); ////
+        let vtable = ItemVTable::<T>::new::<C>(); ////
+        for index in 0..T::capacity().min(children.capacity()) { ////
+            let child = BoxedWidget::new(closure());
+            let item = ListItem {
+                index,
+                child: child.0,
+                vtable: vtable.clone(),
+            };
+            if children.push(WidgetPod::new(item)).is_err() {
+                break;
+            }
+        }
+        List {
+            id: WidgetId::next(), ////
+            ////closure: Box::new(move || Box::new(closure())),
+            children, ////
+            ////children: Vec::new(),
+            added: 0, ////
+            data_len: data_len::<C, T>, ////
+        }
+    }
+
+    /// The number of children that are currently showing an item.
+    fn active_len(&self, data: &T) -> usize { ////
+        (self.data_len)(data).min(self.children.len())
+    }
+
+    /*  ////
+    /// When the widget is created or the data changes, create or remove children as needed
+    ///
+    /// Returns `true` if children were added or removed.
+    fn update_child_count(&mut self, data: &impl ListIter<T>, _env: &Env) -> bool {
+        let len = self.children.len();
+        match len.cmp(&data.data_len()) {
+            Ordering::Greater => self.children.truncate(data.data_len()),
+            Ordering::Less => data.for_each(|_, i| {
+                if i >= len {
+                    let child = WidgetPod::new((self.closure)());
+                    self.children.push(child);
+                }
+            }),
+            Ordering::Equal => (),
+        }
+        len != data.data_len()
+    }
+    */ ////
+}
+
+/// This iterator enables writing List widget for any `Data`.
+///
+/// The items must be stored contiguously, so that a child widget can be
+/// handed the item at its index.
+pub trait ListIter<T>: Data {
+    /// The maximum number of items, which bounds the pool of child widgets.
+    fn capacity() -> usize; ////
+
+    /// The items as a slice.
+    fn as_slice(&self) -> &[T]; ////
+
+    /// The items as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [T]; ////
+
+    /// Iterate over each data child.
+    fn for_each(&self, mut cb: impl FnMut(&T, usize)) { ////
+    ////fn for_each(&self, cb: impl FnMut(&T, usize));
+        for (i, item) in self.as_slice().iter().enumerate() {
+            cb(item, i);
+        }
+    }
+
+    /// Iterate over each data child. Keep track of changed data and update self.
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut T, usize)) { ////
+    ////fn for_each_mut(&mut self, cb: impl FnMut(&mut T, usize));
+        for (i, item) in self.as_mut_slice().iter_mut().enumerate() {
+            cb(item, i);
+        }
+    }
+
+    /// Return data length.
+    fn data_len(&self) -> usize { ////
+    ////fn data_len(&self) -> usize;
+        self.as_slice().len()
+    }
+}
+
+impl<T: Data, N: ArrayLength<T> + 'static> ListIter<T> for heapless::Vec<T, N> { ////
+    fn capacity() -> usize {
+        N::to_usize()
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Data, const N: usize> ListIter<T> for [T; N] { ////
+    fn capacity() -> usize {
+        N
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/* ////
+#[cfg(feature = "im")]
+impl<T: Data> ListIter<T> for Vector<T> {
+    fn for_each(&self, mut cb: impl FnMut(&T, usize)) {
+        for (i, item) in self.iter().enumerate() {
+            cb(item, i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut T, usize)) {
+        for (i, item) in self.iter_mut().enumerate() {
+            cb(item, i);
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.len()
+    }
+}
+
+// S == shared data type
+#[cfg(feature = "im")]
+impl<S: Data, T: Data> ListIter<(S, T)> for (S, Vector<T>) {
+    fn for_each(&self, mut cb: impl FnMut(&(S, T), usize)) {
+        for (i, item) in self.1.iter().enumerate() {
+            let d = (self.0.to_owned(), item.to_owned());
+            cb(&d, i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut (S, T), usize)) {
+        for (i, item) in self.1.iter_mut().enumerate() {
+            let mut d = (self.0.clone(), item.clone());
+            cb(&mut d, i);
+
+            if !self.0.same(&d.0) {
+                self.0 = d.0;
+            }
+            if !item.same(&d.1) {
+                *item = d.1;
+            }
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.1.len()
+    }
+}
+
+impl<T: Data> ListIter<T> for Arc<Vec<T>> {
+    fn for_each(&self, mut cb: impl FnMut(&T, usize)) {
+        for (i, item) in self.iter().enumerate() {
+            cb(item, i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut T, usize)) {
+        let mut new_data = Vec::with_capacity(self.data_len());
+        let mut any_changed = false;
+
+        for (i, item) in self.iter().enumerate() {
+            let mut d = item.to_owned();
+            cb(&mut d, i);
+
+            if !any_changed && !item.same(&d) {
+                any_changed = true;
+            }
+            new_data.push(d);
+        }
+
+        if any_changed {
+            *self = Arc::new(new_data);
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.len()
+    }
+}
+
+// S == shared data type
+impl<S: Data, T: Data> ListIter<(S, T)> for (S, Arc<Vec<T>>) {
+    fn for_each(&self, mut cb: impl FnMut(&(S, T), usize)) {
+        for (i, item) in self.1.iter().enumerate() {
+            let d = (self.0.clone(), item.to_owned());
+            cb(&d, i);
+        }
+    }
+
+    fn for_each_mut(&mut self, mut cb: impl FnMut(&mut (S, T), usize)) {
+        let mut new_data = Vec::with_capacity(self.1.len());
+        let mut any_shared_changed = false;
+        let mut any_el_changed = false;
+
+        for (i, item) in self.1.iter().enumerate() {
+            let mut d = (self.0.clone(), item.to_owned());
+            cb(&mut d, i);
+
+            if !any_shared_changed && !self.0.same(&d.0) {
+                any_shared_changed = true;
+            }
+            if any_shared_changed {
+                self.0 = d.0;
+            }
+            if !any_el_changed && !item.same(&d.1) {
+                any_el_changed = true;
+            }
+            new_data.push(d.1);
+        }
+
+        if any_el_changed {
+            self.1 = Arc::new(new_data);
+        }
+    }
+
+    fn data_len(&self) -> usize {
+        self.1.len()
+    }
+}
+*/ ////
+
+fn data_len<C: Data, T: ListIter<C>>(data: &T) -> usize { ////
+    data.data_len()
+}
+
+impl<T: Data> ItemVTable<T> { ////
+    fn new<C: Data>() -> Self
+    where
+        T: ListIter<C>,
+    {
+        ItemVTable {
+            event: item_event::<C, T>,
+            lifecycle: item_lifecycle::<C, T>,
+            update: item_update::<C, T>,
+            layout: item_layout::<C, T>,
+            paint: item_paint::<C, T>,
+        }
+    }
+}
+
+fn item_event<C: Data, T: ListIter<C>>(
+    child: WidgetId,
+    index: usize,
+    ctx: &mut EventCtx,
+    event: &Event,
+    data: &mut T,
+    env: &Env,
+) {
+    if let Some(item) = data.as_mut_slice().get_mut(index) {
+        BoxedWidget::<C>::new_by_id(child).event(ctx, event, item, env);
+    }
+}
+
+fn item_lifecycle<C: Data, T: ListIter<C>>(
+    child: WidgetId,
+    index: usize,
+    ctx: &mut LifeCycleCtx,
+    event: &LifeCycle,
+    data: &T,
+    env: &Env,
+) {
+    match (data.as_slice().get(index), event) {
+        (Some(item), _) => BoxedWidget::<C>::new_by_id(child).lifecycle(ctx, event, item, env),
+        // without an item the child can't be told, but its slot is freed all the same
+        (None, LifeCycle::WidgetRemoved) => BoxedWidget::<C>::new_by_id(child).remove_widget(),
+        (None, _) => (),
+    }
+}
+
+fn item_update<C: Data, T: ListIter<C>>(
+    child: WidgetId,
+    index: usize,
+    ctx: &mut UpdateCtx,
+    old_data: &T,
+    data: &T,
+    env: &Env,
+) {
+    match (old_data.as_slice().get(index), data.as_slice().get(index)) {
+        (Some(old_item), Some(item)) if !old_item.same(item) => {
+            BoxedWidget::<C>::new_by_id(child).update(ctx, old_item, item, env)
+        }
+        _ => (),
+    }
+}
+
+fn item_layout<C: Data, T: ListIter<C>>(
+    child: WidgetId,
+    index: usize,
+    ctx: &mut LayoutCtx,
+    bc: &BoxConstraints,
+    data: &T,
+    env: &Env,
+) -> Size {
+    match data.as_slice().get(index) {
+        Some(item) => BoxedWidget::<C>::new_by_id(child).layout(ctx, bc, item, env),
+        None => Size::ZERO,
+    }
+}
+
+fn item_paint<C: Data, T: ListIter<C>>(
+    child: WidgetId,
+    index: usize,
+    ctx: &mut PaintCtx,
+    data: &T,
+    env: &Env,
+) {
+    if let Some(item) = data.as_slice().get(index) {
+        BoxedWidget::<C>::new_by_id(child).paint(ctx, item, env);
+    }
+}
+
+impl<T: Data> Widget<T> for ListItem<T> { ////
+    fn id(&self) -> Option<WidgetId> {
+        Some(self.child)
+    }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        (self.vtable.event)(self.child, self.index, ctx, event, data, env)
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        (self.vtable.lifecycle)(self.child, self.index, ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        (self.vtable.update)(self.child, self.index, ctx, old_data, data, env)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        (self.vtable.layout)(self.child, self.index, ctx, bc, data, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        (self.vtable.paint)(self.child, self.index, ctx, data, env)
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::None
+    }
+}
+
+impl<T: Data> Widget<T> for List<T> { ////
+////impl<C: Data, T: ListIter<C>> Widget<T> for List<C> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let len = self.active_len(data).min(self.added); ////
+        for child in self.children.iter_mut().take(len) { ////
+            child.event(ctx, event, data, env);
+        }
+        ////let mut children = self.children.iter_mut();
+        ////data.for_each_mut(|child_data, _| {
+            ////if let Some(child) = children.next() {
+                ////child.event(ctx, event, child_data, env);
+            ////}
+        ////});
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        ////if let LifeCycle::WidgetAdded = event {
+            ////if self.update_child_count(data, env) {
+                ////ctx.children_changed();
+            ////}
+        ////}
+
+        let len = match event { ////
+            LifeCycle::WidgetAdded => {
+                self.added = self.active_len(data);
+                self.added
+            }
+            // idle children are removed too
+            LifeCycle::WidgetRemoved => self.children.len(),
+            _ => self.active_len(data).min(self.added),
+        };
+        for child in self.children.iter_mut().take(len) { ////
+            child.lifecycle(ctx, event, data, env);
+        }
+        ////let mut children = self.children.iter_mut();
+        ////data.for_each(|child_data, _| {
+            ////if let Some(child) = children.next() {
+                ////child.lifecycle(ctx, event, child_data, env);
+            ////}
+        ////});
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        // we send update to children first, before adding or removing children;
+        // this way we avoid sending update to newly added children, at the cost
+        // of potentially updating children that are going to be removed.
+        let len = self.active_len(data); ////
+        for child in self.children.iter_mut().take(len.min(self.added)) { ////
+            child.update(ctx, data, env);
+        }
+        ////let mut children = self.children.iter_mut();
+        ////data.for_each(|child_data, _| {
+            ////if let Some(child) = children.next() {
+                ////child.update(ctx, child_data, env);
+            ////}
+        ////});
+
+        // idle children from the pool are added the first time an item appears
+        // at their index, by the `RouteWidgetAdded` that follows
+        if len > self.added { ////
+            self.added = len;
+            ctx.children_changed();
+        }
+        if len != self.active_len(old_data) { ////
+            ctx.request_layout();
+        }
+        ////if self.update_child_count(data, env) {
+            ////ctx.children_changed();
+        ////}
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("List"); ////
+        let mut width = bc.min().width;
+        let mut y: ScreenCoord = 0; ////
+        ////let mut y = 0.0;
+
+        let mut paint_rect = Rect::ZERO;
+        let len = self.active_len(data).min(self.added); ////
+        for child in self.children.iter_mut().take(len) { ////
+        ////let mut children = self.children.iter_mut();
+        ////data.for_each(|child_data, _| {
+            ////let child = match children.next() {
+                ////Some(child) => child,
+                ////None => {
+                    ////return;
+                ////}
+            ////};
+            let child_bc = BoxConstraints::new(
+                Size::new(bc.min().width, 0), ////
+                ////Size::new(bc.min().width, 0.0),
+                Size::new(bc.max().width, ScreenCoord::MAX), ////
+                ////Size::new(bc.max().width, std::f64::INFINITY),
+            );
+            let child_size = child.layout(ctx, &child_bc, data, env); ////
+            ////let child_size = child.layout(ctx, &child_bc, child_data, env);
+            let rect = Rect::from_origin_size(Point::new(0, y), child_size); ////
+            ////let rect = Rect::from_origin_size(Point::new(0.0, y), child_size);
+            child.set_layout_rect(ctx, data, env, rect); ////
+            ////child.set_layout_rect(ctx, child_data, env, rect);
+            paint_rect = paint_rect.union(child.paint_rect());
+            width = width.max(child_size.width);
+            y = y.saturating_add(child_size.height); ////
+            ////y += child_size.height;
+        } ////
+        ////});
+
+        let my_size = bc.constrain(Size::new(width, y));
+        let insets = paint_rect - Rect::ZERO.with_size(my_size);
+        ctx.set_paint_insets(insets);
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let list = lv_list_create(lv_scr_act(), NULL);          //  Add a list to the current screen
+        lv_obj_set_pos(list, 0, 0);                             //  Set its position
+        lv_obj_set_size(list, 240, 240);                        //  Set its size
+        lv_list_add_btn(list, NULL, "Item");                    //  Add a button for each item
+        */
+        let len = self.active_len(data).min(self.added); ////
+        for child in self.children.iter_mut().take(len) { ////
+            child.paint(ctx, data, env);
+        }
+        ////let mut children = self.children.iter_mut();
+        ////data.for_each(|child_data, _| {
+            ////if let Some(child) = children.next() {
+                ////child.paint(ctx, child_data, env);
+            ////}
+        ////});
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::List(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::harness::Harness;
+    use crate::widget::RadioGroup;
+    use crate::{Command, Selector};
+    use ::core::cell::Cell;
+    use heapless::consts::U4;
+
+    type Items = heapless::Vec<(), U4>;
+
+    const PUSH: Selector = Selector::new("druid-test.push-item");
+    const POP: Selector = Selector::new("druid-test.pop-item");
+
+    /// Adds and removes items on command, as the items can't change the length of the list.
+    #[derive(Clone)]
+    struct Editor {
+        id: WidgetId,
+        list: WidgetPod<Items, List<Items>>,
+    }
+
+    impl Widget<Items> for Editor {
+        fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+        fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Items, env: &Env) {
+            match event {
+                Event::Command(cmd) if cmd.is(PUSH) => data.push(()).unwrap(),
+                Event::Command(cmd) if cmd.is(POP) => data.pop().map(drop).unwrap(),
+                _ => self.list.event(ctx, event, data, env),
+            }
+        }
+
+        fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &Items, env: &Env) {
+            self.list.lifecycle(ctx, event, data, env)
+        }
+
+        fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &Items, data: &Items, env: &Env) {
+            self.list.update(ctx, data, env)
+        }
+
+        fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &Items, env: &Env) -> Size {
+            let size = self.list.layout(ctx, &bc.loosen(), data, env);
+            self.list.set_layout_rect(ctx, data, env, size.to_rect());
+            size
+        }
+
+        fn paint(&mut self, ctx: &mut PaintCtx, data: &Items, env: &Env) {
+            self.list.paint(ctx, data, env)
+        }
+
+        fn to_type(self) -> WidgetType<Items> { WidgetType::None }
+    }
+
+    /// A list of focusable items, with the ids of the list and its pool of children.
+    fn editor(ids: &Cell<Option<(WidgetId, [WidgetId; 4])>>) -> Editor {
+        let list = List::new(|| RadioGroup::new([("item", ())]));
+        let mut children = [list.id; 4];
+        for (id, child) in children.iter_mut().zip(list.children.iter()) {
+            *id = child.id();
+        }
+        ids.set(Some((list.id, children)));
+        Editor { id: WidgetId::next(), list: WidgetPod::new(list) }
+    }
+
+    fn items(len: usize) -> Items {
+        (0..len).map(|_| ()).collect()
+    }
+
+    #[test]
+    fn pool_is_built_up_front() {
+        let ids = Cell::new(None);
+        Harness::create_simple(items(2), || editor(&ids), |harness| {
+            let (list, children) = ids.get().unwrap();
+            harness.send_initial_events();
+            harness.just_layout();
+
+            // Only the slots with items are added and laid out
+            assert_eq!(harness.window().focus_chain(), &children[..2]);
+            assert!(harness.try_get_state(children[2]).is_none());
+            let item_height = harness.get_state(children[0]).layout_rect().height();
+            assert!(item_height > 0);
+            assert_eq!(harness.get_state(list).layout_rect().height(), 2 * item_height);
+        });
+    }
+
+    #[test]
+    fn slots_are_reused_as_items_come_and_go() {
+        let ids = Cell::new(None);
+        Harness::create_simple(items(1), || editor(&ids), |harness| {
+            let (list, children) = ids.get().unwrap();
+            harness.send_initial_events();
+            harness.just_layout();
+            let item_height = harness.get_state(children[0]).layout_rect().height();
+
+            // An idle slot is added when an item first appears at its index
+            harness.submit_command(Command::from(PUSH), None);
+            assert_eq!(harness.window().focus_chain(), &children[..2]);
+            harness.just_layout();
+            assert_eq!(harness.get_state(list).layout_rect().height(), 2 * item_height);
+
+            // When the item goes, the slot is kept idle and isn't sent anything
+            harness.submit_command(Command::from(POP), None);
+            harness.just_layout();
+            assert_eq!(harness.get_state(list).layout_rect().height(), item_height);
+            assert!(harness.try_get_state(children[1]).is_none());
+
+            // until it shows the next item at its index, without being added again
+            harness.submit_command(Command::from(PUSH), None);
+            harness.just_layout();
+            assert_eq!(harness.get_state(list).layout_rect().height(), 2 * item_height);
+            assert_eq!(harness.get_state(children[1]).layout_rect().y0, item_height);
+            assert!(harness.try_get_state(children[2]).is_none());
+        });
+    }
+}
@@ -15,78 +15,94 @@
 //! Common widgets.
 
 mod align;
+mod arc; ////
 ////mod button;
-////mod checkbox;
+mod checkbox;
 ////mod click;
 mod common;
-////mod container;
+mod container;
 ////mod controller;
-////mod either;
+mod either;
 ////mod env_scope;
 mod flex;
+mod gauge; ////
+mod grid; ////
 ////mod identity_wrapper;
-////mod image;
+mod image;
 ////mod invalidation;
+mod keyboard; ////
 mod label;
-////mod list;
+mod list;
 mod padding;
-////mod painter;
+mod painter;
 ////mod parse;
-////mod progress_bar;
-////mod radio;
-////mod scroll;
+mod picker; ////
+mod progress_bar;
+mod radio;
+mod roller; ////
+mod scroll;
 mod sized_box;
-////mod slider;
-////mod spinner;
-////mod split;
-////mod stepper;
-#[cfg(feature = "svg")]
-#[cfg_attr(docsrs, doc(cfg(feature = "svg")))]
+mod slider;
+mod spinner;
+mod split;
+mod stepper;
+////#[cfg(feature = "svg")]
+////#[cfg_attr(docsrs, doc(cfg(feature = "svg")))]
 mod svg;
-////mod switch;
-////mod textbox;
-////mod view_switcher;
+mod switch;
+mod textbox;
+mod view_switcher;
 #[allow(clippy::module_inception)]
 mod widget;
 ////mod widget_ext;
 mod boxed_widget; ////
+mod zstack; ////
 
-////pub use self::image::{Image, ImageData};
+pub use self::image::{ColorFormat, Image, ImageData};
 pub use align::Align;
+pub use arc::Arc; ////
 ////pub use button::Button;
-////pub use checkbox::Checkbox;
+pub use checkbox::Checkbox;
 ////pub use click::Click;
-pub use common::FillStrat;
-////pub use container::Container;
+pub use common::{FillStrat, Numeric};
+pub use container::Container;
 ////pub use controller::{Controller, ControllerHost};
-////pub use either::Either;
+pub use either::Either;
 ////pub use env_scope::EnvScope;
 pub use flex::{CrossAxisAlignment, Flex, FlexParams, MainAxisAlignment};
 pub use flex::Spacer; ////
+pub use gauge::{AnalogClock, ClockTime, Gauge, Hand, Time}; ////
+pub use grid::Grid; ////
 ////pub use identity_wrapper::IdentityWrapper;
+pub use keyboard::{Keyboard, KeyboardMode}; ////
 pub use label::{Label, LabelText};
-////pub use list::{List, ListIter};
+pub use list::{List, ListIter};
 pub use padding::Padding;
-////pub use painter::{BackgroundBrush, Painter};
+pub use painter::{BackgroundBrush, Painter};
 ////pub use parse::Parse;
-////pub use progress_bar::ProgressBar;
-////pub use radio::{Radio, RadioGroup};
-////pub use scroll::Scroll;
+pub use picker::{Date, DatePicker, TimePicker}; ////
+pub use progress_bar::ProgressBar;
+pub use radio::{Radio, RadioGroup};
+pub use roller::{Roller, ROLLER_DIGITS, ROLLER_LETTERS}; ////
+pub use scroll::{Scroll, SCROLL_TO}; ////
 pub use sized_box::SizedBox;
-////pub use slider::Slider;
-////pub use spinner::Spinner;
-////pub use split::Split;
-////pub use stepper::Stepper;
-#[cfg(feature = "svg")]
+pub use slider::Slider;
+pub use spinner::Spinner;
+pub use split::Split;
+pub use stepper::Stepper;
+////#[cfg(feature = "svg")]
 pub use svg::{Svg, SvgData};
-////pub use switch::Switch;
-////pub use textbox::TextBox;
-////pub use view_switcher::ViewSwitcher;
+pub use switch::Switch;
+pub use textbox::TextBox;
+pub use view_switcher::ViewSwitcher;
+pub use zstack::ZStack; ////
 #[doc(hidden)]
 pub use widget::{Widget, WidgetId};
 #[doc(hidden)]
 ////pub use widget_ext::WidgetExt;
 pub use boxed_widget::{BoxedWidget, MAX_WIDGETS, StaticWidgets, WidgetType}; ////
+#[cfg(test)]
+pub(crate) use boxed_widget::WidgetsLock; ////
 
 /// The types required to implement a `Widget`.
 ///
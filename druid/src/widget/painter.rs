@@ -0,0 +1,285 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use crate::piet::{LinearGradient, PaintBrush}; ////
+////use crate::piet::{FixedGradient, LinearGradient, PaintBrush, RadialGradient};
+use crate::{
+    BoxConstraints, Color, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle, LifeCycleCtx, ////
+    PaintCtx, Size, UpdateCtx, Widget, ////
+    ////BoxConstraints, Color, Data, Env, Event, EventCtx, Key, LayoutCtx, LifeCycle, LifeCycleCtx,
+    ////PaintCtx, RenderContext, Size, UpdateCtx, Widget,
+};
+use crate::{WidgetId, WidgetType}; ////
+use core::cell::Cell; ////
+
+/// A widget that only handles painting.
+///
+/// This is useful in a situation where layout is controlled elsewhere and you
+/// do not need to handle events, but you would like to customize appearance.
+///
+/// The draw function is a plain `fn`, not a boxed closure, so it lives in flash
+/// and the `Painter` can be stored in the static widget table. Draw with the
+/// [`PaintCtx`] shape API: `Rect`, `Line`, `Arc`, `Circle` and `PolyLine`.
+///
+/// **When is paint called?**
+///
+/// The `Painter` widget will call its [`paint`]  method anytime its [`Data`]
+/// is changed. If you would like it to repaint at other times (such as when
+/// hot or active state changes) you will need to call [`request_paint`] further
+/// up the tree, perhaps in a [`Controller`] widget.
+///
+/// # Examples
+///
+/// Drawing a battery icon filled to the charge level:
+///
+/// ```
+/// use druid::{Color, Env, PaintCtx, Rect};
+/// use druid::widget::Painter;
+///
+/// let battery: Painter<u8> = Painter::new(|ctx, percent: &u8, _env| {
+///     let bounds = ctx.size().to_rect();
+///     let level = bounds.width() as u16 * *percent as u16 / 100;
+///     ctx.stroke(bounds.to_rounded_rect(3), &Color::WHITE, 1);
+///     ctx.fill(Rect::new(bounds.x0, bounds.y0, bounds.x0 + level as u8, bounds.y1), &Color::rgb8(0, 0xff, 0));
+/// });
+/// ```
+///
+/// Plotting heart rate samples as a line chart:
+///
+/// ```
+/// use druid::{Color, Env, PaintCtx, Point, PolyLine};
+/// use druid::widget::Painter;
+///
+/// let chart: Painter<()> = Painter::new(|ctx, _data, _env| {
+///     let points = [Point::new(0, 40), Point::new(20, 10), Point::new(40, 30), Point::new(60, 20)];
+///     ctx.stroke(PolyLine::new(&points), &Color::rgb8(0xff, 0, 0), 2);
+/// });
+/// ```
+///
+/// [`paint`]: ../trait.Widget.html#tymethod.paint
+/// [`Data`]: ../trait.Data.html
+/// [`PaintCtx`]: ../struct.PaintCtx.html
+/// [`request_paint`]: ../EventCtx.html#method.request_paint
+/// [`Controller`]: trait.Controller.html
+#[derive(Clone)] ////
+pub struct Painter<T> { ////
+    /// Taken when the painter is first put in a `WidgetPod`, so that a
+    /// painter used as a [`BackgroundBrush`] doesn't hold a `WidgetId`.
+    id: Cell<Option<WidgetId>>, ////
+    paint: fn(&mut PaintCtx, &T, &Env), ////
+} ////
+////pub struct Painter<T>(Box<dyn FnMut(&mut PaintCtx, &T, &Env)>);
+
+/// Something that can be used as the background for a widget.
+///
+/// This represents anything that can be painted inside a widgets [`paint`]
+/// method; that is, it may have access to the [`Data`] and the [`Env`].
+///
+/// [`paint`]: ../trait.Widget.html#tymethod.paint
+/// [`Data`]: ../trait.Data.html
+/// [`Env`]: ../struct.Env.html
+#[non_exhaustive]
+#[derive(Clone)] ////
+pub enum BackgroundBrush<T> {
+    Color(Color),
+    ColorKey(KeyOrValue<Color>), ////
+    ////ColorKey(Key<Color>),
+    Linear(LinearGradient),
+    ////Radial(RadialGradient),
+    ////Fixed(FixedGradient),
+    Painter(Painter<T>),
+}
+
+impl<T> Painter<T> {
+    /// Create a new `Painter` with the provided [`paint`] fn.
+    ///
+    /// [`paint`]: ../trait.Widget.html#tymethod.paint
+    pub fn new(f: fn(&mut PaintCtx, &T, &Env)) -> Self { ////
+        Painter { id: Cell::new(None), paint: f } ////
+    ////pub fn new(f: impl FnMut(&mut PaintCtx, &T, &Env) + 'static) -> Self {
+        ////Painter(Box::new(f))
+    }
+}
+
+impl<T: Data> BackgroundBrush<T> {
+    /// Draw this `BackgroundBrush` into a provided [`PaintCtx`].
+    ///
+    /// [`PaintCtx`]: ../struct.PaintCtx.html
+    pub fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let bounds = ctx.size().to_rect();
+        match self {
+            Self::Painter(painter) => painter.paint(ctx, data, env),
+            _ => if let Some(brush) = self.to_brush(env) { ctx.fill(bounds, &brush) }, ////
+            ////Self::Color(color) => ctx.fill(bounds, color),
+            ////Self::ColorKey(key) => ctx.fill(bounds, &env.get(key)),
+            ////Self::Linear(grad) => ctx.fill(bounds, grad),
+            ////Self::Radial(grad) => ctx.fill(bounds, grad),
+            ////Self::Fixed(grad) => ctx.fill(bounds, grad),
+        }
+    }
+
+    /// The plain brush for this background, or `None` for a [`Painter`].
+    ///
+    /// Lets a [`Container`] fill its rounded panel directly, since the
+    /// renderer only clips to rectangles.
+    ///
+    /// [`Painter`]: struct.Painter.html
+    /// [`Container`]: struct.Container.html
+    pub(crate) fn to_brush(&self, env: &Env) -> Option<PaintBrush> { ////
+        match self { ////
+            Self::Color(color) => Some(PaintBrush::Color(*color)), ////
+            Self::ColorKey(key) => Some(PaintBrush::Color(key.resolve(env))), ////
+            Self::Linear(grad) => Some(PaintBrush::Linear(*grad)), ////
+            Self::Painter(_) => None, ////
+        } ////
+    } ////
+}
+
+impl<T: Data> Widget<T> for Painter<T> {
+    fn id(&self) -> Option<WidgetId> { ////
+        if self.id.get().is_none() { ////
+            self.id.set(Some(WidgetId::next())); ////
+        } ////
+        self.id.get() ////
+    } ////
+    fn event(&mut self, _: &mut EventCtx, _: &Event, _: &mut T, _: &Env) {}
+    fn lifecycle(&mut self, _: &mut LifeCycleCtx, _: &LifeCycle, _: &T, _: &Env) {}
+    fn update(&mut self, ctx: &mut UpdateCtx, old: &T, new: &T, _: &Env) {
+        if !old.same(new) {
+            ctx.request_paint();
+        }
+    }
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _: &T, _: &Env) -> Size {
+        bc.max()
+    }
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let canvas = lv_canvas_create(lv_scr_act(), NULL);                      //  Draw into a canvas sized to the widget
+        lv_canvas_set_buffer(canvas, buf, width, height, LV_IMG_CF_TRUE_COLOR); //  Shapes from `ctx.fill` and `ctx.stroke` become lv_canvas_draw_* calls
+        */
+        (self.paint)(ctx, data, env) ////
+        ////(self.0)(ctx, data, env)
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Painter(self)
+    }
+}
+
+impl<T> From<Color> for BackgroundBrush<T> {
+    fn from(src: Color) -> BackgroundBrush<T> {
+        BackgroundBrush::Color(src)
+    }
+}
+
+impl<T> From<KeyOrValue<Color>> for BackgroundBrush<T> { ////
+    fn from(src: KeyOrValue<Color>) -> BackgroundBrush<T> { ////
+////impl<T> From<Key<Color>> for BackgroundBrush<T> {
+    ////fn from(src: Key<Color>) -> BackgroundBrush<T> {
+        BackgroundBrush::ColorKey(src)
+    }
+}
+
+impl<T> From<LinearGradient> for BackgroundBrush<T> {
+    fn from(src: LinearGradient) -> BackgroundBrush<T> {
+        BackgroundBrush::Linear(src)
+    }
+}
+
+/* ////
+impl<T> From<RadialGradient> for BackgroundBrush<T> {
+    fn from(src: RadialGradient) -> BackgroundBrush<T> {
+        BackgroundBrush::Radial(src)
+    }
+}
+
+impl<T> From<FixedGradient> for BackgroundBrush<T> {
+    fn from(src: FixedGradient) -> BackgroundBrush<T> {
+        BackgroundBrush::Fixed(src)
+    }
+}
+*/ ////
+
+impl<T> From<Painter<T>> for BackgroundBrush<T> {
+    fn from(src: Painter<T>) -> BackgroundBrush<T> {
+        BackgroundBrush::Painter(src)
+    }
+}
+
+impl<T> From<PaintBrush> for BackgroundBrush<T> {
+    fn from(src: PaintBrush) -> BackgroundBrush<T> {
+        match src {
+            PaintBrush::Linear(grad) => BackgroundBrush::Linear(grad),
+            ////PaintBrush::Radial(grad) => BackgroundBrush::Radial(grad),
+            ////PaintBrush::Fixed(grad) => BackgroundBrush::Fixed(grad),
+            PaintBrush::Color(color) => BackgroundBrush::Color(color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+    use crate::tests::harness::Harness;
+    use crate::widget::Padding;
+    use crate::Rect;
+
+    const RED: Color = Color::rgb8(0xff, 0, 0);
+
+    #[test]
+    fn draws_with_the_data_and_size() {
+        let fb = Framebuffer::lock();
+        // A bar as long as the data, along the bottom of the painter
+        let bar = || Painter::new(|ctx, len: &u8, _| {
+            let bottom = ctx.size().height;
+            ctx.fill(Rect::new(0, bottom - 1, *len, bottom), &RED)
+        });
+        Harness::create_with_render(5, bar, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+        assert_eq!(fb.row(SIZE - 1)[..6], [0xff, 0xff, 0xff, 0xff, 0xff, 0]);
+        assert!((0..SIZE - 1).all(|y| fb.row(y) == [0; SIZE]));
+    }
+
+    #[test]
+    fn draws_in_widget_coordinates() {
+        let fb = Framebuffer::lock();
+        let padded = || Padding::new(4, Painter::new(|ctx, _, _| ctx.fill(Rect::new(0, 0, 2, 2), &RED)));
+        Harness::create_with_render((), padded, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+        assert_eq!(fb.row(3)[..7], [0; 7]);
+        assert_eq!(fb.row(4)[..7], [0, 0, 0, 0, 0xff, 0xff, 0]);
+        assert_eq!(fb.row(5)[..7], [0, 0, 0, 0, 0xff, 0xff, 0]);
+        assert_eq!(fb.row(6)[..7], [0; 7]);
+    }
+
+    #[test]
+    fn background_brush_fills_or_calls_the_painter() {
+        let env = crate::theme::init();
+        let painter: BackgroundBrush<()> = Painter::new(|_, _, _| {}).into();
+        assert!(painter.to_brush(&env).is_none());
+        // Never put in a `WidgetPod`, so it doesn't take an id that can't be freed
+        assert!(matches!(&painter, BackgroundBrush::Painter(p) if p.id.get().is_none()));
+        let color: BackgroundBrush<()> = RED.into();
+        assert!(matches!(color.to_brush(&env), Some(PaintBrush::Color(c)) if c == RED));
+    }
+}
@@ -0,0 +1,519 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Time and date pickers, built from rollers.
+
+use crate::widget::{Roller, Time};
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, KeyOrValue, LayoutCtx, LifeCycle, LifeCycleCtx,
+    PaintCtx, Rect, ScreenCoord, Size, UpdateCtx, Widget, WidgetId, WidgetType,
+};
+use crate::theme;
+
+const HOURS_24: &str = "00\n01\n02\n03\n04\n05\n06\n07\n08\n09\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19\n20\n21\n22\n23";
+/// The hours of a day from 12 AM to 11 PM, so rolling past 11 flips between AM and PM.
+const HOURS_12: &str = "12\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11";
+const MERIDIEMS: &str = "AM\nPM";
+const MINUTES: &str = "00\n01\n02\n03\n04\n05\n06\n07\n08\n09\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19\n20\n21\n22\n23\n24\n25\n26\n27\n28\n29\n30\n31\n32\n33\n34\n35\n36\n37\n38\n39\n40\n41\n42\n43\n44\n45\n46\n47\n48\n49\n50\n51\n52\n53\n54\n55\n56\n57\n58\n59";
+const DAYS: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n18\n19\n20\n21\n22\n23\n24\n25\n26\n27\n28\n29\n30\n31";
+const MONTHS: &str = "Jan\nFeb\nMar\nApr\nMay\nJun\nJul\nAug\nSep\nOct\nNov\nDec";
+/// The default years of a `DatePicker`, starting from `FIRST_YEAR`.
+const YEARS: &str = "2000\n2001\n2002\n2003\n2004\n2005\n2006\n2007\n2008\n2009\n2010\n2011\n2012\n2013\n2014\n2015\n2016\n2017\n2018\n2019\n2020\n2021\n2022\n2023\n2024\n2025\n2026\n2027\n2028\n2029\n2030\n2031\n2032\n2033\n2034\n2035\n2036\n2037\n2038\n2039\n2040\n2041\n2042\n2043\n2044\n2045\n2046\n2047\n2048\n2049\n2050\n2051\n2052\n2053\n2054\n2055\n2056\n2057\n2058\n2059\n2060\n2061\n2062\n2063\n2064\n2065\n2066\n2067\n2068\n2069\n2070\n2071\n2072\n2073\n2074\n2075\n2076\n2077\n2078\n2079\n2080\n2081\n2082\n2083\n2084\n2085\n2086\n2087\n2088\n2089\n2090\n2091\n2092\n2093\n2094\n2095\n2096\n2097\n2098\n2099";
+const FIRST_YEAR: u16 = 2000;
+
+/// A calendar date, for data that is just the date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Date {
+    /// Year, such as 2020.
+    pub year: u16,
+    /// Month, from 1 to 12.
+    pub month: u8,
+    /// Day of the month, from 1.
+    pub day: u8,
+}
+
+impl Date {
+    /// Create a date from the year, month (1 to 12) and day of the month.
+    pub fn new(year: u16, month: u8, day: u8) -> Self {
+        Date { year, month, day }
+    }
+
+    /// Returns true if `year` has a 29th of February.
+    pub fn is_leap_year(year: u16) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    /// Number of days in `month` (1 to 12) of `year`.
+    pub fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Returns true if the month and day exist in the year.
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month) && self.day >= 1 && self.day <= Date::days_in_month(self.year, self.month)
+    }
+}
+
+impl Default for Date {
+    fn default() -> Self {
+        Date::new(FIRST_YEAR, 1, 1)
+    }
+}
+
+impl Data for Date {
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// A time picker, with rollers for the hour and minute, like the
+/// `lv_roller`s of an alarm app.
+///
+/// The hours are shown from 0 to 23, or from 12 AM to 11 PM with a third
+/// roller when `theme::CLOCK_24_HOUR` is false. Rolling the hours past 11
+/// flips between AM and PM. The seconds are kept as is.
+///
+/// Only the parts of the time whose roller is rolled are written back, so a
+/// time that the rollers can't show, like an hour of 24, is kept until then.
+#[derive(Clone)]
+pub struct TimePicker<T> {
+    id: WidgetId,
+    use_24_hour: KeyOrValue<bool>,
+    size: Size,
+    hour: Roller<T>,
+    minute: Roller<T>,
+    meridiem: Roller<T>,
+    /// Reads the time.
+    get: fn(&T) -> Time,
+    /// Writes the time.
+    set: fn(&mut T, Time),
+}
+
+impl TimePicker<Time> {
+    /// Create a time picker for a `Time`.
+    pub fn new() -> Self {
+        let id = WidgetId::next();
+        TimePicker {
+            id,
+            use_24_hour: theme::CLOCK_24_HOUR,
+            size: Size::ZERO,
+            hour: Roller::hosted(id, HOURS_24).with_wraparound(true),
+            minute: Roller::hosted(id, MINUTES).with_wraparound(true),
+            meridiem: Roller::hosted(id, MERIDIEMS),
+            get: |time| *time,
+            set: |data, time| *data = time,
+        }
+    }
+}
+
+impl<T> TimePicker<T> {
+    /// Builder-style method to show the hours from 0 to 23, or with AM and PM.
+    ///
+    /// The default is `theme::CLOCK_24_HOUR`.
+    pub fn with_24_hour(mut self, use_24_hour: impl Into<KeyOrValue<bool>>) -> Self {
+        self.use_24_hour = use_24_hour.into();
+        self
+    }
+
+    /// Select `time` in the rollers.
+    fn show(&mut self, time: Time, use_24_hour: bool) {
+        if use_24_hour {
+            self.hour.set_options(HOURS_24);
+            self.hour.select(time.hour as usize);
+        } else {
+            self.hour.set_options(HOURS_12);
+            self.hour.select(time.hour as usize);
+            self.meridiem.select(time.hour as usize / 12);
+        }
+        self.minute.select(time.minute as usize);
+    }
+
+    /// The options selected in the hour, minute and meridiem rollers.
+    fn selection(&self) -> [usize; 3] {
+        [self.hour.selected(), self.minute.selected(), self.meridiem.selected()]
+    }
+
+    /// `time` with the parts whose rollers were rolled since `before`.
+    fn picked(&self, mut time: Time, before: [usize; 3], use_24_hour: bool) -> Time {
+        let [hour, minute, meridiem] = self.selection();
+        if hour != before[0] {
+            time.hour = hour as u8;
+        }
+        if minute != before[1] {
+            time.minute = minute as u8;
+        }
+        if !use_24_hour && meridiem != before[2] {
+            time.hour = time.hour % 12 + 12 * meridiem as u8;
+        }
+        time
+    }
+
+    /// Call `f` with each roller shown and its area, from left to right.
+    fn for_each_roller(&mut self, use_24_hour: bool, f: impl FnMut(&mut Roller<T>, Rect)) {
+        let count = if use_24_hour { 2 } else { 3 };
+        let rollers = [&mut self.hour, &mut self.minute, &mut self.meridiem];
+        for_each_column(self.size, rollers, count, f);
+    }
+}
+
+impl<T: Data> Widget<T> for TimePicker<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let use_24_hour = self.use_24_hour.resolve(env);
+        let time = (self.get)(data);
+        self.show(time, use_24_hour);
+        let before = self.selection();
+        self.for_each_roller(use_24_hour, |roller, area| {
+            roller.roll_event(ctx, event, area);
+        });
+        let picked = self.picked(time, before, use_24_hour);
+        if picked != time {
+            (self.set)(data, picked);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        if let LifeCycle::AnimFrame(interval) = event {
+            let row_height = theme::BASIC_WIDGET_HEIGHT.resolve(env);
+            let mut settling = false;
+            self.for_each_roller(false, |roller, _| settling |= roller.settle(*interval, row_height));
+            if settling {
+                ctx.request_anim_frame();
+            }
+            ctx.request_paint();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        self.size = bc.constrain(Size::new(theme::WIDE_WIDGET_WIDTH.resolve(env), self.hour.height(env)));
+        self.size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let hour = lv_roller_create(parent, NULL);                      //  One roller for each column
+        lv_roller_set_options(hour, HOURS_24, LV_ROLLER_MODE_INFINITE); //  Or HOURS_12, and another roller for AM and PM
+        lv_roller_set_selected(hour, time.hour, LV_ANIM_OFF);
+        let minute = lv_roller_create(parent, NULL);
+        lv_roller_set_options(minute, MINUTES, LV_ROLLER_MODE_INFINITE);
+        lv_roller_set_selected(minute, time.minute, LV_ANIM_OFF);
+        */
+        let use_24_hour = self.use_24_hour.resolve(env);
+        self.show((self.get)(data), use_24_hour);
+        self.for_each_roller(use_24_hour, |roller, area| roller.paint_rows(ctx, area, env));
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::TimePicker(self)
+    }
+}
+
+/// A date picker, with rollers for the day, month and year.
+///
+/// The days are limited to those in the selected month and year, so the day
+/// is moved back when the month is shortened, e.g. from 31 January to
+/// 28 or 29 February. The years are from 2000 to 2099, unless they are set
+/// with [`with_years`].
+///
+/// Only the parts of the date whose roller is rolled are written back, so a
+/// date that the rollers can't show, like a year before the first one, is
+/// kept until then.
+///
+/// [`with_years`]: #method.with_years
+#[derive(Clone)]
+pub struct DatePicker<T> {
+    id: WidgetId,
+    size: Size,
+    /// The year of the first option of the year roller.
+    first_year: u16,
+    day: Roller<T>,
+    month: Roller<T>,
+    year: Roller<T>,
+    /// Reads the date.
+    get: fn(&T) -> Date,
+    /// Writes the date.
+    set: fn(&mut T, Date),
+}
+
+impl DatePicker<Date> {
+    /// Create a date picker for a `Date`.
+    pub fn new() -> Self {
+        let id = WidgetId::next();
+        DatePicker {
+            id,
+            size: Size::ZERO,
+            first_year: FIRST_YEAR,
+            day: Roller::hosted(id, DAYS).with_wraparound(true),
+            month: Roller::hosted(id, MONTHS).with_wraparound(true),
+            year: Roller::hosted(id, YEARS),
+            get: |date| *date,
+            set: |data, date| *data = date,
+        }
+    }
+}
+
+impl<T> DatePicker<T> {
+    /// Builder-style method to set the years shown, as consecutive years
+    /// separated by `\n`, starting from `first_year`.
+    ///
+    /// The default is from 2000 to 2099.
+    pub fn with_years(mut self, first_year: u16, years: &'static str) -> Self {
+        self.first_year = first_year;
+        self.year.set_options(years);
+        self
+    }
+
+    /// Select `date` in the rollers, with only the days in its month.
+    fn show(&mut self, date: Date) {
+        self.year.select(date.year.saturating_sub(self.first_year) as usize);
+        self.month.select(date.month.saturating_sub(1) as usize);
+        self.day.truncate(Date::days_in_month(date.year, date.month) as usize);
+        self.day.select(date.day.saturating_sub(1) as usize);
+    }
+
+    /// The options selected in the day, month and year rollers.
+    fn selection(&self) -> [usize; 3] {
+        [self.day.selected(), self.month.selected(), self.year.selected()]
+    }
+
+    /// `date` with the parts whose rollers were rolled since `before`, and
+    /// the day moved back to the end of the month if it's beyond.
+    fn picked(&self, mut date: Date, before: [usize; 3]) -> Date {
+        let [day, month, year] = self.selection();
+        if day != before[0] {
+            date.day = day as u8 + 1;
+        }
+        if month != before[1] {
+            date.month = month as u8 + 1;
+        }
+        if year != before[2] {
+            date.year = self.first_year + year as u16;
+        }
+        if month != before[1] || year != before[2] {
+            date.day = date.day.min(Date::days_in_month(date.year, date.month));
+        }
+        date
+    }
+
+    /// Call `f` with each roller and its area, from left to right.
+    fn for_each_roller(&mut self, f: impl FnMut(&mut Roller<T>, Rect)) {
+        let rollers = [&mut self.day, &mut self.month, &mut self.year];
+        for_each_column(self.size, rollers, 3, f);
+    }
+}
+
+impl<T: Data> Widget<T> for DatePicker<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        let date = (self.get)(data);
+        self.show(date);
+        let before = self.selection();
+        self.for_each_roller(|roller, area| {
+            roller.roll_event(ctx, event, area);
+        });
+        let picked = self.picked(date, before);
+        if picked != date {
+            (self.set)(data, picked);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        if let LifeCycle::AnimFrame(interval) = event {
+            let row_height = theme::BASIC_WIDGET_HEIGHT.resolve(env);
+            let mut settling = false;
+            self.for_each_roller(|roller, _| settling |= roller.settle(*interval, row_height));
+            if settling {
+                ctx.request_anim_frame();
+            }
+            ctx.request_paint();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        self.size = bc.constrain(Size::new(theme::WIDE_WIDGET_WIDTH.resolve(env), self.day.height(env)));
+        self.size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let day = lv_roller_create(parent, NULL);                       //  One roller for each column
+        lv_roller_set_options(day, DAYS, LV_ROLLER_MODE_INFINITE);      //  Options beyond the month are hidden
+        lv_roller_set_selected(day, date.day - 1, LV_ANIM_OFF);
+        let month = lv_roller_create(parent, NULL);
+        lv_roller_set_options(month, MONTHS, LV_ROLLER_MODE_INFINITE);
+        lv_roller_set_selected(month, date.month - 1, LV_ANIM_OFF);
+        */
+        self.show((self.get)(data));
+        self.for_each_roller(|roller, area| roller.paint_rows(ctx, area, env));
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::DatePicker(self)
+    }
+}
+
+/// Call `f` with the first `count` rollers, each in an equal column of `size`.
+fn for_each_column<T>(size: Size, mut rollers: [&mut Roller<T>; 3], count: ScreenCoord, mut f: impl FnMut(&mut Roller<T>, Rect)) {
+    let width = size.width / count;
+    for (column, roller) in rollers.iter_mut().take(count as usize).enumerate() {
+        let x = width * column as ScreenCoord;
+        f(roller, Rect::new(x, 0, x + width, size.height));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::{event_ctx, layout_ctx, lifecycle_ctx};
+    use crate::{MouseEvent, Point, TimerToken};
+
+    /// Lay out `widget` on a 240 pixel screen, drag it through `points`, and let it settle.
+    fn drag<T: Data>(widget: &mut impl Widget<T>, data: &mut T, points: &[(ScreenCoord, ScreenCoord)]) {
+        let env = theme::init();
+        let bc = BoxConstraints::new(Size::ZERO, Size::new(240, 240));
+        assert_eq!(widget.layout(&mut layout_ctx(), &bc, data, &env), Size::new(200, 108));
+        let mut ctx = event_ctx();
+        let mouse = |(x, y)| MouseEvent::new(Point::new(x, y));
+        widget.event(&mut ctx, &Event::MouseDown(mouse(points[0])), data, &env);
+        for point in points {
+            widget.event(&mut ctx, &Event::MouseMove(mouse(*point)), data, &env);
+        }
+        widget.event(&mut ctx, &Event::MouseUp(mouse(*points.last().unwrap())), data, &env);
+        // Let the rollers settle
+        widget.lifecycle(&mut lifecycle_ctx(), &LifeCycle::AnimFrame(1_000_000_000), data, &env);
+    }
+
+    #[test]
+    fn time_picker_24_hour() {
+        let mut picker = TimePicker::new();
+        let mut time = Time::new(8, 0, 30);
+        // Tap the hour below, in the left column
+        drag(&mut picker, &mut time, &[(50, 90)]);
+        assert_eq!(time, Time::new(9, 0, 30));
+        // Tap the minute above, which wraps around
+        drag(&mut picker, &mut time, &[(150, 10)]);
+        assert_eq!(time, Time::new(9, 59, 30));
+        // Roll the hours up by 2 rows
+        drag(&mut picker, &mut time, &[(50, 90), (50, 50), (50, 18)]);
+        assert_eq!(time, Time::new(11, 59, 30));
+    }
+
+    #[test]
+    fn time_picker_12_hour() {
+        let mut picker = TimePicker::new().with_24_hour(false);
+        let mut time = Time::new(23, 30, 0);
+        // Tap AM, in the right column
+        drag(&mut picker, &mut time, &[(180, 10)]);
+        assert_eq!(time, Time::new(11, 30, 0));
+        // Tap the hour below 11 AM, which is 12 PM
+        drag(&mut picker, &mut time, &[(30, 90)]);
+        assert_eq!(time, Time::new(12, 30, 0));
+        // Tap the hour above 12 PM, which is 11 AM again
+        drag(&mut picker, &mut time, &[(30, 10)]);
+        assert_eq!(time, Time::new(11, 30, 0));
+        // 12 is the first option, so 12 AM is midnight
+        assert_eq!(picker.hour.option(0), Some("12"));
+        let mut time = Time::new(23, 30, 0);
+        drag(&mut picker, &mut time, &[(30, 90)]);
+        assert_eq!(time, Time::new(0, 30, 0));
+    }
+
+    #[test]
+    fn date_picker_validates_days() {
+        let mut picker = DatePicker::new();
+        let mut date = Date::new(2024, 1, 31);
+        // Roll the month up to February, in the middle column
+        drag(&mut picker, &mut date, &[(100, 80), (100, 60), (100, 44)]);
+        assert_eq!(date, Date::new(2024, 2, 29));
+        // The next year isn't a leap year
+        drag(&mut picker, &mut date, &[(180, 90)]);
+        assert_eq!(date, Date::new(2025, 2, 28));
+        // The days wrap around within February
+        drag(&mut picker, &mut date, &[(30, 90)]);
+        assert_eq!(date, Date::new(2025, 2, 1));
+        assert!(date.is_valid());
+    }
+
+    #[test]
+    fn pickers_keep_what_they_cant_show() {
+        let env = theme::init();
+        let mut picker = TimePicker::new();
+        let mut time = Time::new(24, 0, 0);
+        picker.event(&mut event_ctx(), &Event::Timer(TimerToken::INVALID), &mut time, &env);
+        assert_eq!(time, Time::new(24, 0, 0));
+        // Only the minute is rolled
+        drag(&mut picker, &mut time, &[(150, 90)]);
+        assert_eq!(time, Time::new(24, 1, 0));
+
+        let mut picker = DatePicker::new();
+        let mut date = Date::new(1999, 0, 0);
+        picker.event(&mut event_ctx(), &Event::Timer(TimerToken::INVALID), &mut date, &env);
+        assert_eq!(date, Date::new(1999, 0, 0));
+        // Only the month is rolled
+        drag(&mut picker, &mut date, &[(100, 90)]);
+        assert_eq!(date, Date::new(1999, 2, 0));
+    }
+
+    #[test]
+    fn date_picker_with_years() {
+        let mut picker = DatePicker::new().with_years(1990, "1990\n1991\n1992");
+        let mut date = Date::new(1990, 3, 1);
+        drag(&mut picker, &mut date, &[(180, 90)]);
+        assert_eq!(date, Date::new(1991, 3, 1));
+        // Can't roll beyond the last year
+        drag(&mut picker, &mut date, &[(180, 90)]);
+        drag(&mut picker, &mut date, &[(180, 90)]);
+        assert_eq!(date, Date::new(1992, 3, 1));
+    }
+
+    #[test]
+    fn leap_years() {
+        assert!(Date::is_leap_year(2000));
+        assert!(Date::is_leap_year(2024));
+        assert!(!Date::is_leap_year(1900));
+        assert!(!Date::is_leap_year(2023));
+        assert_eq!(Date::days_in_month(2023, 2), 28);
+        assert_eq!(Date::days_in_month(2023, 4), 30);
+        assert_eq!(Date::days_in_month(2023, 12), 31);
+        assert!(!Date::new(2023, 2, 29).is_valid());
+        assert!(!Date::new(2023, 13, 1).is_valid());
+    }
+}
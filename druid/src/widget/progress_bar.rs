@@ -0,0 +1,204 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A progress bar widget.
+
+use crate::{Point, Rect, Size}; ////
+////use crate::kurbo::{Point, Rect, Size};
+use crate::theme;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient, ////
+    PaintCtx, UnitPoint, UpdateCtx, Widget, ////
+    ////BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient,
+    ////PaintCtx, RenderContext, UnitPoint, UpdateCtx, Widget,
+};
+use crate::{ScreenCoord, ScreenFactor, WidgetId, WidgetType}; ////
+
+/// A progress bar, displaying a numeric progress value.
+///
+/// This type impls `Widget<f32>`, expecting a float in the range `0.0..1.0`.
+/// Values outside the range are clamped.
+#[derive(Debug, Clone)] ////
+////#[derive(Debug, Clone, Default)]
+pub struct ProgressBar<T> { ////
+////pub struct ProgressBar;
+    id: WidgetId, ////
+    /// Reads the progress, in the range `0.0..1.0`.
+    get: fn(&T) -> ScreenFactor, ////
+}
+
+impl ProgressBar<f32> { ////
+////impl ProgressBar {
+    /// Return a new `ProgressBar`.
+    pub fn new() -> ProgressBar<f32> { ////
+    ////pub fn new() -> ProgressBar {
+        ProgressBar { ////
+            id: WidgetId::next(),
+            get: |data| *data,
+        }
+        ////Self::default()
+    }
+}
+
+impl<T> ProgressBar<T> { ////
+    /// Width of the filled part of a bar that is `width` wide.
+    fn bar_width(&self, data: &T, width: ScreenCoord) -> ScreenCoord {
+        let clamped = (self.get)(data).clamp(0.0, 1.0);
+        (clamped * width as ScreenFactor + 0.5) as ScreenCoord
+    }
+}
+
+impl<T: Data> Widget<T> for ProgressBar<T> { ////
+////impl Widget<f64> for ProgressBar {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {} ////
+    ////fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut f64, _env: &Env) {}
+
+    fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {} ////
+    ////fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &f64, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &f64, _data: &f64, _env: &Env) {
+        ctx.request_paint();
+    }
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T, ////
+        ////_data: &f64,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("ProgressBar");
+        bc.constrain(Size::new(
+            theme::WIDE_WIDGET_WIDTH.resolve(env), ////
+            theme::BASIC_WIDGET_HEIGHT.resolve(env), ////
+            ////env.get(theme::WIDE_WIDGET_WIDTH),
+            ////env.get(theme::BASIC_WIDGET_HEIGHT),
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let size = ctx.size();
+        let bar = lv_bar_create(lv_scr_act(), NULL);            //  Add a bar to the current screen
+        lv_obj_set_size(bar, size.width, size.height);          //  Set its size
+        lv_bar_set_range(bar, 0, size.width);                   //  One step per pixel of the bar
+        lv_bar_set_value(bar, self.bar_width(data, size.width), LV_ANIM_OFF);  //  Fill the bar
+        */
+        ////let clamped = data.max(0.0).min(1.0);
+        let stroke_width = 2; ////
+        ////let stroke_width = 2.0;
+        ////let inset = -stroke_width / 2.0;
+
+        let rounded_rect = Rect::from_origin_size(
+            Point::ORIGIN,
+            Size::new(ctx.size().width, theme::BASIC_WIDGET_HEIGHT.resolve(env)), ////
+            ////(Size {
+                ////width: ctx.size().width,
+                ////height: env.get(theme::BASIC_WIDGET_HEIGHT),
+            ////})
+            ////.to_vec2(),
+        )
+        ////.inset(inset)
+        .to_rounded_rect(4); ////
+        ////.to_rounded_rect(4.0);
+
+        // Paint the background
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::BACKGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::BACKGROUND_LIGHT),
+                theme::BACKGROUND_DARK.resolve(env), ////
+                ////env.get(theme::BACKGROUND_DARK),
+            ),
+        );
+        ctx.fill(rounded_rect, &background_gradient);
+
+        // Paint the border
+        //  Strokes are drawn inside the shape, so the outer half of the border
+        //  is painted, and the bar fills the inside
+        ctx.stroke(rounded_rect, &theme::BORDER_DARK.resolve(env), stroke_width / 2); ////
+        ////ctx.stroke(rounded_rect, &env.get(theme::BORDER_DARK), stroke_width);
+
+        // Paint the bar
+        let inside = rounded_rect.shrink(stroke_width / 2).rect(); ////
+        let calculated_bar_width = self.bar_width(data, inside.width()); ////
+        ////let calculated_bar_width = clamped * rounded_rect.width();
+
+        let rounded_rect = Rect::from_origin_size(
+            Point::new(inside.x0, inside.y0), ////
+            Size::new(calculated_bar_width, inside.height()), ////
+            ////Point::new(-inset, 0.),
+            ////(Size {
+                ////width: calculated_bar_width,
+                ////height: env.get(theme::BASIC_WIDGET_HEIGHT),
+            ////})
+            ////.to_vec2(),
+        )
+        ////.inset((0.0, inset))
+        .to_rounded_rect(theme::PROGRESS_BAR_RADIUS.resolve(env)); ////
+        ////.to_rounded_rect(env.get(theme::PROGRESS_BAR_RADIUS));
+
+        let bar_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (theme::PRIMARY_LIGHT.resolve(env), theme::PRIMARY_DARK.resolve(env)), ////
+            ////(env.get(theme::PRIMARY_LIGHT), env.get(theme::PRIMARY_DARK)),
+        );
+        ctx.fill(rounded_rect, &bar_gradient);
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::ProgressBar(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer};
+    use crate::tests::harness::Harness;
+
+    #[test]
+    fn bar_width_is_clamped() {
+        let bar = ProgressBar::new();
+        assert_eq!(bar.bar_width(&0.0, 200), 0);
+        assert_eq!(bar.bar_width(&0.25, 200), 50);
+        assert_eq!(bar.bar_width(&1.0, 200), 200);
+        assert_eq!(bar.bar_width(&-0.5, 200), 0);
+        assert_eq!(bar.bar_width(&1.5, 200), 200);
+    }
+
+    #[test]
+    fn bar_fills_the_inside_of_the_border() {
+        let fb = Framebuffer::lock();
+        Harness::create_with_render(0.5, ProgressBar::new, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+        // Half of the 46 pixels inside the border, near the top of the gradients
+        assert_eq!(fb.row(3)[0], 0x3a);
+        assert!(fb.row(3)[1] > 0x40 && fb.row(3)[23] > 0x40);
+        assert!(fb.row(3)[24] < 0x40);
+    }
+}
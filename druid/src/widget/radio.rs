@@ -0,0 +1,362 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A radio button widget.
+
+use crate::{Circle, Point, Rect, Size}; ////
+////use crate::kurbo::{Circle, Point, Rect, Size};
+use crate::theme;
+use crate::widget::{Label, LabelText}; ////
+////use crate::widget::{CrossAxisAlignment, Flex, Label, LabelText, Padding};
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient, ////
+    PaintCtx, UnitPoint, UpdateCtx, Widget, WidgetPod, ////
+    ////BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient,
+    ////PaintCtx, RenderContext, UnitPoint, UpdateCtx, Widget, WidgetExt, WidgetPod,
+};
+use crate::{ScreenCoord, Vec, WidgetId, WidgetType}; ////
+
+/// Space between the radio buttons of a group.
+const GROUP_PADDING: ScreenCoord = 5; ////
+
+/// A group of radio buttons
+///
+/// The buttons are stacked in a column. When the group has the focus, e.g.
+/// when it is reached with the side button, the selected button is highlighted.
+#[derive(Clone)] ////
+////#[derive(Debug, Clone)]
+pub struct RadioGroup<T> { ////
+////pub struct RadioGroup;
+    id: WidgetId, ////
+    children: Vec<WidgetPod<T, Radio<T>>>, ////
+    has_focus: bool, ////
+}
+
+impl<T: Data> RadioGroup<T> { ////
+////impl RadioGroup {
+    /// Given a vector of `(label_text, enum_variant)` tuples, create a group of Radio buttons
+    ///
+    /// At most the capacity of a `Vec` of buttons is created; further variants are ignored.
+    pub fn new( ////
+        variants: impl IntoIterator<Item = (impl Into<LabelText<T>>, T)>, ////
+    ) -> RadioGroup<T> { ////
+    ////pub fn new<T: Data + PartialEq>(
+        ////variants: impl IntoIterator<Item = (impl Into<LabelText<T>> + 'static, T)>,
+    ////) -> impl Widget<T> {
+        ////let mut col = Flex::column().cross_axis_alignment(CrossAxisAlignment::Start);
+        let mut children = Vec::new(); ////
+        for (label, variant) in variants.into_iter() {
+            let radio = Radio::new(label, variant);
+            if children.push(WidgetPod::new(radio)).is_err() { ////
+                break;
+            }
+            ////col.add_child(Padding::new(5.0, radio));
+        }
+        RadioGroup { ////
+            id: WidgetId::next(),
+            children,
+            has_focus: false,
+        }
+        ////col
+    }
+
+    /// Highlight the selected button while the group has the focus.
+    fn update_focus(&mut self, data: &T) { ////
+        for child in self.children.iter_mut() {
+            let radio = child.widget_mut();
+            radio.has_focus = self.has_focus && radio.is_selected(data);
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for RadioGroup<T> { ////
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        for child in self.children.iter_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        if let LifeCycle::FocusChanged(has_focus) = event {
+            self.has_focus = *has_focus;
+            self.update_focus(data);
+            ctx.request_paint();
+        }
+        for child in self.children.iter_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if !old_data.same(data) {
+            self.update_focus(data);
+        }
+        for child in self.children.iter_mut() {
+            child.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("RadioGroup");
+        let mut width = bc.min().width;
+        let mut y: ScreenCoord = 0;
+        for child in self.children.iter_mut() {
+            let child_bc = BoxConstraints::new(
+                Size::new(0, 0),
+                Size::new(bc.max().width.saturating_sub(2 * GROUP_PADDING), ScreenCoord::MAX),
+            );
+            let child_size = child.layout(ctx, &child_bc, data, env);
+            let origin = Point::new(GROUP_PADDING, y.saturating_add(GROUP_PADDING));
+            child.set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, child_size));
+            width = width.max(child_size.width.saturating_add(2 * GROUP_PADDING));
+            y = y.saturating_add(child_size.height).saturating_add(2 * GROUP_PADDING);
+        }
+        bc.constrain(Size::new(width, y))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        for child in self.children.iter_mut() {
+            child.paint(ctx, data, env);
+        }
+    }
+
+    /// The selected radio shows the focus, so there's no ring around the group.
+    fn paint_focus_ring(&mut self, _ctx: &mut PaintCtx, _data: &T, _env: &Env) {}
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::RadioGroup(self)
+    }
+}
+
+/// A single radio button
+#[derive(Clone)] ////
+pub struct Radio<T> {
+    id: WidgetId, ////
+    variant: T,
+    child_label: WidgetPod<T, Label<T>>, ////
+    ////child_label: WidgetPod<T, Box<dyn Widget<T>>>,
+    /// Show the focus highlight.
+    has_focus: bool, ////
+}
+
+impl<T: Data> Radio<T> {
+    /// Create a lone Radio button from label text and an enum variant
+    pub fn new(label: impl Into<LabelText<T>>, variant: T) -> Radio<T> {
+        Radio {
+            id: WidgetId::next(), ////
+            variant,
+            child_label: WidgetPod::new(Label::new(label)), ////
+            ////child_label: WidgetPod::new(Label::new(label).boxed()),
+            has_focus: false, ////
+        }
+    }
+
+    /// Check if data enum matches our variant
+    fn is_selected(&self, data: &T) -> bool { ////
+        data.same(&self.variant)
+    }
+}
+
+impl<T: Data> Widget<T> for Radio<T> { ////
+////impl<T: Data + PartialEq> Widget<T> for Radio<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        match event {
+            Event::MouseDown(_) => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    if ctx.is_hot() {
+                        *data = self.variant.clone();
+                    }
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) { ////
+    ////fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        self.child_label.lifecycle(ctx, event, data, env); ////
+        if let LifeCycle::HotChanged(_) = event {
+            ctx.request_paint();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {
+        self.child_label.update(ctx, data, env); ////
+        ctx.request_paint();
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Radio");
+
+        ////let label_size = self.child_label.layout(ctx, &bc, data, env);
+        let padding: ScreenCoord = 5; ////
+        ////let padding = 5.0;
+        let label_x_offset = theme::BASIC_WIDGET_HEIGHT.resolve(env) + padding; ////
+        ////let label_x_offset = env.get(theme::BASIC_WIDGET_HEIGHT) + padding;
+        // The label takes the width beside the box, so that its rect stays on the screen ////
+        let label_size = self.child_label.layout(ctx, &bc.shrink((label_x_offset, 0)), data, env); ////
+        let origin = Point::new(label_x_offset, 0); ////
+        ////let origin = Point::new(label_x_offset, 0.0);
+
+        self.child_label.set_layout_rect(
+            ctx,
+            data,
+            env,
+            Rect::from_origin_size(origin, label_size),
+        );
+
+        bc.constrain(Size::new(
+            label_x_offset.saturating_add(label_size.width), ////
+            ////label_x_offset + label_size.width,
+            theme::BASIC_WIDGET_HEIGHT.resolve(env).max(label_size.height), ////
+            ////env.get(theme::BASIC_WIDGET_HEIGHT).max(label_size.height),
+        ))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let cb = lv_checkbox_create(lv_scr_act(), NULL);        //  LVGL has no radio button: use a checkbox
+        lv_obj_add_style(cb, LV_CHECKBOX_PART_BULLET, &style_radio);  //  with a round bullet
+        lv_checkbox_set_text(cb, label_text);                   //  Set the label from the child label
+        lv_checkbox_set_checked(cb, self.is_selected(data));    //  Check the button for the selected variant
+        if self.has_focus { lv_obj_add_state(cb, LV_STATE_FOCUSED); }  //  Show the focus highlight
+        */
+        let size = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        //  The circles were drawn for a button of 18 pixels, so they're scaled to the button
+        let scale = |v: ScreenCoord| (v as u16 * size as u16 / 18) as ScreenCoord; ////
+        let center = Point::new(size / 2, size / 2); ////
+
+        let circle = Circle::new(center, scale(7)); ////
+        ////let circle = Circle::new((size / 2., size / 2.), 7.);
+
+        // Paint the background
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::BACKGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::BACKGROUND_LIGHT),
+                theme::BACKGROUND_DARK.resolve(env), ////
+                ////env.get(theme::BACKGROUND_DARK),
+            ),
+        );
+
+        ctx.fill(circle, &background_gradient);
+
+        //  The selected radio of a focused group shows the focus on its border
+        let (border_color, border_width) = if self.has_focus { ////
+            (theme::FOCUS_RING_COLOR.resolve(env), theme::FOCUS_RING_WIDTH.resolve(env)) ////
+        } else if ctx.is_hot() { ////
+        ////let border_color = if ctx.is_hot() {
+            (theme::BORDER_LIGHT.resolve(env), 1) ////
+            ////env.get(theme::BORDER_LIGHT)
+        } else {
+            (theme::BORDER_DARK.resolve(env), 1) ////
+            ////env.get(theme::BORDER_DARK)
+        };
+
+        ctx.stroke(circle, &border_color, border_width); ////
+        ////ctx.stroke(circle, &border_color, 1.);
+
+        // Check if data enum matches our variant
+        if self.is_selected(data) { ////
+        ////if *data == self.variant {
+            let inner_circle = Circle::new(center, scale(2)); ////
+            ////let inner_circle = Circle::new((size / 2., size / 2.), 2.);
+
+            ctx.fill(inner_circle, &theme::LABEL_COLOR.resolve(env)); ////
+            ////ctx.fill(inner_circle, &env.get(theme::LABEL_COLOR));
+        }
+
+        // Paint the text label
+        self.child_label.paint(ctx, data, env);
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Radio(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer};
+    use crate::tests::harness::Harness;
+    use crate::tests::helpers::lifecycle_ctx;
+    use crate::{ButtonAction, ButtonEvent, MouseEvent};
+
+    #[test]
+    fn tap_selects_variant() {
+        Harness::create_simple(0u8, || Radio::new("24h", 1u8), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let mouse = MouseEvent::new(Point::new(4, 4));
+            harness.event(Event::MouseDown(mouse.clone()));
+            harness.event(Event::MouseUp(mouse));
+            assert_eq!(*harness.data(), 1);
+        });
+    }
+
+    #[test]
+    fn focus_highlights_selection() {
+        let env = theme::init();
+        let mut group = RadioGroup::new([("12h", 0u8), ("24h", 1u8)].iter().cloned());
+        let mut ctx = lifecycle_ctx();
+        group.lifecycle(&mut ctx, &LifeCycle::FocusChanged(true), &1, &env);
+        assert!(!group.children[0].widget().has_focus);
+        assert!(group.children[1].widget().has_focus);
+
+        group.lifecycle(&mut ctx, &LifeCycle::FocusChanged(false), &1, &env);
+        assert!(!group.children[1].widget().has_focus);
+    }
+
+    #[test]
+    fn selected_radio_is_filled_and_shows_the_focus() {
+        let fb = Framebuffer::lock();
+        let group = || RadioGroup::new([("12h", 0u8), ("24h", 1u8)].iter().cloned());
+        Harness::create_with_render(0u8, group, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+            // The dot of the first radio, in the middle of its padded circle
+            let middle = (GROUP_PADDING + 18) as usize;
+            assert_eq!(fb.row(middle)[middle], 0xff);
+            assert_eq!(fb.row(middle)[middle - 14], 0x3a);
+
+            // A button press moves the focus to the group
+            let release = ButtonAction::Release { long: false };
+            harness.event(Event::Button(ButtonEvent { button: 0, action: release }));
+            harness.paint();
+            // and the selected radio shows it with a thicker border in the focus color
+            assert_eq!(fb.row(middle)[middle - 14..middle - 12], [0x5c, 0x5c]);
+        }, Some(draw));
+    }
+}
@@ -0,0 +1,435 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A roller widget, for picking one of a list of options.
+
+use core::time::Duration;
+
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Rect, ScreenCoord, ScreenFactor, Size, UpdateCtx, Widget, WidgetId, WidgetType,
+};
+use crate::widget::common::Numeric;
+use crate::theme;
+
+/// Options of a `Roller` for entering digits, such as a PIN or an alarm time.
+pub const ROLLER_DIGITS: &str = "0\n1\n2\n3\n4\n5\n6\n7\n8\n9";
+/// Options of a `Roller` for entering short text, starting with a space.
+pub const ROLLER_LETTERS: &str =
+    " \na\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no\np\nq\nr\ns\nt\nu\nv\nw\nx\ny\nz";
+
+/// Time for the options to settle by one row, in seconds.
+const SETTLE_TIME: ScreenFactor = 0.15;
+/// Distance that a touch moves before it rolls the options instead of tapping.
+const TOUCH_SLOP: ScreenCoord = 4;
+
+/// A roller, like `lv_roller`, for picking one of a list of options with
+/// large touch targets.
+///
+/// The options are separated by `\n`, and the selected option is in the
+/// middle row. Dragging rolls the options, which settle on the selected row
+/// on `AnimFrame`. Tapping a row above or below the middle selects it.
+///
+/// The roller implements `Widget<T>` for any [`Numeric`] `T`, which is the
+/// index of the selected option.
+///
+/// [`Numeric`]: trait.Numeric.html
+#[derive(Clone)]
+pub struct Roller<T> {
+    id: WidgetId,
+    options: &'static str,
+    /// Number of options.
+    len: usize,
+    visible_rows: u8,
+    wrap: bool,
+    selected: usize,
+    /// Distance that the options are rolled down from the selected row.
+    offset: ScreenFactor,
+    /// Vertical position of the touch when it started, and when it last moved.
+    touch: Option<(ScreenCoord, ScreenCoord)>,
+    /// Set once the touch has moved beyond `TOUCH_SLOP`.
+    dragged: bool,
+    /// Reads the index of the selected option.
+    get: fn(&T) -> usize,
+    /// Writes the index of the selected option.
+    set: fn(&mut T, usize),
+}
+
+impl<T: Numeric> Roller<T> {
+    /// Create a roller for the options separated by `\n`, showing 3 rows.
+    pub fn new(options: &'static str) -> Self {
+        Roller {
+            get: |data| data.to_factor() as usize,
+            set: |data, index| *data = T::from_factor(index as ScreenFactor),
+            ..Roller::hosted(WidgetId::next(), options)
+        }
+    }
+}
+
+impl<T> Roller<T> {
+    /// Create a roller that is shown by another widget, with that widget's id.
+    /// The selected option is kept in the roller.
+    pub(crate) fn hosted(id: WidgetId, options: &'static str) -> Self {
+        Roller {
+            id,
+            options,
+            len: options.split('\n').count(),
+            visible_rows: 3,
+            wrap: false,
+            selected: 0,
+            offset: 0.,
+            touch: None,
+            dragged: false,
+            get: |_| 0,
+            set: |_, _| (),
+        }
+    }
+
+    /// Builder-style method to set the number of rows shown, which is rounded up to odd.
+    ///
+    /// The default is 3 rows.
+    pub fn with_visible_rows(mut self, rows: u8) -> Self {
+        self.visible_rows = rows.max(1) | 1;
+        self
+    }
+
+    /// Builder-style method to roll from the last option back to the first,
+    /// like `LV_ROLLER_MODE_INFINITE`.
+    ///
+    /// The default is `false`.
+    pub fn with_wraparound(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// The option at `index`, if any.
+    pub fn option(&self, index: usize) -> Option<&'static str> {
+        self.options.split('\n').nth(index)
+    }
+
+    /// Index of the selected option.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the option at `index`, or the last option if it's beyond the end.
+    pub(crate) fn select(&mut self, index: usize) {
+        self.selected = index.min(self.len.saturating_sub(1));
+    }
+
+    /// Show `options` instead, keeping the selected index if it's still valid.
+    pub(crate) fn set_options(&mut self, options: &'static str) {
+        self.options = options;
+        self.len = options.split('\n').count();
+        self.select(self.selected);
+    }
+
+    /// Show only the first `len` options, such as the days in a month.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.len = self.options.split('\n').count().min(len);
+        self.select(self.selected);
+    }
+
+    /// Height of the roller, with each row tall enough for a fingertip.
+    pub(crate) fn height(&self, env: &Env) -> ScreenCoord {
+        theme::BASIC_WIDGET_HEIGHT.resolve(env).saturating_mul(self.visible_rows)
+    }
+
+    /// Returns true while the options are touched.
+    pub(crate) fn is_touched(&self) -> bool {
+        self.touch.is_some()
+    }
+
+    /// Height of each row within `area`.
+    fn row_height(&self, area: Rect) -> ScreenFactor {
+        area.height() as ScreenFactor / self.visible_rows as ScreenFactor
+    }
+
+    /// Select the next option if `forward`, otherwise the previous one.
+    /// Returns false at the ends, unless the roller wraps around.
+    fn step(&mut self, forward: bool) -> bool {
+        let last = self.len.saturating_sub(1);
+        self.selected = match (forward, self.selected) {
+            (true, selected) if selected < last => selected + 1,
+            (true, _) if self.wrap => 0,
+            (false, 0) if self.wrap => last,
+            (false, selected) if selected > 0 => selected - 1,
+            _ => return false,
+        };
+        true
+    }
+
+    /// Roll the options down by `distance`, selecting the option nearest
+    /// to the middle row. The options can't be rolled beyond the ends.
+    fn roll(&mut self, distance: ScreenFactor, row_height: ScreenFactor) {
+        let half = row_height / 2.;
+        self.offset += distance;
+        while self.offset > half && self.step(false) {
+            self.offset -= row_height;
+        }
+        while self.offset < -half && self.step(true) {
+            self.offset += row_height;
+        }
+        self.offset = self.offset.max(-half).min(half);
+    }
+
+    /// Move the options towards the selected row for `interval` nanoseconds.
+    /// Returns true if they haven't settled yet.
+    pub(crate) fn settle(&mut self, interval: u64, row_height: ScreenCoord) -> bool {
+        let change = Duration::from_nanos(interval).as_secs_f32() * row_height as ScreenFactor / SETTLE_TIME;
+        if self.offset > change {
+            self.offset -= change;
+        } else if self.offset < -change {
+            self.offset += change;
+        } else {
+            self.offset = 0.;
+        }
+        self.offset != 0.
+    }
+
+    /// Handle touches on the options within `area`. Returns the index of the
+    /// selected option when the middle row is tapped.
+    pub(crate) fn roll_event(&mut self, ctx: &mut EventCtx, event: &Event, area: Rect) -> Option<usize> {
+        let row_height = self.row_height(area);
+        match event {
+            Event::MouseDown(mouse) if area.winding(mouse.pos) != 0 => {
+                self.touch = Some((mouse.pos.y, mouse.pos.y));
+                self.dragged = false;
+                ctx.set_active(true);
+            }
+            Event::MouseMove(mouse) => {
+                if let Some((start, last)) = self.touch {
+                    self.dragged |= (mouse.pos.y as i16 - start as i16).abs() > TOUCH_SLOP as i16;
+                    if self.dragged {
+                        self.roll(mouse.pos.y as ScreenFactor - last as ScreenFactor, row_height);
+                        self.touch = Some((start, mouse.pos.y));
+                        ctx.request_paint();
+                    }
+                }
+            }
+            Event::MouseUp(mouse) if self.touch.is_some() => {
+                self.touch = None;
+                ctx.set_active(false);
+                ctx.request_anim_frame();
+                ctx.request_paint();
+                if self.dragged {
+                    return None;
+                }
+                // Rows tapped below the middle row are positive
+                let middle = area.y0 as ScreenFactor + row_height * (self.visible_rows / 2) as ScreenFactor;
+                let rows = (mouse.pos.y as ScreenFactor - middle) / row_height;
+                // Round down, as `as` rounds towards zero
+                let rows = rows as i16 - (rows < (rows as i16) as ScreenFactor) as i16;
+                if rows == 0 {
+                    return Some(self.selected);
+                }
+                for _ in 0..rows.abs() {
+                    if self.step(rows > 0) {
+                        // Settle from the row that was tapped
+                        self.offset += if rows > 0 { row_height } else { -row_height };
+                    }
+                }
+            }
+            _ => (),
+        }
+        None
+    }
+
+    /// Call `f` with the index of each option shown within `area`, and its rect
+    /// as rolled. The rects of the first and last rows may extend beyond `area`.
+    fn for_each_row(&self, area: Rect, mut f: impl FnMut(usize, Rect)) {
+        if self.len == 0 {
+            return;
+        }
+        let row_height = self.row_height(area);
+        let middle = (self.visible_rows / 2) as i16;
+        // One more row on each side, for the rows rolling into view
+        for row in -middle - 1..=middle + 1 {
+            let index = self.selected as i16 + row;
+            let index = if self.wrap {
+                index.rem_euclid(self.len as i16)
+            } else if index < 0 || index >= self.len as i16 {
+                continue;
+            } else {
+                index
+            };
+            let y0 = area.y0 as ScreenFactor + row_height * (row + middle) as ScreenFactor + self.offset;
+            let y1 = y0 + row_height;
+            if y1 <= area.y0 as ScreenFactor || y0 >= area.y1 as ScreenFactor {
+                continue;
+            }
+            let clamp = |y: ScreenFactor| y.max(0.).min(ScreenCoord::MAX as ScreenFactor) as ScreenCoord;
+            f(index as usize, Rect::new(area.x0, clamp(y0), area.x1, clamp(y1)));
+        }
+    }
+
+    /// Paint the options within `area`.
+    pub(crate) fn paint_rows(&self, ctx: &mut PaintCtx, area: Rect, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let roller = lv_roller_create(lv_scr_act(), NULL);              //  Add a roller to the current screen
+        lv_roller_set_options(roller, self.options, LV_ROLLER_MODE_NORMAL);  //  Or LV_ROLLER_MODE_INFINITE to wrap around
+        lv_roller_set_visible_row_count(roller, self.visible_rows);     //  Rows shown
+        lv_roller_set_selected(roller, self.selected, LV_ANIM_ON);      //  Roll to the selected option
+        */
+        let row_height = self.row_height(area) as ScreenCoord;
+        let middle = area.y0.saturating_add(row_height.saturating_mul(self.visible_rows / 2));
+        let selected_rect = Rect::new(area.x0, middle, area.x1, middle.saturating_add(row_height));
+        ctx.fill(area, &theme::BACKGROUND_LIGHT.resolve(env));
+        ctx.fill(selected_rect, &theme::PRIMARY_DARK.resolve(env));
+        let border_color = theme::BORDER_DARK.resolve(env);
+        ctx.with_save(|ctx| {
+            ctx.clip(area);
+            self.for_each_row(area, |_index, rect| {
+                ctx.stroke(rect, &border_color, 1);
+                //// TODO: Draw the option text
+            });
+        });
+    }
+}
+
+impl<T: Data> Widget<T> for Roller<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) {
+        self.select((self.get)(data));
+        let area = ctx.size().to_rect();
+        self.roll_event(ctx, event, area);
+        if self.selected != (self.get)(data) {
+            (self.set)(data, self.selected);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.register_for_focus();
+        }
+        if let LifeCycle::AnimFrame(interval) = event {
+            if self.settle(*interval, theme::BASIC_WIDGET_HEIGHT.resolve(env)) {
+                ctx.request_anim_frame();
+            }
+            ctx.request_paint();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) {
+        if !old_data.same(data) {
+            self.select((self.get)(data));
+            ctx.request_paint();
+        }
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        bc.constrain(Size::new(theme::WIDE_WIDGET_WIDTH.resolve(env) / 2, self.height(env)))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        let area = ctx.size().to_rect();
+        self.paint_rows(ctx, area, env);
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::Roller(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::event_ctx;
+    use crate::{MouseEvent, Point};
+
+    fn area() -> Rect {
+        Rect::new(0, 0, 100, 108)
+    }
+
+    fn touch(roller: &mut Roller<u8>, ctx: &mut EventCtx, ys: &[ScreenCoord]) -> Option<usize> {
+        roller.roll_event(ctx, &Event::MouseDown(MouseEvent::new(Point::new(50, ys[0]))), area());
+        for y in ys {
+            roller.roll_event(ctx, &Event::MouseMove(MouseEvent::new(Point::new(50, *y))), area());
+        }
+        let last = *ys.last().unwrap();
+        roller.roll_event(ctx, &Event::MouseUp(MouseEvent::new(Point::new(50, last))), area())
+    }
+
+    #[test]
+    fn options_are_split() {
+        let roller = Roller::<u8>::new("one\ntwo\nthree");
+        assert_eq!(roller.len, 3);
+        assert_eq!(roller.option(1), Some("two"));
+        assert_eq!(roller.option(3), None);
+        assert_eq!(Roller::<u8>::new(ROLLER_LETTERS).len, 27);
+    }
+
+    #[test]
+    fn tap_selects_row() {
+        let mut roller = Roller::<u8>::new(ROLLER_DIGITS);
+        let mut ctx = event_ctx();
+        // Rows are 36 pixels, the middle row is selected
+        assert_eq!(touch(&mut roller, &mut ctx, &[50]), Some(0));
+        assert_eq!(touch(&mut roller, &mut ctx, &[100]), None);
+        assert_eq!(roller.selected(), 1);
+        assert_eq!(roller.offset, 36.);
+        // The first row is above the first option
+        assert_eq!(touch(&mut roller, &mut ctx, &[10]), None);
+        assert_eq!(touch(&mut roller, &mut ctx, &[10]), None);
+        assert_eq!(roller.selected(), 0);
+    }
+
+    #[test]
+    fn tap_selects_row_with_5_rows() {
+        let mut roller = Roller::<u8>::new(ROLLER_DIGITS).with_visible_rows(5);
+        let mut ctx = event_ctx();
+        roller.select(5);
+        // Rows are 21.6 pixels, the middle row is from 43.2 to 64.8
+        assert_eq!(touch(&mut roller, &mut ctx, &[50]), Some(5));
+        assert_eq!(touch(&mut roller, &mut ctx, &[10]), None);
+        assert_eq!(roller.selected(), 3);
+        assert_eq!(touch(&mut roller, &mut ctx, &[30]), None);
+        assert_eq!(roller.selected(), 2);
+        assert_eq!(touch(&mut roller, &mut ctx, &[100]), None);
+        assert_eq!(roller.selected(), 4);
+        assert_eq!(touch(&mut roller, &mut ctx, &[70]), None);
+        assert_eq!(roller.selected(), 5);
+    }
+
+    #[test]
+    fn drag_rolls_and_settles() {
+        let mut roller = Roller::<u8>::new(ROLLER_DIGITS);
+        let mut ctx = event_ctx();
+        // Roll up by 2 rows and a bit
+        assert_eq!(touch(&mut roller, &mut ctx, &[100, 60, 20]), None);
+        assert_eq!(roller.selected(), 2);
+        assert_eq!(roller.offset, -8.);
+        assert!(roller.settle(10_000_000, 36));
+        assert!(!roller.settle(100_000_000, 36));
+        assert_eq!(roller.offset, 0.);
+        // Can't roll beyond the first option
+        touch(&mut roller, &mut ctx, &[0, 100, 107]);
+        assert_eq!(roller.selected(), 0);
+        assert_eq!(roller.offset, 18.);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut roller = Roller::<u8>::new(ROLLER_DIGITS).with_wraparound(true);
+        let mut rows = crate::Vec::<usize>::new();
+        roller.for_each_row(area(), |index, _| rows.push(index).unwrap());
+        assert_eq!(&rows[..], &[9, 0, 1]);
+        assert!(roller.step(false));
+        assert_eq!(roller.selected(), 9);
+        assert!(roller.step(true));
+        assert_eq!(roller.selected(), 0);
+    }
+}
@@ -0,0 +1,1092 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A container that scrolls its contents.
+
+////use std::f64::INFINITY;
+////use std::time::Duration;
+
+use crate::{Point, Rect, Size, Vec2}; ////
+////use crate::kurbo::{Affine, Point, Rect, RoundedRect, Size, Vec2};
+////use crate::theme;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    UpdateCtx, Widget, WidgetPod,
+    ////RenderContext, TimerToken, UpdateCtx, Widget, WidgetPod,
+};
+use crate::{BoxedWidget, InternalEvent, MouseEvent, ScreenCoord, ScreenFactor, Selector, WidgetId, WidgetType}; ////
+
+////const SCROLLBAR_MIN_SIZE: f64 = 45.0;
+
+/// Scroll so that the region in the payload, in the child's coordinates, is visible.
+///
+/// The command is handled by the `Scroll` it targets, as with [`Scroll::scroll_to`].
+///
+/// [`Scroll::scroll_to`]: struct.Scroll.html#method.scroll_to
+pub const SCROLL_TO: Selector<Rect> = Selector::new("druid-builtin.scroll-to");
+
+/// Length of one physics step, in nanoseconds. Animation frames are split
+/// into steps of this length, so the motion only depends on the total time.
+const STEP_NS: u64 = 8_000_000;
+/// Length of one physics step, in seconds.
+const STEP_SECS: ScreenFactor = STEP_NS as ScreenFactor * 1e-9;
+/// Distance a touch must move before it becomes a drag instead of a tap.
+const TOUCH_SLOP: ScreenCoord = 4;
+/// How far the content may be pulled past either edge, in pixels.
+const MAX_OVERSCROLL: ScreenFactor = 40.0;
+/// Fraction of the fling velocity lost per second.
+const DECELERATION: ScreenFactor = 4.0;
+/// Below this speed, in pixels per second, the content comes to rest.
+const MIN_VELOCITY: ScreenFactor = 20.0;
+/// Fastest fling, in pixels per second.
+const MAX_VELOCITY: ScreenFactor = 2000.0;
+/// Spring constant of the edge bounce and of `scroll_to`.
+const STIFFNESS: ScreenFactor = 150.0;
+/// Damping of the spring, close to critical damping for `STIFFNESS`.
+const DAMPING: ScreenFactor = 25.0;
+
+/* ////
+#[derive(Debug, Clone)]
+enum ScrollDirection {
+    Horizontal,
+    Vertical,
+    All,
+}
+
+impl ScrollDirection {
+    /// Return the maximum size the container can be given
+    /// its scroll direction and box constraints.
+    /// In practice vertical scrolling will be width limited to
+    /// box constraints and horizontal will be height limited.
+    pub fn max_size(&self, bc: &BoxConstraints) -> Size {
+        match self {
+            ScrollDirection::Horizontal => Size::new(INFINITY, bc.max().height),
+            ScrollDirection::Vertical => Size::new(bc.max().width, INFINITY),
+            ScrollDirection::All => Size::new(INFINITY, INFINITY),
+        }
+    }
+}
+
+enum BarHoveredState {
+    None,
+    Vertical,
+    Horizontal,
+}
+
+impl BarHoveredState {
+    fn is_hovered(&self) -> bool {
+        matches!(
+            self,
+            BarHoveredState::Vertical | BarHoveredState::Horizontal
+        )
+    }
+}
+
+enum BarHeldState {
+    None,
+    /// Vertical scrollbar is being dragged. Contains an `f64` with
+    /// the initial y-offset of the dragging input
+    Vertical(f64),
+    /// Horizontal scrollbar is being dragged. Contains an `f64` with
+    /// the initial x-offset of the dragging input
+    Horizontal(f64),
+}
+
+struct ScrollbarsState {
+    opacity: f64,
+    timer_id: TimerToken,
+    hovered: BarHoveredState,
+    held: BarHeldState,
+}
+
+impl Default for ScrollbarsState {
+    fn default() -> Self {
+        Self {
+            opacity: 0.0,
+            timer_id: TimerToken::INVALID,
+            hovered: BarHoveredState::None,
+            held: BarHeldState::None,
+        }
+    }
+}
+
+impl ScrollbarsState {
+    /// true if either scrollbar is currently held down/being dragged
+    fn are_held(&self) -> bool {
+        !matches!(self.held, BarHeldState::None)
+    }
+}
+*/ ////
+
+/// The touch currently held on the scroll container.
+#[derive(Clone, Copy, Debug, PartialEq)] ////
+enum Touch {
+    /// No touch is held.
+    None,
+    /// A touch is held but has not moved beyond `TOUCH_SLOP` yet; it may still be a tap
+    /// on the child. Contains the y position where the touch started.
+    Pressed(ScreenCoord),
+    /// The touch is dragging the content. Contains the last y position of the touch.
+    Dragging(ScreenCoord),
+}
+
+/// Deterministic scroll physics along one axis.
+///
+/// `position` is the scroll offset of the content, which goes below zero or beyond
+/// `max` while the content is pulled past an edge.
+#[derive(Clone, Debug, Default)] ////
+struct Kinetic {
+    position: ScreenFactor,
+    /// Pixels per second, positive towards the end of the content.
+    velocity: ScreenFactor,
+    /// Offset that `scroll_to` is animating towards.
+    target: Option<ScreenFactor>,
+    dragging: bool,
+    /// Distance dragged since the last animation frame.
+    drag_delta: ScreenFactor,
+    /// Time not yet consumed by a physics step.
+    pending_ns: u64,
+}
+
+impl Kinetic {
+    /// Grab the content with a touch, stopping any motion.
+    fn grab(&mut self) {
+        self.dragging = true;
+        self.velocity = 0.0;
+        self.target = None;
+        self.drag_delta = 0.0;
+        self.pending_ns = 0;
+    }
+
+    /// Move the content by `delta` under the finger. Past the edges the content
+    /// follows the finger less and less, up to `MAX_OVERSCROLL`.
+    fn drag_by(&mut self, delta: ScreenFactor, max: ScreenFactor) {
+        let overscroll = overshoot(self.position, max);
+        let delta = if overscroll * delta > 0.0 {
+            delta * (1.0 - abs(overscroll) / MAX_OVERSCROLL).max(0.0)
+        } else {
+            delta
+        };
+        self.position = (self.position + delta)
+            .max(-MAX_OVERSCROLL)
+            .min(max + MAX_OVERSCROLL);
+        self.drag_delta += delta;
+    }
+
+    /// Let go of the content, which keeps moving with the velocity of the drag.
+    fn release(&mut self) {
+        self.dragging = false;
+        self.drag_delta = 0.0;
+    }
+
+    /// Animate towards `position`, which must lie between 0 and `max`.
+    fn animate_to(&mut self, position: ScreenFactor) {
+        self.target = Some(position);
+    }
+
+    /// Advance the animation by `interval_ns` nanoseconds.
+    ///
+    /// Returns `true` if the content is still moving.
+    fn advance(&mut self, interval_ns: u64, max: ScreenFactor) -> bool {
+        if self.dragging {
+            // measure the velocity of the finger, to be used for the fling
+            if interval_ns > 0 {
+                let measured = self.drag_delta / (interval_ns as ScreenFactor * 1e-9);
+                self.velocity = (0.8 * measured + 0.2 * self.velocity).clamp(-MAX_VELOCITY, MAX_VELOCITY);
+                self.drag_delta = 0.0;
+            }
+            return true;
+        }
+        self.pending_ns += interval_ns;
+        while self.pending_ns >= STEP_NS && self.is_moving(max) {
+            self.pending_ns -= STEP_NS;
+            self.step(max);
+        }
+        if !self.is_moving(max) {
+            self.pending_ns = 0;
+        }
+        self.is_moving(max)
+    }
+
+    /// One physics step of `STEP_SECS`.
+    fn step(&mut self, max: ScreenFactor) {
+        let overscroll = overshoot(self.position, max);
+        let accel = if let Some(target) = self.target {
+            STIFFNESS * (target - self.position) - DAMPING * self.velocity
+        } else if overscroll != 0.0 {
+            -STIFFNESS * overscroll - DAMPING * self.velocity
+        } else {
+            self.velocity *= 1.0 - DECELERATION * STEP_SECS;
+            0.0
+        };
+        self.velocity += accel * STEP_SECS;
+        self.position += self.velocity * STEP_SECS;
+
+        // a fast fling stops dead at the end of the overscroll
+        if self.position < -MAX_OVERSCROLL || self.position > max + MAX_OVERSCROLL {
+            self.position = self.position.clamp(-MAX_OVERSCROLL, max + MAX_OVERSCROLL);
+            self.velocity = 0.0;
+        }
+
+        // come to rest
+        let slow = abs(self.velocity) < MIN_VELOCITY;
+        if let Some(target) = self.target {
+            if slow && abs(target - self.position) < 0.5 {
+                self.position = target;
+                self.velocity = 0.0;
+                self.target = None;
+            }
+        } else {
+            let overscroll = overshoot(self.position, max);
+            if overscroll == 0.0 {
+                if slow {
+                    self.velocity = 0.0;
+                }
+            } else if slow && abs(overscroll) < 0.5 {
+                self.position -= overscroll;
+                self.velocity = 0.0;
+            }
+        }
+    }
+
+    /// Returns `true` if the content is moving or will move by itself.
+    fn is_moving(&self, max: ScreenFactor) -> bool {
+        self.dragging
+            || self.velocity != 0.0
+            || self.target.is_some()
+            || overshoot(self.position, max) != 0.0
+    }
+}
+
+/// How far `position` is beyond the edges of `0..=max`, negative above the top.
+fn overshoot(position: ScreenFactor, max: ScreenFactor) -> ScreenFactor {
+    if position < 0.0 {
+        position
+    } else if position > max {
+        position - max
+    } else {
+        0.0
+    }
+}
+
+/// Absolute value, which is not in `core` for floats.
+fn abs(x: ScreenFactor) -> ScreenFactor {
+    if x < 0.0 { -x } else { x }
+}
+
+/// A container that scrolls its contents.
+///
+/// This container holds a single child, and scrolls it vertically when the child
+/// is taller than the viewport. The content is dragged with a touch, keeps moving
+/// with momentum when let go, and bounces back when pulled past an edge.
+///
+/// The child is laid out with the width of the container and unbounded height.
+/// Descendants outside the viewport are not painted.
+///
+/// As layout coordinates are bytes, the child is at most 255 pixels tall, so
+/// on the 240 pixel screen the content scrolls by at most 15 pixels.
+#[derive(Clone)] ////
+pub struct Scroll<T> { ////
+////pub struct Scroll<T, W> {
+    id: WidgetId, ////
+    child: WidgetPod<T, BoxedWidget<T>>, ////
+    ////child: WidgetPod<T, W>,
+    child_size: Size,
+    /// Size of the viewport, as of the last layout.
+    size: Size, ////
+    kinetic: Kinetic, ////
+    touch: Touch, ////
+    ////scroll_offset: Vec2,
+    ////direction: ScrollDirection,
+    ////scrollbars: ScrollbarsState,
+}
+
+impl<T: Data> Scroll<T> { ////
+////impl<T, W: Widget<T>> Scroll<T, W> {
+    /// Create a new scroll container.
+    ///
+    /// The container scrolls vertically if the child's height is larger than the viewport.
+    pub fn new(child: impl Widget<T> + 'static + Clone) -> Scroll<T> { ////
+    ////pub fn new(child: W) -> Scroll<T, W> {
+        Scroll {
+            id: WidgetId::next(), ////
+            child: WidgetPod::new(BoxedWidget::new(child)), ////
+            ////child: WidgetPod::new(child),
+            child_size: Default::default(),
+            size: Default::default(), ////
+            kinetic: Default::default(), ////
+            touch: Touch::None, ////
+            ////scroll_offset: Vec2::new(0.0, 0.0),
+            ////direction: ScrollDirection::All,
+            ////scrollbars: ScrollbarsState::default(),
+        }
+    }
+
+    /* ////
+    /// Limit scroll behavior to allow only vertical scrolling (Y-axis).
+    /// The child is laid out with constrained width and infinite height.
+    pub fn vertical(mut self) -> Self {
+        self.direction = ScrollDirection::Vertical;
+        self
+    }
+
+    /// Limit scroll behavior to allow only horizontal scrolling (X-axis).
+    /// The child is laid out with constrained height and infinite width.
+    pub fn horizontal(mut self) -> Self {
+        self.direction = ScrollDirection::Horizontal;
+        self
+    }
+
+    /// Returns a reference to the child widget.
+    pub fn child(&self) -> &W {
+        self.child.widget()
+    }
+
+    /// Returns a mutable reference to the child widget.
+    pub fn child_mut(&mut self) -> &mut W {
+        self.child.widget_mut()
+    }
+    */ ////
+
+    /// Returns the size of the child widget.
+    pub fn child_size(&self) -> Size {
+        self.child_size
+    }
+
+    /// Update the scroll.
+    ///
+    /// Returns `true` if the scroll has been updated.
+    pub fn scroll(&mut self, delta: Vec2, size: Size) -> bool { ////
+        let old = self.offset();
+        self.size = size;
+        let max = self.max_offset();
+        self.kinetic.position = (self.kinetic.position + delta.y as ScreenFactor).clamp(0.0, max);
+        self.kinetic.velocity = 0.0;
+        self.kinetic.target = None;
+        self.child.set_viewport_offset(self.offset());
+        self.offset() != old
+        ////let mut offset = self.scroll_offset + delta;
+        ////offset.x = offset.x.min(self.child_size.width - size.width).max(0.0);
+        ////offset.y = offset.y.min(self.child_size.height - size.height).max(0.0);
+        ////if (offset - self.scroll_offset).hypot2() > 1e-12 {
+            ////self.scroll_offset = offset;
+            ////self.child.set_viewport_offset(offset);
+            ////true
+        ////} else {
+            ////false
+        ////}
+    }
+
+    /// Animate the content so that `region` of the child is visible, moving
+    /// as little as possible.
+    ///
+    /// `region` is in the child's coordinates, for example the layout rect of
+    /// an item of a [`List`]. Returns `true` if the content needs to move; the
+    /// caller should then request an animation frame.
+    ///
+    /// [`List`]: struct.List.html
+    pub fn scroll_to(&mut self, region: Rect) -> bool { ////
+        let top = region.y0 as ScreenFactor;
+        let bottom = region.y1 as ScreenFactor - self.size.height as ScreenFactor;
+        let current = self.kinetic.target.unwrap_or(self.kinetic.position);
+        let target = if current > top {
+            top
+        } else if current < bottom {
+            bottom.min(top)
+        } else {
+            return false;
+        };
+        self.kinetic.animate_to(target.clamp(0.0, self.max_offset()));
+        true
+    }
+
+    /// Returns the current scroll offset.
+    ///
+    /// While the content is pulled past an edge the offset stays at that edge,
+    /// though the content is painted where it's pulled.
+    pub fn offset(&self) -> Vec2 {
+        let y = self.kinetic.position.clamp(0.0, self.max_offset());
+        Vec2::new(0, (y + 0.5) as ScreenCoord) ////
+        ////self.scroll_offset
+    }
+
+    /// Returns the precise scroll position, which is below zero or beyond the
+    /// end of the content while the content is pulled past an edge.
+    pub fn position(&self) -> ScreenFactor { ////
+        self.kinetic.position
+    }
+
+    /// Returns `true` while the content is being dragged or is still moving.
+    pub fn is_scrolling(&self) -> bool { ////
+        self.kinetic.is_moving(self.max_offset())
+    }
+
+    /// How far the content is pulled past an edge, in whole pixels, negative
+    /// when it's pulled down past the top.
+    fn overscroll(&self) -> i16 { ////
+        let overscroll = overshoot(self.kinetic.position, self.max_offset());
+        if overscroll < 0.0 { (overscroll - 0.5) as i16 } else { (overscroll + 0.5) as i16 }
+    }
+
+    /// The largest scroll offset, which shows the end of the content.
+    fn max_offset(&self) -> ScreenFactor { ////
+        self.child_size.height.saturating_sub(self.size.height) as ScreenFactor
+    }
+
+    /// Starts a drag: the child loses the touch, so that a button under the
+    /// finger is not clicked when the touch is lifted.
+    fn begin_drag(&mut self, ctx: &mut EventCtx, data: &mut T, env: &Env) { ////
+        self.kinetic.grab();
+        let leave = Event::Internal(InternalEvent::MouseLeave);
+        self.child.event(ctx, &leave, data, env);
+        if self.child.has_active() {
+            let far = Point::new(ScreenCoord::MAX, ScreenCoord::MAX);
+            self.child.event(ctx, &Event::MouseUp(MouseEvent::new(far)), data, env);
+        }
+    }
+
+    /* ////
+    /// Makes the scrollbars visible, and resets the fade timer.
+    pub fn reset_scrollbar_fade<F>(&mut self, request_timer: F, env: &Env)
+    where
+        F: FnOnce(Duration) -> TimerToken,
+    {
+        // Display scroll bars and schedule their disappearance
+        self.scrollbars.opacity = env.get(theme::SCROLLBAR_MAX_OPACITY);
+        let fade_delay = env.get(theme::SCROLLBAR_FADE_DELAY);
+        let deadline = Duration::from_millis(fade_delay);
+        self.scrollbars.timer_id = request_timer(deadline);
+    }
+
+    fn calc_vertical_bar_bounds(&self, viewport: Rect, env: &Env) -> Rect {
+        let bar_width = env.get(theme::SCROLLBAR_WIDTH);
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
+
+        let percent_visible = viewport.height() / self.child_size.height;
+        let percent_scrolled = self.scroll_offset.y / (self.child_size.height - viewport.height());
+
+        let length = (percent_visible * viewport.height()).ceil();
+        let length = length.max(SCROLLBAR_MIN_SIZE);
+
+        let vertical_padding = bar_pad + bar_pad + bar_width;
+
+        let top_y_offset =
+            ((viewport.height() - length - vertical_padding) * percent_scrolled).ceil();
+        let bottom_y_offset = top_y_offset + length;
+
+        let x0 = self.scroll_offset.x + viewport.width() - bar_width - bar_pad;
+        let y0 = self.scroll_offset.y + top_y_offset + bar_pad;
+
+        let x1 = self.scroll_offset.x + viewport.width() - bar_pad;
+        let y1 = self.scroll_offset.y + bottom_y_offset;
+
+        Rect::new(x0, y0, x1, y1)
+    }
+
+    fn calc_horizontal_bar_bounds(&self, viewport: Rect, env: &Env) -> Rect {
+        let bar_width = env.get(theme::SCROLLBAR_WIDTH);
+        let bar_pad = env.get(theme::SCROLLBAR_PAD);
+
+        let percent_visible = viewport.width() / self.child_size.width;
+        let percent_scrolled = self.scroll_offset.x / (self.child_size.width - viewport.width());
+
+        let length = (percent_visible * viewport.width()).ceil();
+        let length = length.max(SCROLLBAR_MIN_SIZE);
+
+        let horizontal_padding = bar_pad + bar_pad + bar_width;
+
+        let left_x_offset =
+            ((viewport.width() - length - horizontal_padding) * percent_scrolled).ceil();
+        let right_x_offset = left_x_offset + length;
+
+        let x0 = self.scroll_offset.x + left_x_offset + bar_pad;
+        let y0 = self.scroll_offset.y + viewport.height() - bar_width - bar_pad;
+
+        let x1 = self.scroll_offset.x + right_x_offset;
+        let y1 = self.scroll_offset.y + viewport.height() - bar_pad;
+
+        Rect::new(x0, y0, x1, y1)
+    }
+
+    /// Draw scroll bars.
+    fn draw_bars(&self, ctx: &mut PaintCtx, viewport: Rect, env: &Env) {
+        if self.scrollbars.opacity <= 0.0 {
+            return;
+        }
+
+        let brush = ctx.render_ctx.solid_brush(
+            env.get(theme::SCROLLBAR_COLOR)
+                .with_alpha(self.scrollbars.opacity),
+        );
+        let border_brush = ctx.render_ctx.solid_brush(
+            env.get(theme::SCROLLBAR_BORDER_COLOR)
+                .with_alpha(self.scrollbars.opacity),
+        );
+
+        let radius = env.get(theme::SCROLLBAR_RADIUS);
+        let edge_width = env.get(theme::SCROLLBAR_EDGE_WIDTH);
+
+        // Vertical bar
+        if viewport.height() < self.child_size.height {
+            let bounds = self
+                .calc_vertical_bar_bounds(viewport, env)
+                .inset(-edge_width / 2.0);
+            let rect = RoundedRect::from_rect(bounds, radius);
+            ctx.render_ctx.fill(rect, &brush);
+            ctx.render_ctx.stroke(rect, &border_brush, edge_width);
+        }
+
+        // Horizontal bar
+        if viewport.width() < self.child_size.width {
+            let bounds = self
+                .calc_horizontal_bar_bounds(viewport, env)
+                .inset(-edge_width / 2.0);
+            let rect = RoundedRect::from_rect(bounds, radius);
+            ctx.render_ctx.fill(rect, &brush);
+            ctx.render_ctx.stroke(rect, &border_brush, edge_width);
+        }
+    }
+
+    fn point_hits_vertical_bar(&self, viewport: Rect, pos: Point, env: &Env) -> bool {
+        if viewport.height() < self.child_size.height {
+            // Stretch hitbox to edge of widget
+            let mut bounds = self.calc_vertical_bar_bounds(viewport, env);
+            bounds.x1 = self.scroll_offset.x + viewport.width();
+            bounds.contains(pos)
+        } else {
+            false
+        }
+    }
+
+    fn point_hits_horizontal_bar(&self, viewport: Rect, pos: Point, env: &Env) -> bool {
+        if viewport.width() < self.child_size.width {
+            // Stretch hitbox to edge of widget
+            let mut bounds = self.calc_horizontal_bar_bounds(viewport, env);
+            bounds.y1 = self.scroll_offset.y + viewport.height();
+            bounds.contains(pos)
+        } else {
+            false
+        }
+    }
+    */ ////
+}
+
+impl<T: Data> Widget<T> for Scroll<T> { ////
+////impl<T: Data, W: Widget<T>> Widget<T> for Scroll<T, W> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let size = ctx.size();
+        let viewport = Rect::from_origin_size(Point::ORIGIN, size);
+        let max = self.max_offset(); ////
+
+        // touches that drag the content are not passed to the child
+        let dragged = match (event, self.touch) { ////
+            (Event::MouseDown(mouse), _) if viewport.winding(mouse.pos) != 0 => {
+                self.touch = Touch::Pressed(mouse.pos.y);
+                self.kinetic.velocity = 0.0;
+                self.kinetic.target = None;
+                ctx.set_active(true);
+                false
+            }
+            (Event::MouseMove(mouse), Touch::Pressed(start))
+                if (mouse.pos.y as i16 - start as i16).abs() > TOUCH_SLOP as i16 =>
+            {
+                self.begin_drag(ctx, data, env);
+                self.kinetic.drag_by(start as ScreenFactor - mouse.pos.y as ScreenFactor, max);
+                self.touch = Touch::Dragging(mouse.pos.y);
+                ctx.request_anim_frame();
+                true
+            }
+            (Event::MouseMove(mouse), Touch::Dragging(last)) => {
+                self.kinetic.drag_by(last as ScreenFactor - mouse.pos.y as ScreenFactor, max);
+                self.touch = Touch::Dragging(mouse.pos.y);
+                ctx.request_anim_frame();
+                true
+            }
+            (Event::MouseUp(_), Touch::Pressed(_)) => {
+                self.touch = Touch::None;
+                ctx.set_active(false);
+                false
+            }
+            (Event::MouseUp(_), Touch::Dragging(_)) => {
+                // let go of the content, which keeps moving in the animation frames
+                self.touch = Touch::None;
+                self.kinetic.release();
+                ctx.set_active(false);
+                ctx.request_anim_frame();
+                true
+            }
+            _ => false,
+        };
+        if dragged { ////
+            self.child.set_viewport_offset(self.offset());
+            ctx.set_handled();
+            return;
+        }
+        if let Event::Command(cmd) = event { ////
+            if let Some(region) = cmd.get(SCROLL_TO) {
+                if self.scroll_to(*region) {
+                    ctx.request_anim_frame();
+                }
+                ctx.set_handled();
+                return;
+            }
+        }
+
+        let force_event = self.child.is_hot() || self.child.is_active();
+        let child_event = event.transform_scroll(self.offset(), viewport, force_event); ////
+        ////let child_event = event.transform_scroll(self.scroll_offset, viewport, force_event);
+        if let Some(child_event) = child_event {
+            self.child.event(ctx, &child_event, data, env);
+        };
+        /* ////
+        let scrollbar_is_hovered = match event {
+            Event::MouseMove(e) | Event::MouseUp(e) | Event::MouseDown(e) => {
+                let offset_pos = e.pos + self.scroll_offset;
+                self.point_hits_vertical_bar(viewport, offset_pos, env)
+                    || self.point_hits_horizontal_bar(viewport, offset_pos, env)
+            }
+            _ => false,
+        };
+
+        if self.scrollbars.are_held() {
+            // if we're dragging a scrollbar
+            match event {
+                Event::MouseMove(event) => {
+                    match self.scrollbars.held {
+                        BarHeldState::Vertical(offset) => {
+                            let scale_y = viewport.height() / self.child_size.height;
+                            let bounds = self.calc_vertical_bar_bounds(viewport, env);
+                            let mouse_y = event.pos.y + self.scroll_offset.y;
+                            let delta = mouse_y - bounds.y0 - offset;
+                            self.scroll(Vec2::new(0f64, (delta / scale_y).ceil()), size);
+                        }
+                        BarHeldState::Horizontal(offset) => {
+                            let scale_x = viewport.width() / self.child_size.width;
+                            let bounds = self.calc_horizontal_bar_bounds(viewport, env);
+                            let mouse_x = event.pos.x + self.scroll_offset.x;
+                            let delta = mouse_x - bounds.x0 - offset;
+                            self.scroll(Vec2::new((delta / scale_x).ceil(), 0f64), size);
+                        }
+                        _ => (),
+                    }
+                    ctx.request_paint();
+                }
+                Event::MouseUp(_) => {
+                    self.scrollbars.held = BarHeldState::None;
+                    ctx.set_active(false);
+
+                    if !scrollbar_is_hovered {
+                        self.scrollbars.hovered = BarHoveredState::None;
+                        self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                    }
+                }
+                _ => (), // other events are a noop
+            }
+        } else if scrollbar_is_hovered {
+            // if we're over a scrollbar but not dragging
+            match event {
+                Event::MouseMove(event) => {
+                    let offset_pos = event.pos + self.scroll_offset;
+                    if self.point_hits_vertical_bar(viewport, offset_pos, env) {
+                        self.scrollbars.hovered = BarHoveredState::Vertical;
+                    } else {
+                        self.scrollbars.hovered = BarHoveredState::Horizontal;
+                    }
+
+                    self.scrollbars.opacity = env.get(theme::SCROLLBAR_MAX_OPACITY);
+                    self.scrollbars.timer_id = TimerToken::INVALID; // Cancel any fade out in progress
+                    ctx.request_paint();
+                }
+                Event::MouseDown(event) => {
+                    let pos = event.pos + self.scroll_offset;
+
+                    if self.point_hits_vertical_bar(viewport, pos, env) {
+                        ctx.set_active(true);
+                        self.scrollbars.held = BarHeldState::Vertical(
+                            pos.y - self.calc_vertical_bar_bounds(viewport, env).y0,
+                        );
+                    } else if self.point_hits_horizontal_bar(viewport, pos, env) {
+                        ctx.set_active(true);
+                        self.scrollbars.held = BarHeldState::Horizontal(
+                            pos.x - self.calc_horizontal_bar_bounds(viewport, env).x0,
+                        );
+                    }
+                }
+                // if the mouse was downed elsewhere, moved over a scroll bar and released: noop.
+                Event::MouseUp(_) => (),
+                _ => unreachable!(),
+            }
+        } else {
+            let force_event = self.child.is_hot() || self.child.is_active();
+            let child_event = event.transform_scroll(self.scroll_offset, viewport, force_event);
+            if let Some(child_event) = child_event {
+                self.child.event(ctx, &child_event, data, env);
+            };
+
+            match event {
+                Event::MouseMove(_) => {
+                    // if we have just stopped hovering
+                    if self.scrollbars.hovered.is_hovered() && !scrollbar_is_hovered {
+                        self.scrollbars.hovered = BarHoveredState::None;
+                        self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                    }
+                }
+                Event::Timer(id) if *id == self.scrollbars.timer_id => {
+                    // Schedule scroll bars animation
+                    ctx.request_anim_frame();
+                    self.scrollbars.timer_id = TimerToken::INVALID;
+                }
+                _ => (),
+            }
+        }
+
+        if !ctx.is_handled() {
+            if let Event::Wheel(mouse) = event {
+                if self.scroll(mouse.wheel_delta, size) {
+                    ctx.request_paint();
+                    ctx.set_handled();
+                    self.reset_scrollbar_fade(|d| ctx.request_timer(d), env);
+                }
+            }
+        }
+        */ ////
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::AnimFrame(interval) = event { ////
+            // Drag, fling and bounce are all stepped here, at a fixed rate
+            if self.kinetic.advance(*interval, self.max_offset()) {
+                ctx.request_anim_frame();
+            }
+            self.child.set_viewport_offset(self.offset());
+            ctx.request_paint();
+        }
+        /* ////
+        match event {
+            LifeCycle::AnimFrame(interval) => {
+                // Guard by the timer id being invalid, otherwise the scroll bars would fade
+                // immediately if some other widgeet started animating.
+                if self.scrollbars.timer_id == TimerToken::INVALID {
+                    // Animate scroll bars opacity
+                    let diff = 2.0 * (*interval as f64) * 1e-9;
+                    self.scrollbars.opacity -= diff;
+                    if self.scrollbars.opacity > 0.0 {
+                        ctx.request_anim_frame();
+                    }
+                }
+            }
+            // Show the scrollbars any time our size changes
+            LifeCycle::Size(_) => self.reset_scrollbar_fade(|d| ctx.request_timer(d), &env),
+            _ => (),
+        }
+        */ ////
+        self.child.lifecycle(ctx, event, data, env)
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("Scroll");
+
+        let child_bc = BoxConstraints::new(Size::ZERO, Size::new(bc.max().width, ScreenCoord::MAX)); ////
+        ////let child_bc = BoxConstraints::new(Size::ZERO, self.direction.max_size(bc));
+        let size = self.child.layout(ctx, &child_bc, data, env);
+        log_size_warnings(size);
+
+        self.child_size = size;
+        self.child.set_layout_rect(ctx, data, env, size.to_rect());
+        let self_size = bc.constrain(self.child_size);
+        self.size = self_size; ////
+        if !self.kinetic.is_moving(self.max_offset()) { ////
+            // the content may have shrunk below the current offset
+            let _ = self.scroll(Vec2::ZERO, self_size);
+        }
+        self.child.set_viewport_offset(self.offset()); ////
+        ////let _ = self.scroll(Vec2::new(0.0, 0.0), self_size);
+        self_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let page = lv_page_create(lv_scr_act(), NULL);          //  Add a page to the current screen
+        lv_obj_set_size(page, 240, 240);                        //  Clip the children to the viewport
+        lv_page_set_scrl_layout(page, LV_LAYOUT_COLUMN_MID);    //  Children are stacked vertically
+        lv_obj_set_y(lv_page_get_scrl(page), -position);        //  Scroll the content, including the overscroll
+        */
+        let viewport = ctx.size().to_rect();
+        let offset = self.offset(); ////
+        // Past an edge the content is painted where it's pulled, and bounces back ////
+        let overscroll = self.overscroll(); ////
+        let pulled_down = Vec2::new(0, (-overscroll).max(0) as ScreenCoord); ////
+        let pushed_up = Vec2::new(0, overscroll.max(0) as ScreenCoord); ////
+        let child = &mut self.child; ////
+        ctx.with_save(|ctx| {
+            ctx.clip(viewport);
+            ctx.render_ctx.translate_back(offset); ////
+            ctx.render_ctx.translate(pulled_down); ////
+            ctx.render_ctx.translate_back(pushed_up); ////
+            ////ctx.transform(Affine::translate(-self.scroll_offset));
+
+            let visible = ctx.region().to_rect() - pulled_down + offset; ////
+            let visible = Rect::new(visible.x0, visible.y0.saturating_add(pushed_up.y), visible.x1, visible.y1.saturating_add(pushed_up.y)); ////
+            ////let visible = ctx.region().to_rect() + self.scroll_offset;
+            ctx.with_child_ctx(visible, |ctx| child.paint_raw(ctx, data, env)); ////
+            ////ctx.with_child_ctx(visible, |ctx| self.child.paint_raw(ctx, data, env));
+
+            ////self.draw_bars(ctx, viewport, env);
+        });
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Scroll(self)
+    }
+}
+
+fn log_size_warnings(size: Size) {
+    if size.width == ScreenCoord::MAX { ////
+    ////if size.width.is_infinite() {
+        log::warn!("Scroll widget's child has an infinite width.");
+    }
+
+    if size.height == ScreenCoord::MAX { ////
+    ////if size.height.is_infinite() {
+        log::warn!("Scroll widget's child may be taller than the 255 pixels that can be laid out."); ////
+        ////log::warn!("Scroll widget's child has an infinite height.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::harness::Harness;
+    use crate::widget::Painter;
+    use crate::{Color, Target};
+    use ::core::cell::Cell;
+
+    const FRAME_NS: u64 = 16_000_000;
+
+    /// Rows painted white, in window coordinates.
+    static mut PAINTED: Option<Rect> = None;
+
+    fn draw(rect: Rect, color: Color) {
+        if color == Color::WHITE {
+            unsafe { PAINTED = Some(PAINTED.map_or(rect, |painted| painted.union(rect))); }
+        }
+    }
+
+    /// A scroll over content that is taller than the window, with a white bar at rows 20..30.
+    fn scroll(content: &Cell<Option<WidgetId>>) -> Scroll<()> {
+        let painter = Painter::new(|ctx, _, _| ctx.fill(Rect::new(0, 20, 240, 30), &Color::WHITE));
+        content.set(painter.id());
+        Scroll::new(painter)
+    }
+
+    fn touch(pos: (ScreenCoord, ScreenCoord)) -> MouseEvent {
+        MouseEvent::new(Point::new(pos.0, pos.1))
+    }
+
+    #[test]
+    fn taps_pass_through_to_the_child() {
+        let content = Cell::new(None);
+        Harness::create_simple((), || scroll(&content), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let content = content.get().unwrap();
+
+            harness.event(Event::MouseDown(touch((100, 100))));
+            harness.event(Event::MouseMove(touch((100, 102))));
+            harness.event(Event::MouseUp(touch((100, 102))));
+            assert!(harness.get_state(content).is_hot);
+            assert_eq!(harness.window().root.widget().offset(), Vec2::ZERO);
+        });
+    }
+
+    #[test]
+    fn drags_are_captured() {
+        let content = Cell::new(None);
+        Harness::create_simple((), || scroll(&content), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let content = content.get().unwrap();
+
+            harness.event(Event::MouseDown(touch((100, 100))));
+            assert!(harness.get_state(content).is_hot);
+            harness.event(Event::MouseMove(touch((100, 90))));
+            // the child loses the touch once it becomes a drag
+            assert!(!harness.get_state(content).is_hot);
+            harness.anim_frame(FRAME_NS);
+            assert_eq!(harness.window().root.widget().offset(), Vec2::new(0, 10));
+
+            harness.event(Event::MouseUp(touch((100, 90))));
+            assert!(!harness.get_state(content).is_hot);
+        });
+    }
+
+    #[test]
+    fn scroll_to_moves_painted_content() {
+        let content = Cell::new(None);
+        let size = Size::new(240, 240);
+        Harness::create_with_render((), || scroll(&content), size, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            let scroll = harness.window().root.id();
+
+            // the bottom of the content, past the 240 rows of the window
+            let bottom = Rect::new(0, 250, 240, 255);
+            harness.submit_command(SCROLL_TO.with(bottom), Target::Widget(scroll));
+            for _ in 0..100 {
+                harness.anim_frame(FRAME_NS);
+            }
+            assert_eq!(harness.window().root.widget().offset(), Vec2::new(0, 15));
+
+            unsafe { PAINTED = None; }
+            harness.paint();
+            assert_eq!(unsafe { PAINTED }, Some(Rect::new(0, 5, 240, 15)));
+        }, Some(draw));
+    }
+
+    #[test]
+    fn overscroll_is_painted_and_bounces_back() {
+        let content = Cell::new(None);
+        let size = Size::new(240, 240);
+        Harness::create_with_render((), || scroll(&content), size, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+
+            // pull the content down past the top
+            harness.event(Event::MouseDown(touch((100, 100))));
+            harness.event(Event::MouseMove(touch((100, 110))));
+            assert_eq!(harness.window().root.widget().offset(), Vec2::ZERO);
+            unsafe { PAINTED = None; }
+            harness.paint();
+            assert_eq!(unsafe { PAINTED }, Some(Rect::new(0, 30, 240, 40)));
+
+            harness.event(Event::MouseUp(touch((100, 110))));
+            for _ in 0..100 {
+                harness.anim_frame(FRAME_NS);
+            }
+            unsafe { PAINTED = None; }
+            harness.paint();
+            assert_eq!(unsafe { PAINTED }, Some(Rect::new(0, 20, 240, 30)));
+        }, Some(draw));
+    }
+
+    /// Run animation frames until the content comes to rest, returning the number of frames.
+    fn settle(kinetic: &mut Kinetic, max: ScreenFactor) -> usize {
+        let mut frames = 0;
+        while kinetic.advance(FRAME_NS, max) {
+            frames += 1;
+            assert!(frames < 1000, "scroll never came to rest");
+        }
+        frames
+    }
+
+    /// Drag by `delta` pixels over `frames` animation frames.
+    fn drag(kinetic: &mut Kinetic, delta: ScreenFactor, frames: usize, max: ScreenFactor) {
+        kinetic.grab();
+        kinetic.advance(0, max);
+        for _ in 0..frames {
+            kinetic.drag_by(delta / frames as ScreenFactor, max);
+            kinetic.advance(FRAME_NS, max);
+        }
+    }
+
+    #[test]
+    fn drag_follows_finger() {
+        let mut kinetic = Kinetic::default();
+        kinetic.grab();
+        kinetic.drag_by(30.0, 100.0);
+        kinetic.drag_by(-10.0, 100.0);
+        assert_eq!(kinetic.position, 20.0);
+    }
+
+    #[test]
+    fn overscroll_is_resisted_and_bounded() {
+        let mut kinetic = Kinetic::default();
+        kinetic.grab();
+        kinetic.drag_by(-20.0, 100.0);
+        assert_eq!(kinetic.position, -20.0);
+        kinetic.drag_by(-20.0, 100.0);
+        assert!(kinetic.position > -40.0 && kinetic.position < -20.0);
+        for _ in 0..100 {
+            kinetic.drag_by(-20.0, 100.0);
+        }
+        assert!(kinetic.position >= -MAX_OVERSCROLL);
+    }
+
+    #[test]
+    fn fling_decelerates_and_stops() {
+        let mut kinetic = Kinetic::default();
+        drag(&mut kinetic, 20.0, 2, 200.0);
+        kinetic.release();
+        assert!(kinetic.velocity > 0.0);
+
+        let mut last = kinetic.position;
+        while kinetic.advance(FRAME_NS, 200.0) {
+            assert!(kinetic.position >= last);
+            last = kinetic.position;
+        }
+        assert!(kinetic.position > 20.0 && kinetic.position <= 200.0);
+        assert_eq!(kinetic.velocity, 0.0);
+    }
+
+    #[test]
+    fn bounces_back_to_edge() {
+        let mut kinetic = Kinetic::default();
+        drag(&mut kinetic, -30.0, 3, 100.0);
+        kinetic.release();
+        assert!(kinetic.position < 0.0);
+        settle(&mut kinetic, 100.0);
+        assert_eq!(kinetic.position, 0.0);
+
+        // past the end of the content
+        let mut kinetic = Kinetic { position: 100.0, ..Default::default() };
+        drag(&mut kinetic, 30.0, 3, 100.0);
+        kinetic.release();
+        settle(&mut kinetic, 100.0);
+        assert_eq!(kinetic.position, 100.0);
+    }
+
+    #[test]
+    fn animates_to_target() {
+        let mut kinetic = Kinetic::default();
+        kinetic.animate_to(60.0);
+        settle(&mut kinetic, 100.0);
+        assert_eq!(kinetic.position, 60.0);
+        assert!(!kinetic.is_moving(100.0));
+    }
+
+    #[test]
+    fn physics_is_deterministic() {
+        // the same touches give the same motion, however the frames are timed
+        let mut a = Kinetic::default();
+        let mut b = Kinetic::default();
+        drag(&mut a, 40.0, 4, 150.0);
+        drag(&mut b, 40.0, 4, 150.0);
+        a.release();
+        b.release();
+        for _ in 0..20 {
+            a.advance(FRAME_NS, 150.0);
+            b.advance(FRAME_NS / 2, 150.0);
+            b.advance(FRAME_NS / 2, 150.0);
+        }
+        assert_eq!(a.position, b.position);
+        assert_eq!(a.velocity, b.velocity);
+    }
+}
@@ -35,6 +35,7 @@ const INFINITY: ScreenCoord = ScreenCoord::MAX; ////
 /// it will be treated as zero.
 #[derive(Clone)] ////
 pub struct SizedBox<T> {
+    id: WidgetId, //// Own id for its static slot, as the child's id is taken
     inner: Option<BoxedWidget<T>>, ////
     ////inner: Option<Box<dyn Widget<T>>>,
     width: Option<ScreenCoord>, ////
@@ -49,6 +50,7 @@ impl<T: Clone> SizedBox<T> { ////
     pub fn new(inner: impl Widget<T> + 'static + Clone) -> Self { ////
     ////pub fn new(inner: impl Widget<T> + 'static) -> Self {
         Self {
+            id: WidgetId::next(), ////
             inner: Some(BoxedWidget::new(inner)), ////
             ////inner: Some(Box::new(inner)),
             width: None,
@@ -59,6 +61,7 @@ impl<T: Clone> SizedBox<T> { ////
     /// Construct container without child, and both width and height not set.
     pub fn empty() -> Self {
         Self {
+            id: WidgetId::next(), ////
             inner: None,
             width: None,
             height: None,
@@ -190,7 +193,8 @@ impl<T: Data> Widget<T> for SizedBox<T> {
     }
 
     fn id(&self) -> Option<WidgetId> {
-        self.inner.as_ref().and_then(|inner| inner.id())
+        Some(self.id) ////
+        ////self.inner.as_ref().and_then(|inner| inner.id())
     }
 
     fn to_type(self) -> WidgetType<T> { ////
@@ -0,0 +1,369 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A slider widget.
+
+use crate::{Circle, Point, Rect, Size}; ////
+////use crate::kurbo::{Circle, Point, Rect, Shape, Size};
+use crate::theme;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient, ////
+    PaintCtx, UnitPoint, UpdateCtx, Widget, ////
+    ////BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, LinearGradient,
+    ////PaintCtx, RenderContext, UnitPoint, UpdateCtx, Widget,
+};
+use crate::{ScreenCoord, ScreenFactor, WidgetId, WidgetType}; ////
+use crate::widget::common::{round, Numeric}; ////
+
+/// A slider, allowing interactive update of a numeric value.
+///
+/// This slider implements `Widget<T>` for any [`Numeric`] `T`, such as `f32`
+/// or `u8`, and works on values clamped in the range `min..max`. The knob is
+/// dragged with a touch, or jumps to where the track is touched.
+///
+/// [`Numeric`]: trait.Numeric.html
+#[derive(Debug, Clone)] ////
+////#[derive(Debug, Clone, Default)]
+pub struct Slider<T> { ////
+////pub struct Slider {
+    id: WidgetId, ////
+    min: ScreenFactor, ////
+    ////min: f64,
+    max: ScreenFactor, ////
+    ////max: f64,
+    /// Values are snapped to multiples of `step` from `min`, if nonzero.
+    step: ScreenFactor, ////
+    knob_pos: Point,
+    knob_hovered: bool,
+    x_offset: ScreenFactor, ////
+    ////x_offset: f64,
+    /// Reads the value as a `ScreenFactor`.
+    get: fn(&T) -> ScreenFactor, ////
+    /// Writes a `ScreenFactor` to the value.
+    set: fn(&mut T, ScreenFactor), ////
+}
+
+impl<T: Numeric> Slider<T> { ////
+////impl Slider {
+    /// Create a new `Slider`.
+    pub fn new() -> Slider<T> { ////
+    ////pub fn new() -> Slider {
+        Slider {
+            id: WidgetId::next(), ////
+            min: 0.,
+            max: 1.,
+            step: 0., ////
+            knob_pos: Default::default(),
+            knob_hovered: Default::default(),
+            x_offset: Default::default(),
+            get: |data| data.to_factor(), ////
+            set: |data, value| *data = T::from_factor(value), ////
+        }
+    }
+}
+
+impl<T> Slider<T> { ////
+////impl Slider {
+    /// Builder-style method to set the range covered by this slider.
+    ///
+    /// The default range is `0.0..1.0`.
+    pub fn with_range(mut self, min: ScreenFactor, max: ScreenFactor) -> Self { ////
+    ////pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Builder-style method to snap the value to multiples of `step` from `min`.
+    ///
+    /// The default step is `0.0`, which lets the value change continuously.
+    /// Integer values are always rounded to whole numbers.
+    pub fn with_step(mut self, step: ScreenFactor) -> Self { ////
+        self.step = step;
+        self
+    }
+}
+
+impl<T> Slider<T> { ////
+////impl Slider {
+    fn knob_hit_test(&self, knob_width: ScreenCoord, mouse_pos: Point) -> bool { ////
+    ////fn knob_hit_test(&self, knob_width: f64, mouse_pos: Point) -> bool {
+        let dx = mouse_pos.x as i32 - self.knob_pos.x as i32; ////
+        let dy = mouse_pos.y as i32 - self.knob_pos.y as i32; ////
+        let radius = knob_width as i32 / 2; ////
+        dx * dx + dy * dy < radius * radius ////
+        ////let knob_circle = Circle::new(self.knob_pos, knob_width / 2.);
+        ////knob_circle.winding(mouse_pos) > 0
+    }
+
+    fn calculate_value(&self, mouse_x: ScreenCoord, knob_width: ScreenCoord, slider_width: ScreenCoord) -> ScreenFactor { ////
+    ////fn calculate_value(&self, mouse_x: f64, knob_width: f64, slider_width: f64) -> f64 {
+        let track_width = slider_width.saturating_sub(knob_width).max(1) as ScreenFactor; ////
+        let scalar = ((mouse_x as ScreenFactor + self.x_offset - knob_width as ScreenFactor / 2.) / track_width) ////
+        ////let scalar = ((mouse_x + self.x_offset - knob_width / 2.) / (slider_width - knob_width))
+            .clamp(0.0, 1.0); ////
+            ////.max(0.0)
+            ////.min(1.0);
+        self.snap(self.min + scalar * (self.max - self.min)) ////
+        ////self.min + scalar * (self.max - self.min)
+    }
+
+    /// Snap `value` to the nearest step within the range.
+    fn snap(&self, value: ScreenFactor) -> ScreenFactor { ////
+        //  `clamp` panics if the range is reversed
+        if self.step > 0. && self.min <= self.max {
+            let steps = round((value - self.min) / self.step);
+            (self.min + steps * self.step).clamp(self.min, self.max)
+        } else {
+            value
+        }
+    }
+
+    fn normalize(&self, data: ScreenFactor) -> ScreenFactor { ////
+    ////fn normalize(&self, data: f64) -> f64 {
+        if self.max <= self.min { ////
+            return 0.;
+        }
+        (data.clamp(self.min, self.max) - self.min) / (self.max - self.min)
+    }
+}
+
+impl<T: Data> Widget<T> for Slider<T> { ////
+////impl Widget<f64> for Slider {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) { ////
+    ////fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut f64, env: &Env) {
+        let knob_size = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let slider_width = ctx.size().width;
+
+        match event {
+            Event::MouseDown(mouse) => {
+                ctx.set_active(true);
+                if self.knob_hit_test(knob_size, mouse.pos) {
+                    self.x_offset = self.knob_pos.x as ScreenFactor - mouse.pos.x as ScreenFactor ////
+                    ////self.x_offset = self.knob_pos.x - mouse.pos.x
+                } else {
+                    self.x_offset = 0.;
+                    (self.set)(data, self.calculate_value(mouse.pos.x, knob_size, slider_width)); ////
+                    ////*data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                }
+                ctx.request_paint();
+            }
+            Event::MouseUp(mouse) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    (self.set)(data, self.calculate_value(mouse.pos.x, knob_size, slider_width)); ////
+                    ////*data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseMove(mouse) => {
+                if ctx.is_active() {
+                    (self.set)(data, self.calculate_value(mouse.pos.x, knob_size, slider_width)); ////
+                    ////*data = self.calculate_value(mouse.pos.x, knob_size, slider_width);
+                    ctx.request_paint();
+                }
+                if ctx.is_hot() {
+                    let knob_hover = self.knob_hit_test(knob_size, mouse.pos);
+                    if knob_hover != self.knob_hovered {
+                        self.knob_hovered = knob_hover;
+                        ctx.request_paint();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) { ////
+        match event { ////
+            LifeCycle::WidgetAdded => ctx.register_for_focus(), ////
+            LifeCycle::FocusChanged(_) => ctx.request_paint(), ////
+            _ => (), ////
+        } ////
+    } ////
+    ////fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &f64, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &f64, _data: &f64, _env: &Env) {
+        ctx.request_paint();
+    }
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T, ////
+        ////_data: &f64,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Slider");
+        let height = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let height = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let width = theme::WIDE_WIDGET_WIDTH.resolve(env); ////
+        ////let width = env.get(theme::WIDE_WIDGET_WIDTH);
+        bc.constrain((width, height))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &f64, env: &Env) {
+        let clamped = self.normalize((self.get)(data)); ////
+        ////let clamped = self.normalize(*data);
+        let rect = Rect::from_origin_size(Point::ORIGIN, ctx.size());
+        let knob_size = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let knob_size = env.get(theme::BASIC_WIDGET_HEIGHT);
+
+        let knob_position = (rect.width().saturating_sub(knob_size) as ScreenFactor * clamped) as ScreenCoord + knob_size / 2; ////
+        ////let knob_position = (rect.width() - knob_size) * clamped + knob_size / 2.;
+        self.knob_pos = Point::new(knob_position, knob_size / 2);
+
+        //// TODO: Render with LVGL
+        /*
+        let slider = lv_slider_create(lv_scr_act(), NULL);              //  Add a slider to the current screen
+        lv_obj_set_size(slider, rect.width(), knob_size / 4);           //  Track thickness
+        lv_slider_set_range(slider, self.min as i16, self.max as i16);  //  Range of the value
+        lv_slider_set_value(slider, (self.get)(data) as i16, LV_ANIM_OFF);  //  Position of the knob
+        */
+        let track_thickness = 4; ////
+        ////let track_thickness = 4.;
+        let border_width = 2; ////
+        ////let border_width = 2.;
+        let knob_stroke_width = 2; ////
+        ////let knob_stroke_width = 2.;
+
+        //Paint the background
+        let background_width = rect.width().saturating_sub(knob_size); ////
+        ////let background_width = rect.width() - knob_size;
+        let background_origin = Point::new(knob_size / 2, knob_size.saturating_sub(track_thickness) / 2); ////
+        ////let background_origin = Point::new(knob_size / 2., (knob_size - track_thickness) / 2.);
+        let background_size = Size::new(background_width, track_thickness);
+        let background_rect = Rect::from_origin_size(background_origin, background_size)
+            ////.inset(-border_width / 2.)
+            .to_rounded_rect(2); ////
+            ////.to_rounded_rect(2.);
+
+        let background_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::BACKGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::BACKGROUND_LIGHT),
+                theme::BACKGROUND_DARK.resolve(env), ////
+                ////env.get(theme::BACKGROUND_DARK),
+            ),
+        );
+
+        //  Strokes are drawn inside the shape, so the outer half of the border
+        //  is painted over the fill
+        ctx.fill(background_rect, &background_gradient); ////
+        ctx.stroke(background_rect, &theme::BORDER_DARK.resolve(env), border_width / 2); ////
+        ////ctx.stroke(background_rect, &env.get(theme::BORDER_DARK), border_width);
+
+        ////ctx.fill(background_rect, &background_gradient);
+
+        //Get ready to paint the knob
+        let is_active = ctx.is_active();
+        let is_hovered = self.knob_hovered;
+
+        ////let knob_position = (rect.width() - knob_size) * clamped + knob_size / 2.;
+        ////self.knob_pos = Point::new(knob_position, knob_size / 2.);
+        let knob_circle = Circle::new(self.knob_pos, knob_size / 2); ////
+        ////let knob_circle = Circle::new(self.knob_pos, (knob_size - knob_stroke_width) / 2.);
+
+        let normal_knob_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::FOREGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::FOREGROUND_LIGHT),
+                theme::FOREGROUND_DARK.resolve(env), ////
+                ////env.get(theme::FOREGROUND_DARK),
+            ),
+        );
+        let flipped_knob_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::FOREGROUND_DARK.resolve(env), ////
+                ////env.get(theme::FOREGROUND_DARK),
+                theme::FOREGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::FOREGROUND_LIGHT),
+            ),
+        );
+
+        let knob_gradient = if is_active {
+            flipped_knob_gradient
+        } else {
+            normal_knob_gradient
+        };
+
+        //Paint the border
+        let border_color = if is_hovered || is_active {
+            theme::FOREGROUND_LIGHT.resolve(env) ////
+            ////env.get(theme::FOREGROUND_LIGHT)
+        } else {
+            theme::FOREGROUND_DARK.resolve(env) ////
+            ////env.get(theme::FOREGROUND_DARK)
+        };
+
+        ////ctx.stroke(knob_circle, &border_color, knob_stroke_width);
+
+        //Actually paint the knob
+        ctx.fill(knob_circle, &knob_gradient);
+        ctx.stroke(knob_circle, &border_color, knob_stroke_width / 2); ////
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Slider(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::layout_ctx;
+
+    #[test]
+    fn layout() {
+        let env = theme::init();
+        let mut slider = Slider::<f32>::new();
+        let mut ctx = layout_ctx();
+
+        let bc = BoxConstraints::new(Size::ZERO, Size::new(240, 240));
+        let size = slider.layout(&mut ctx, &bc, &0.5, &env);
+        assert_eq!(size, Size::new(200, 36));
+
+        // a narrow screen squeezes the track
+        let bc = BoxConstraints::new(Size::ZERO, Size::new(120, 240));
+        let size = slider.layout(&mut ctx, &bc, &0.5, &env);
+        assert_eq!(size, Size::new(120, 36));
+    }
+
+    #[test]
+    fn value_from_touch() {
+        // brightness in 0..=255, track from x = 18 to x = 218
+        let slider = Slider::<u8>::new().with_range(0., 255.);
+        assert_eq!(slider.calculate_value(0, 36, 236), 0.);
+        assert_eq!(slider.calculate_value(118, 36, 236), 127.5);
+        assert_eq!(slider.calculate_value(236, 36, 236), 255.);
+        assert_eq!(u8::from_factor(127.5), 128);
+
+        // vibration strength in steps of 25%
+        let slider = Slider::<f32>::new().with_step(0.25);
+        assert_eq!(slider.calculate_value(60, 36, 236), 0.25);
+        assert_eq!(slider.calculate_value(200, 36, 236), 1.);
+    }
+}
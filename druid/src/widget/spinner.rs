@@ -0,0 +1,236 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! An animated spinner widget.
+
+use core::marker::PhantomData; ////
+////use std::f64::consts::PI;
+
+////use druid::kurbo::Line;
+////use druid::widget::prelude::*;
+use crate::{ ////
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
+    UpdateCtx, Widget,
+};
+use crate::{theme, Color, Data, KeyOrValue}; ////
+////use druid::{theme, Color, Data, KeyOrValue, Point, Vec2};
+use crate::piet::polar; ////
+use crate::{Line, Point, ScreenCoord, ScreenFactor, WidgetId, WidgetType}; ////
+
+/// Time for the spinner to complete one turn, in seconds.
+const SPIN_TIME: ScreenFactor = 1.0; ////
+
+/// An animated spinner widget for showing a loading state.
+///
+/// The spinner turns on every `AnimFrame`, so it keeps requesting frames
+/// for as long as it is shown.
+///
+/// To customize the spinner's size, you can place it inside a [`SizedBox`]
+/// that has a fixed width and height.
+///
+/// [`SizedBox`]: struct.SizedBox.html
+#[derive(Clone)] ////
+pub struct Spinner<T> { ////
+////pub struct Spinner {
+    id: WidgetId, ////
+    t: ScreenFactor, ////
+    ////t: f64,
+    color: KeyOrValue<Color>,
+    phantom: PhantomData<T>, ////
+}
+
+impl<T> Spinner<T> { ////
+////impl Spinner {
+    /// Create a spinner widget
+    pub fn new() -> Spinner<T> { ////
+    ////pub fn new() -> Spinner {
+        Spinner::default()
+    }
+
+    /// Builder-style method for setting the spinner's color.
+    ///
+    /// The argument can be either a `Color` or a [`Key<Color>`].
+    ///
+    /// [`Key<Color>`]: ../struct.Key.html
+    pub fn with_color(mut self, color: impl Into<KeyOrValue<Color>>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the spinner's color.
+    ///
+    /// The argument can be either a `Color` or a [`Key<Color>`].
+    ///
+    /// [`Key<Color>`]: ../struct.Key.html
+    pub fn set_color(&mut self, color: impl Into<KeyOrValue<Color>>) {
+        self.color = color.into();
+    }
+}
+
+impl<T> Default for Spinner<T> { ////
+////impl Default for Spinner {
+    fn default() -> Self {
+        Spinner {
+            id: WidgetId::next(), ////
+            t: 0.0,
+            color: theme::LABEL_COLOR.into(),
+            phantom: PhantomData, ////
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for Spinner<T> { ////
+////impl<T: Data> Widget<T> for Spinner {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.request_anim_frame();
+        }
+
+        if let LifeCycle::AnimFrame(interval) = event {
+            self.t += (*interval as ScreenFactor) * 1e-9 / SPIN_TIME; ////
+            ////self.t += (*interval as f64) * 1e-9;
+            self.t %= 1.0; ////
+            ////if self.t >= 1.0 {
+                ////self.t = 0.0;
+            ////}
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) {}
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T,
+        env: &Env,
+    ) -> Size {
+        bc.debug_check("Spinner");
+
+        if bc.is_width_bounded() && bc.is_height_bounded() {
+            bc.max()
+        } else {
+            bc.constrain(Size::new(
+                theme::BASIC_WIDGET_HEIGHT.resolve(env), ////
+                theme::BASIC_WIDGET_HEIGHT.resolve(env), ////
+                ////env.get(theme::BASIC_WIDGET_HEIGHT),
+                ////env.get(theme::BASIC_WIDGET_HEIGHT),
+            ))
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let size = ctx.size();
+        let spinner = lv_spinner_create(lv_scr_act(), NULL);    //  Add a spinner to the current screen
+        lv_obj_set_size(spinner, size.width, size.height);      //  Set its size
+        lv_spinner_set_spin_time(spinner, (SPIN_TIME * 1000.) as u16);  //  LVGL turns the arc itself
+        lv_spinner_set_arc_length(spinner, 60);                 //  Length of the arc in degrees
+        lv_obj_set_style_local_line_color(spinner, LV_SPINNER_PART_INDIC, LV_STATE_DEFAULT, self.color.resolve(env));
+        */
+        let t = self.t;
+        let (width, height) = (ctx.size().width, ctx.size().height);
+        let center = Point::new(width / 2, height / 2); ////
+        ////let center = Point::new(width / 2.0, height / 2.0);
+        //  Colors have no alpha, so the lines fade into the background instead
+        let spinner_color = self.color.resolve(env); ////
+        let background = ctx.render_ctx.background(); ////
+        ////let (r, g, b, original_alpha) = Color::as_rgba(&self.color.resolve(env));
+        let scale_factor = width.min(height) as ScreenFactor / 40.0; ////
+        ////let scale_factor = width.min(height) / 40.0;
+
+        for step in 1..=12 {
+            let step = step as ScreenFactor; ////
+            ////let step = f64::from(step);
+            let fade_t = ((t * 12.0 + 1.0) as u8) as ScreenFactor; ////
+            ////let fade_t = (t * 12.0 + 1.0).trunc();
+            let fade = ((fade_t + step) % 12.0 / 12.0) + 1.0 / 12.0; ////
+            ////let fade = ((fade_t + step).rem_euclid(12.0) / 12.0) + 1.0 / 12.0;
+            //  Angles in degrees go clockwise, so negative angles go anticlockwise
+            let angle = (step / 12.0) * -360.0; ////
+            ////let angle = Vec2::from_angle((step / 12.0) * -2.0 * PI);
+            let ambit_start = polar(center, 10.0 * scale_factor, angle); ////
+            ////let ambit_start = center + (10.0 * scale_factor * angle);
+            let ambit_end = polar(center, 20.0 * scale_factor, angle); ////
+            ////let ambit_end = center + (20.0 * scale_factor * angle);
+            let color = background.lerp(spinner_color, fade); ////
+            ////let color = Color::rgba(r, g, b, fade * original_alpha);
+
+            ctx.stroke(
+                Line::new(ambit_start, ambit_end),
+                &color,
+                (3.0 * scale_factor + 0.5) as ScreenCoord, ////
+                ////3.0 * scale_factor,
+            );
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Spinner(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+    use crate::tests::harness::Harness;
+    use crate::tests::helpers::lifecycle_ctx;
+
+    #[test]
+    fn turns_on_anim_frame() {
+        let env = theme::init();
+        let mut spinner = Spinner::<()>::new();
+        let mut ctx = lifecycle_ctx();
+        let frame = LifeCycle::AnimFrame(250_000_000);
+        spinner.lifecycle(&mut ctx, &frame, &(), &env);
+        assert!((spinner.t - 0.25).abs() < 1e-6);
+        assert!(ctx.widget_state.request_anim);
+
+        // a full turn starts over, keeping the overshoot
+        for _ in 0..2 {
+            spinner.lifecycle(&mut ctx, &frame, &(), &env);
+        }
+        spinner.lifecycle(&mut ctx, &LifeCycle::AnimFrame(400_000_000), &(), &env);
+        assert!((spinner.t - 0.15).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lines_fade_around_the_center() {
+        let fb = Framebuffer::lock();
+        Harness::create_with_render((), Spinner::new, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+        let center = Point::new(SIZE as ScreenCoord / 2, SIZE as ScreenCoord / 2);
+        let pixel = |angle| {
+            let point = polar(center, 18., angle);
+            fb.row(point.y as usize)[point.x as usize]
+        };
+        // The lines get brighter anticlockwise, from the faintest to the brightest
+        assert_eq!(pixel(60.), 0xff);
+        assert!(pixel(30.) < 0x20);
+        assert!(pixel(0.) > pixel(30.));
+        // and the middle is left empty
+        assert_eq!(fb.row(SIZE / 2)[SIZE / 2], 0);
+    }
+}
@@ -0,0 +1,440 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A stepper widget.
+
+use core::f32::EPSILON; ////
+////use std::f64::EPSILON;
+use core::time::Duration; ////
+////use std::time::Duration;
+
+////use crate::kurbo::{BezPath, Rect};
+////use crate::piet::{LinearGradient, RenderContext, UnitPoint};
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size, ////
+    ////BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Size,
+    TimerToken, UpdateCtx, Widget,
+};
+use crate::{LinearGradient, Point, PolyLine, Rect, ScreenFactor, UnitPoint, WidgetId, WidgetType}; ////
+use crate::widget::common::Numeric; ////
+
+use crate::theme;
+////use crate::Point;
+
+// Delay until stepper starts automatically changing valued when one of the button is held down.
+const STEPPER_REPEAT_DELAY: Duration = Duration::from_millis(500);
+// Delay between value changes when one of the button is held down.
+const STEPPER_REPEAT: Duration = Duration::from_millis(200);
+
+/// A stepper widget for step-wise increasing and decreasing a value.
+///
+/// The stepper implements `Widget<T>` for any [`Numeric`] `T`. Touching the
+/// upper half increases the value, the lower half decreases it, and holding
+/// the touch repeats the step.
+///
+/// [`Numeric`]: trait.Numeric.html
+#[derive(Clone)] ////
+pub struct Stepper<T> { ////
+////pub struct Stepper {
+    id: WidgetId, ////
+    max: ScreenFactor, ////
+    ////max: f64,
+    min: ScreenFactor, ////
+    ////min: f64,
+    step: ScreenFactor, ////
+    ////step: f64,
+    wrap: bool,
+    /// Keeps track of which button is currently triggered.
+    increase_active: bool,
+    decrease_active: bool,
+    timer_id: TimerToken,
+    /// Reads the value as a `ScreenFactor`.
+    get: fn(&T) -> ScreenFactor, ////
+    /// Writes a `ScreenFactor` to the value.
+    set: fn(&mut T, ScreenFactor), ////
+}
+
+impl<T: Numeric> Stepper<T> { ////
+////impl Stepper {
+    pub fn new() -> Self {
+        Stepper {
+            id: WidgetId::next(), ////
+            max: ScreenFactor::MAX, ////
+            ////max: std::f64::MAX,
+            min: ScreenFactor::MIN, ////
+            ////min: std::f64::MIN,
+            step: 1.0,
+            wrap: false,
+            increase_active: false,
+            decrease_active: false,
+            timer_id: TimerToken::INVALID,
+            get: |data| data.to_factor(), ////
+            set: |data, value| *data = T::from_factor(value), ////
+        }
+    }
+}
+
+impl<T> Stepper<T> { ////
+    /// Set the range covered by this slider.
+    ///
+    /// The default range is `f32::MIN..f32::MAX`.
+    pub fn with_range(mut self, min: ScreenFactor, max: ScreenFactor) -> Self { ////
+    ////pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the steppers amount by which the value increases or decreases.
+    ///
+    /// The default step is `1.0`.
+    pub fn with_step(mut self, step: ScreenFactor) -> Self { ////
+    ////pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set whether the stepper should wrap around the minimum/maximum values.
+    ///
+    /// When wraparound is enabled incrementing above max behaves like this:
+    /// - if the previous value is < max it becomes max
+    /// - if the previous value is = max it becomes min
+    /// Same logic applies for decrementing
+    ///
+    /// The default is `false`.
+    pub fn with_wraparound(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    fn increment(&mut self, data: &mut T) { ////
+    ////fn increment(&mut self, data: &mut f64) {
+        let value = (self.get)(data); ////
+        let next = value + self.step; ////
+        ////let next = *data + self.step;
+        let was_greater = value + EPSILON >= self.max; ////
+        ////let was_greater = *data + EPSILON >= self.max;
+        let is_greater = next + EPSILON > self.max;
+        let value = match (self.wrap, was_greater, is_greater) { ////
+        ////*data = match (self.wrap, was_greater, is_greater) {
+            (true, true, true) => self.min,
+            (true, false, true) => self.max,
+            (false, _, true) => self.max,
+            _ => next,
+        }; ////
+        (self.set)(data, value); ////
+    }
+
+    fn decrement(&mut self, data: &mut T) { ////
+    ////fn decrement(&mut self, data: &mut f64) {
+        let value = (self.get)(data); ////
+        let next = value - self.step; ////
+        ////let next = *data - self.step;
+        let was_less = value - EPSILON <= self.min; ////
+        ////let was_less = *data - EPSILON <= self.min;
+        let is_less = next - EPSILON < self.min;
+        let value = match (self.wrap, was_less, is_less) { ////
+        ////*data = match (self.wrap, was_less, is_less) {
+            (true, true, true) => self.max,
+            (true, false, true) => self.min,
+            (false, _, true) => self.min,
+            _ => next,
+        }; ////
+        (self.set)(data, value); ////
+    }
+}
+
+impl<T: Numeric> Default for Stepper<T> { ////
+////impl Default for Stepper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for Stepper<T> { ////
+////impl Widget<f64> for Stepper {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, _data: &f64, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let spinbox = lv_spinbox_create(lv_scr_act(), NULL);            //  Add a spinbox to the current screen
+        lv_spinbox_set_range(spinbox, self.min as i32, self.max as i32);    //  Range of the value
+        lv_spinbox_set_step(spinbox, self.step as u32);                 //  Step of the buttons
+        lv_spinbox_set_value(spinbox, (self.get)(_data) as i32);        //  Current value
+        lv_spinbox_set_rollover(spinbox, self.wrap);                    //  Wrap around
+        */
+        let stroke_width = 2; ////
+        ////let stroke_width = 2.0;
+        let rounded_rect = ctx
+            .size()
+            .to_rect()
+            ////.inset(-stroke_width / 2.0)
+            .to_rounded_rect(4); ////
+            ////.to_rounded_rect(4.0);
+
+        let height = ctx.size().height;
+        let width = theme::BASIC_WIDGET_HEIGHT.resolve(env); ////
+        ////let width = env.get(theme::BASIC_WIDGET_HEIGHT);
+        let button_size = Size::new(width, height / 2); ////
+        ////let button_size = Size::new(width, height / 2.);
+
+        ctx.stroke(rounded_rect, &theme::BORDER_DARK.resolve(env), stroke_width); ////
+        ////ctx.stroke(rounded_rect, &env.get(theme::BORDER_DARK), stroke_width);
+        //  Clips are rectangles, and the stroke is drawn inside the shape, so the
+        //  buttons are clipped to the inside of the border
+        ctx.with_save(|ctx| { ////
+            ctx.clip(rounded_rect.shrink(stroke_width).rect()); ////
+            ////ctx.clip(rounded_rect);
+
+            // draw buttons for increase/decrease
+            let increase_button_origin = Point::ORIGIN;
+            let mut decrease_button_origin = Point::ORIGIN;
+            decrease_button_origin.y += height / 2; ////
+            ////decrease_button_origin.y += height / 2.;
+
+            let increase_button_rect = Rect::from_origin_size(increase_button_origin, button_size);
+            let decrease_button_rect = Rect::from_origin_size(decrease_button_origin, button_size);
+
+            let active_gradient = LinearGradient::new(
+                UnitPoint::TOP,
+                UnitPoint::BOTTOM,
+                (theme::PRIMARY_LIGHT.resolve(env), theme::PRIMARY_DARK.resolve(env)), ////
+                ////(env.get(theme::PRIMARY_LIGHT), env.get(theme::PRIMARY_DARK)),
+            );
+
+            let inactive_gradient = LinearGradient::new(
+                UnitPoint::TOP,
+                UnitPoint::BOTTOM,
+                (theme::BUTTON_DARK.resolve(env), theme::BUTTON_LIGHT.resolve(env)), ////
+                ////(env.get(theme::BUTTON_DARK), env.get(theme::BUTTON_LIGHT)),
+            );
+
+            // draw buttons that are currently triggered as active
+            if self.increase_active {
+                ctx.fill(increase_button_rect, &active_gradient);
+            } else {
+                ctx.fill(increase_button_rect, &inactive_gradient);
+            };
+
+            if self.decrease_active {
+                ctx.fill(decrease_button_rect, &active_gradient);
+            } else {
+                ctx.fill(decrease_button_rect, &inactive_gradient);
+            };
+
+            // draw up and down triangles
+            //  A `PolyLine` can't be filled, so the triangles are outlined
+            let middle = height / 2; ////
+            let up_arrow = [ ////
+                Point::new(4, middle.saturating_sub(4)), ////
+                Point::new(width.saturating_sub(4), middle.saturating_sub(4)), ////
+                Point::new(width / 2, 4), ////
+                Point::new(4, middle.saturating_sub(4)), ////
+            ]; ////
+            let down_arrow = [ ////
+                Point::new(4, middle.saturating_add(4)), ////
+                Point::new(width.saturating_sub(4), middle.saturating_add(4)), ////
+                Point::new(width / 2, height.saturating_sub(4)), ////
+                Point::new(4, middle.saturating_add(4)), ////
+            ]; ////
+            let label_color = theme::LABEL_COLOR.resolve(env); ////
+            ctx.stroke(PolyLine::new(&up_arrow), &label_color, 1); ////
+            ctx.stroke(PolyLine::new(&down_arrow), &label_color, 1); ////
+        }); ////
+        ////let mut arrows = BezPath::new();
+        ////arrows.move_to(Point::new(4., height / 2. - 4.));
+        ////arrows.line_to(Point::new(width - 4., height / 2. - 4.));
+        ////arrows.line_to(Point::new(width / 2., 4.));
+        ////arrows.close_path();
+
+        ////arrows.move_to(Point::new(4., height / 2. + 4.));
+        ////arrows.line_to(Point::new(width - 4., height / 2. + 4.));
+        ////arrows.line_to(Point::new(width / 2., height - 4.));
+        ////arrows.close_path();
+
+        ////ctx.fill(arrows, &env.get(theme::LABEL_COLOR));
+    }
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T, ////
+        ////_data: &f64,
+        env: &Env,
+    ) -> Size {
+        bc.constrain(Size::new(
+            theme::BASIC_WIDGET_HEIGHT.resolve(env), ////
+            theme::BORDERED_WIDGET_HEIGHT.resolve(env), ////
+            ////env.get(theme::BASIC_WIDGET_HEIGHT),
+            ////env.get(theme::BORDERED_WIDGET_HEIGHT),
+        ))
+    }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, _env: &Env) { ////
+    ////fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut f64, env: &Env) {
+        // Split where the arrows are painted, at any height ////
+        let height = ctx.size().height; ////
+        ////let height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+
+        match event {
+            Event::MouseDown(mouse) => {
+                ctx.set_active(true);
+
+                if mouse.pos.y > height / 2 { ////
+                ////if mouse.pos.y > height / 2. {
+                    self.decrease_active = true;
+                    self.decrement(data);
+                } else {
+                    self.increase_active = true;
+                    self.increment(data);
+                }
+
+                self.timer_id = ctx.request_timer(STEPPER_REPEAT_DELAY);
+
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) => {
+                ctx.set_active(false);
+
+                self.decrease_active = false;
+                self.increase_active = false;
+                self.timer_id = TimerToken::INVALID;
+
+                ctx.request_paint();
+            }
+            Event::Timer(id) if *id == self.timer_id => {
+                if self.increase_active {
+                    self.increment(data);
+                }
+                if self.decrease_active {
+                    self.decrement(data);
+                }
+                self.timer_id = ctx.request_timer(STEPPER_REPEAT);
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, _env: &Env) { ////
+        match event { ////
+            LifeCycle::WidgetAdded => ctx.register_for_focus(), ////
+            LifeCycle::FocusChanged(_) => ctx.request_paint(), ////
+            _ => (), ////
+        } ////
+    } ////
+    ////fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &f64, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, old_data: &f64, data: &f64, _env: &Env) {
+        if !old_data.same(data) { ////
+        ////if (*data - old_data).abs() > EPSILON {
+            ctx.request_paint();
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Stepper(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+    use crate::tests::harness::Harness;
+    use crate::tests::helpers::layout_ctx;
+    use crate::MouseEvent;
+
+    #[test]
+    fn layout() {
+        let env = theme::init();
+        let mut stepper = Stepper::<u8>::new();
+        let mut ctx = layout_ctx();
+        let bc = BoxConstraints::new(Size::ZERO, Size::new(240, 240));
+        assert_eq!(stepper.layout(&mut ctx, &bc, &0, &env), Size::new(36, 48));
+    }
+
+    #[test]
+    fn steps_within_range() {
+        let mut stepper = Stepper::<u8>::new().with_range(0., 10.).with_step(4.);
+        let mut data = 0;
+        stepper.increment(&mut data);
+        stepper.increment(&mut data);
+        assert_eq!(data, 8);
+        stepper.increment(&mut data);
+        assert_eq!(data, 10);
+        stepper.increment(&mut data);
+        assert_eq!(data, 10);
+
+        let mut stepper = stepper.with_wraparound(true);
+        stepper.increment(&mut data);
+        assert_eq!(data, 0);
+        stepper.decrement(&mut data);
+        assert_eq!(data, 10);
+    }
+
+    #[test]
+    fn holding_repeats_the_step() {
+        let top = MouseEvent::new(Point::new(10, 10));
+        Harness::create_simple(0u8, || Stepper::new().with_range(0., 10.), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.event(Event::MouseDown(top.clone()));
+            assert_eq!(*harness.data(), 1);
+
+            // The step repeats after a delay, then faster
+            harness.advance_time(Duration::from_millis(400));
+            assert_eq!(*harness.data(), 1);
+            harness.advance_time(Duration::from_millis(100));
+            assert_eq!(*harness.data(), 2);
+            harness.advance_time(Duration::from_millis(200));
+            harness.advance_time(Duration::from_millis(200));
+            assert_eq!(*harness.data(), 4);
+
+            // until the touch is lifted
+            harness.event(Event::MouseUp(top.clone()));
+            harness.advance_time(Duration::from_millis(1000));
+            assert_eq!(*harness.data(), 4);
+        });
+    }
+
+    #[test]
+    fn paints_the_pressed_button_highlighted() {
+        let fb = Framebuffer::lock();
+        Harness::create_with_render(0u8, Stepper::new, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.event(Event::MouseDown(MouseEvent::new(Point::new(8, 4))));
+            harness.paint();
+        }, Some(draw));
+        // The border with rounded corners, then the top of each button's gradient
+        assert_eq!(fb.row(0)[..3], [0, 0, 0x3a]);
+        assert!(fb.row(3)[4] > 0x21);
+        assert!(fb.row(SIZE - 3)[4] <= 0x21);
+        // and the bases of the arrows over the buttons
+        assert_eq!(fb.row(SIZE / 2 - 4)[6], 0xff);
+        assert_eq!(fb.row(SIZE / 2 + 4)[6], 0xff);
+    }
+
+    #[test]
+    fn float_steps() {
+        let mut stepper = Stepper::<f32>::new().with_range(0., 1.).with_step(0.25);
+        let mut data = 0.5;
+        stepper.decrement(&mut data);
+        assert_eq!(data, 0.25);
+    }
+}
@@ -13,21 +13,41 @@
 // limitations under the License.
 
 //! An SVG widget.
+//!
+//! SVG files are rasterized when the app is built, as there is no room to parse
+//! them on the watch. See the `druid-app` build script, which converts
+//! `images/<name>.<width>x<height>.svg` into an `SvgData` static at that size.
 
-use std::error::Error;
-use std::str::FromStr;
-use std::sync::Arc;
+////use std::error::Error;
+////use std::str::FromStr;
+////use std::sync::Arc;
 
-use log::error;
+////use log::error;
 
 use crate::{
-    kurbo::BezPath, widget::common::FillStrat, Affine, BoxConstraints, Color, Data, Env, Event,
-    EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Rect, RenderContext, Size, UpdateCtx,
+    widget::common::FillStrat, BoxConstraints, Data, Env, Event, ////
+    EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Rect, Size, UpdateCtx, ////
     Widget,
+    ////kurbo::BezPath, widget::common::FillStrat, Affine, BoxConstraints, Color, Data, Env, Event,
+    ////EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, Rect, RenderContext, Size, UpdateCtx,
 };
+use crate::{widget::{ColorFormat, ImageData}, ScreenCoord, WidgetId, WidgetType}; ////
 
 /// A widget that renders a SVG
+///
+/// ```
+/// use druid::widget::{ColorFormat, FillStrat, Svg, SvgData};
+///
+/// /// Generated by the build script from `images/bell.24x24.svg`
+/// static BELL: SvgData = SvgData::new(ColorFormat::TrueColorAlpha, 24, 24, &[
+///     // ...
+/// ]);
+///
+/// let icon = Svg::new(BELL).fill_mode(FillStrat::None);
+/// ```
+#[derive(Clone)] ////
 pub struct Svg {
+    id: WidgetId, ////
     svg_data: SvgData,
     fill: FillStrat,
 }
@@ -38,14 +58,16 @@ impl Svg {
     /// The SVG will scale to fit its box constraints.
     pub fn new(svg_data: SvgData) -> Self {
         Svg {
+            id: WidgetId::next(), ////
             svg_data,
             fill: FillStrat::default(),
         }
     }
 
     /// Measure the SVG's size
-    #[allow(clippy::needless_return)]
     fn get_size(&self) -> Size {
+        self.svg_data.image.size() ////
+        /* ////
         let root = self.svg_data.tree.root();
         match *root.borrow() {
             usvg::NodeKind::Svg(svg) => {
@@ -58,6 +80,7 @@ impl Svg {
                 return Size::ZERO;
             }
         };
+        */ ////
     }
 
     /// A builder-style method for specifying the fill strategy.
@@ -73,6 +96,8 @@ impl Svg {
 }
 
 impl<T: Data> Widget<T> for Svg {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
     fn event(&mut self, _ctx: &mut EventCtx, _event: &Event, _data: &mut T, _env: &Env) {}
 
     fn lifecycle(&mut self, _ctx: &mut LifeCycleCtx, _event: &LifeCycle, _data: &T, _env: &Env) {}
@@ -97,6 +122,12 @@ impl<T: Data> Widget<T> for Svg {
     fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, _env: &Env) {
         let offset_matrix = self.fill.affine_to_fill(ctx.size(), self.get_size());
 
+        //// TODO: Render with LVGL
+        /*
+        let img = lv_img_create(lv_scr_act(), NULL);                     //  Same as `Image`, the SVG is already an lv_img_dsc_t
+        lv_img_set_src(img, &img_dsc);
+        */
+
         let clip_rect = Rect::ZERO.with_size(ctx.size());
 
         // The SvgData's to_piet function dose not clip to the svg's size
@@ -104,18 +135,31 @@ impl<T: Data> Widget<T> for Svg {
         ctx.clip(clip_rect);
         self.svg_data.to_piet(offset_matrix, ctx);
     }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Svg(self)
+    }
 }
 
 /// Stored SVG data.
-/// Implements `FromStr` and can be converted to piet draw instructions.
-#[derive(Clone)]
+/// Rasterized at build time into an image in LVGL's `lv_img_dsc_t` layout.
+#[derive(Clone, Copy)] ////
+////#[derive(Clone)]
 pub struct SvgData {
-    tree: Arc<usvg::Tree>,
+    image: ImageData, ////
+    ////tree: Arc<usvg::Tree>,
 }
 
 impl SvgData {
+    /// Create SVG data from the image rasterized by the build script.
+    pub const fn new(format: ColorFormat, width: ScreenCoord, height: ScreenCoord, data: &'static [u8]) -> Self { ////
+        SvgData { image: ImageData::new(format, width, height, data) } ////
+    } ////
+
     /// Create an empty SVG
-    pub fn empty() -> Self {
+    pub const fn empty() -> Self { ////
+        SvgData { image: ImageData::empty() } ////
+        /* ////
         let re_opt = usvg::Options {
             keep_named_groups: false,
             ..usvg::Options::default()
@@ -131,8 +175,20 @@ impl SvgData {
         SvgData {
             tree: Arc::new(usvg::Tree::from_str(empty_svg, &re_opt).unwrap()),
         }
+        */ ////
     }
 
+    /// The rasterized image.
+    pub fn image(&self) -> &ImageData { ////
+        &self.image ////
+    } ////
+
+    /// Draw the rasterized image, placed and scaled by `offset_matrix`.
+    pub fn to_piet(&self, offset_matrix: crate::Affine, ctx: &mut PaintCtx) { ////
+        ctx.draw_image(&self.image, offset_matrix); ////
+    } ////
+
+    /* ////
     /// Convert SvgData into Piet draw instructions
     pub fn to_piet(&self, offset_matrix: Affine, ctx: &mut PaintCtx) {
         let root = self.tree.root();
@@ -202,12 +258,22 @@ impl SvgData {
     }
 }
 
+    */ ////
+}
+
 impl Default for SvgData {
     fn default() -> Self {
         SvgData::empty()
     }
 }
 
+impl From<ImageData> for SvgData { ////
+    fn from(image: ImageData) -> Self { ////
+        SvgData { image } ////
+    } ////
+} ////
+
+/* ////
 impl FromStr for SvgData {
     type Err = Box<dyn Error>;
 
@@ -236,121 +302,42 @@ fn color_from_usvg(paint: &usvg::Paint, opacity: usvg::Opacity) -> Color {
         }
     }
 }
+*/ ////
 
-#[cfg(not(target_arch = "wasm32"))]
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{piet::Piet, Color};
 
-    #[test]
-    fn translate() {
-        use crate::tests::harness::Harness;
-
-        let svg_data = SvgData::from_str(
-            "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 2 2'>
-        <g>
-            <g>
-                <rect width='1' height='1'/>
-            </g>
-        </g>
-        <g transform=\"translate(1, 1)\">
-            <g>
-                <rect width='1' height='1'/>
-            </g>
-        </g>
-    </svg>",
-        )
-        .unwrap();
-
-        let svg_widget = Svg::new(svg_data);
-
-        Harness::create_with_render(
-            true,
-            svg_widget,
-            Size::new(400., 600.),
-            |harness| {
-                harness.send_initial_events();
-                harness.just_layout();
-                harness.paint();
-            },
-            |target| {
-                let raw_pixels = target.into_raw();
-                assert_eq!(raw_pixels.len(), 400 * 600 * 4);
-
-                // Being a tall widget with a square image the top and bottom rows will be
-                // the padding color and the middle rows will not have any padding.
-
-                // Check that the middle row 400 pix wide is 200 black then 200 white.
-                let expecting: Vec<u8> = [
-                    vec![41, 41, 41, 255].repeat(200),
-                    vec![0, 0, 0, 255].repeat(200),
-                ]
-                .concat();
-                assert_eq!(raw_pixels[400 * 300 * 4..400 * 301 * 4], expecting[..]);
-
-                // Check that all of the last 100 rows are all the background color.
-                let expecting: Vec<u8> = vec![41, 41, 41, 255].repeat(400 * 100);
-                assert_eq!(
-                    raw_pixels[400 * 600 * 4 - 4 * 400 * 100..400 * 600 * 4],
-                    expecting[..]
-                );
-            },
-        )
+    const SIZE: usize = 4;
+    /// Pixels painted by `draw`, as the color's red channel.
+    static mut PIXELS: [[u8; SIZE]; SIZE] = [[0x29; SIZE]; SIZE];
+
+    fn draw(rect: Rect, color: Color) {
+        for y in rect.y0..rect.y1 {
+            for x in rect.x0..rect.x1 {
+                unsafe { PIXELS[y as usize][x as usize] = color.as_rgb8().0; }
+            }
+        }
+    }
+
+    fn row(y: usize) -> [u8; SIZE] {
+        unsafe { PIXELS[y] }
     }
 
     #[test]
-    fn scale() {
-        use crate::tests::harness::Harness;
-
-        let svg_data = SvgData::from_str(
-            "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 2 2'>
-        <g>
-            <g>
-                <rect width='1' height='1'/>
-            </g>
-        </g>
-        <g transform=\"translate(1, 1)\">
-            <g transform=\"scale(1, 2)\">
-                <rect width='1' height='0.5'/>
-            </g>
-        </g>
-    </svg>",
-        )
-        .unwrap();
-
-        let svg_widget = Svg::new(svg_data);
-
-        Harness::create_with_render(
-            true,
-            svg_widget,
-            Size::new(400., 600.),
-            |harness| {
-                harness.send_initial_events();
-                harness.just_layout();
-                harness.paint();
-            },
-            |target| {
-                let raw_pixels = target.into_raw();
-                assert_eq!(raw_pixels.len(), 400 * 600 * 4);
-
-                // Being a tall widget with a square image the top and bottom rows will be
-                // the padding color and the middle rows will not have any padding.
-
-                // Check that the middle row 400 pix wide is 200 black then 200 white.
-                let expecting: Vec<u8> = [
-                    vec![41, 41, 41, 255].repeat(200),
-                    vec![0, 0, 0, 255].repeat(200),
-                ]
-                .concat();
-                assert_eq!(raw_pixels[400 * 300 * 4..400 * 301 * 4], expecting[..]);
-
-                // Check that all of the last 100 rows are all the background color.
-                let expecting: Vec<u8> = vec![41, 41, 41, 255].repeat(400 * 100);
-                assert_eq!(
-                    raw_pixels[400 * 600 * 4 - 4 * 400 * 100..400 * 600 * 4],
-                    expecting[..]
-                );
-            },
-        )
+    fn translate() {
+        // Rasterized from two black squares on a transparent 2x2 view box
+        let svg_data = SvgData::new(ColorFormat::TrueColorAlpha, 2, 2, &[
+            0x00, 0x00, 0xff,  0xff, 0xff, 0x00,
+            0xff, 0xff, 0x00,  0x00, 0x00, 0xff,
+        ]);
+        let mut piet = Piet::with_display(Size::new(4, 4), Some(draw));
+        let offset_matrix = FillStrat::Contain.affine_to_fill(Size::new(4, 4), Svg::new(svg_data).get_size());
+        piet.draw_image(svg_data.image(), offset_matrix);
+
+        // Transparent pixels keep the background colour
+        assert_eq!(row(0), [0, 0, 0x29, 0x29]);
+        assert_eq!(row(3), [0x29, 0x29, 0, 0]);
     }
 }
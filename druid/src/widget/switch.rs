@@ -0,0 +1,533 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A toggle switch widget.
+
+use core::time::Duration; ////
+////use std::time::Duration;
+
+use crate::{Circle, LinearGradient, Point, Size, UnitPoint}; ////
+////use crate::kurbo::{Circle, Point, Rect, Shape, Size};
+////use crate::piet::{
+    ////FontBuilder, LinearGradient, RenderContext, Text, TextLayout, TextLayoutBuilder, UnitPoint,
+////};
+use crate::theme;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx, ////
+    ////BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, UpdateCtx,
+    Widget,
+};
+use crate::{ScreenCoord, ScreenFactor, WidgetId, WidgetType}; ////
+
+const SWITCH_CHANGE_TIME: ScreenFactor = 0.2; ////
+////const SWITCH_CHANGE_TIME: f64 = 0.2;
+const SWITCH_PADDING: ScreenCoord = 3; ////
+////const SWITCH_PADDING: f64 = 3.;
+const SWITCH_WIDTH_RATIO: ScreenFactor = 2.75; ////
+////const SWITCH_WIDTH_RATIO: f64 = 2.75;
+
+/// A switch that toggles a `bool`.
+///
+/// The switch is toggled by a tap, or by dragging the knob across. The knob
+/// slides to its new position on `AnimFrame`.
+#[derive(Debug, Clone)] ////
+////#[derive(Debug, Clone, Default)]
+pub struct Switch<T> { ////
+////pub struct Switch {
+    id: WidgetId, ////
+    knob_pos: Point,
+    /// Precise x position of the knob while it slides.
+    knob_x: ScreenFactor, ////
+    knob_hovered: bool,
+    knob_dragged: bool,
+    animation_in_progress: bool,
+    /// Reads the state of the switch.
+    get: fn(&T) -> bool, ////
+    /// Writes the state of the switch.
+    set: fn(&mut T, bool), ////
+}
+
+/// Horizontal geometry of a switch: its width, and the centre of the knob when off and on.
+struct Geometry { ////
+    width: ScreenCoord,
+    knob_size: ScreenCoord,
+    off_pos: ScreenCoord,
+    on_pos: ScreenCoord,
+}
+
+impl Geometry { ////
+    fn new(env: &Env) -> Self {
+        let switch_height = theme::BORDERED_WIDGET_HEIGHT.resolve(env);
+        let width = (switch_height as ScreenFactor * SWITCH_WIDTH_RATIO) as ScreenCoord;
+        let knob_size = switch_height - 2 * SWITCH_PADDING;
+        Geometry {
+            width,
+            knob_size,
+            off_pos: knob_size / 2 + SWITCH_PADDING,
+            on_pos: width - knob_size / 2 - SWITCH_PADDING,
+        }
+    }
+}
+
+impl Switch<bool> { ////
+////impl Switch {
+    /// Create a new `Switch`.
+    pub fn new() -> Switch<bool> { ////
+    ////pub fn new() -> Switch {
+        Switch {
+            id: WidgetId::next(),
+            knob_pos: Point::ORIGIN,
+            knob_x: 0.,
+            knob_hovered: false,
+            knob_dragged: false,
+            animation_in_progress: false,
+            get: |data| *data,
+            set: |data, value| *data = value,
+        }
+        ////Self::default()
+    }
+}
+
+impl<T> Switch<T> { ////
+    fn knob_hit_test(&self, knob_width: ScreenCoord, mouse_pos: Point) -> bool { ////
+    ////fn knob_hit_test(&self, knob_width: f64, mouse_pos: Point) -> bool {
+        let dx = mouse_pos.x as i32 - self.knob_pos.x as i32; ////
+        let dy = mouse_pos.y as i32 - self.knob_pos.y as i32; ////
+        let radius = knob_width as i32 / 2; ////
+        dx * dx + dy * dy < radius * radius ////
+        ////let knob_circle = Circle::new(self.knob_pos, knob_width / 2.);
+        ////knob_circle.winding(mouse_pos) > 0
+    }
+
+    /// Move the knob, keeping it on the track.
+    fn set_knob_x(&mut self, x: ScreenFactor, geometry: &Geometry) { ////
+        self.knob_x = x
+            .max(geometry.off_pos as ScreenFactor)
+            .min(geometry.on_pos as ScreenFactor);
+        self.knob_pos.x = (self.knob_x + 0.5) as ScreenCoord;
+    }
+
+    /* ////
+    fn paint_labels(&mut self, ctx: &mut PaintCtx, env: &Env, switch_width: f64) {
+        let font_name = env.get(theme::FONT_NAME);
+        let font_size = env.get(theme::TEXT_SIZE_NORMAL);
+        let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        let knob_size = switch_height - 2. * SWITCH_PADDING;
+
+        let font = ctx
+            .text()
+            .new_font_by_name(font_name, font_size)
+            .build()
+            .unwrap();
+
+        // off/on labels
+        // TODO: use LocalizedString
+        let on_label_layout = ctx
+            .text()
+            .new_text_layout(&font, "ON", std::f64::INFINITY)
+            .build()
+            .unwrap();
+
+        let off_label_layout = ctx
+            .text()
+            .new_text_layout(&font, "OFF", std::f64::INFINITY)
+            .build()
+            .unwrap();
+
+        // position off/on labels
+        let mut on_label_origin = UnitPoint::LEFT.resolve(Rect::from_origin_size(
+            Point::ORIGIN,
+            Size::new(
+                (ctx.size().width - on_label_layout.width()).max(0.0),
+                switch_height + (font_size * 1.2) / 2.,
+            ),
+        ));
+
+        let mut off_label_origin = UnitPoint::LEFT.resolve(Rect::from_origin_size(
+            Point::ORIGIN,
+            Size::new(
+                (ctx.size().width - off_label_layout.width()).max(0.0),
+                switch_height + (font_size * 1.2) / 2.,
+            ),
+        ));
+
+        // adjust label position
+        on_label_origin.y = on_label_origin.y.min(switch_height);
+        off_label_origin.y = off_label_origin.y.min(switch_height);
+
+        on_label_origin.x = self.knob_pos.x - switch_width + knob_size;
+        off_label_origin.x = switch_width - off_label_layout.width() - SWITCH_PADDING * 2.
+            + self.knob_pos.x
+            - knob_size / 2.
+            - SWITCH_PADDING;
+
+        ctx.draw_text(
+            &on_label_layout,
+            on_label_origin,
+            &env.get(theme::LABEL_COLOR),
+        );
+        ctx.draw_text(
+            &off_label_layout,
+            off_label_origin,
+            &env.get(theme::LABEL_COLOR),
+        );
+    }
+    */ ////
+}
+
+impl<T: Data> Widget<T> for Switch<T> { ////
+////impl Widget<bool> for Switch {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) { ////
+    ////fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut bool, env: &Env) {
+        let geometry = Geometry::new(env); ////
+        ////let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        ////let switch_width = switch_height * SWITCH_WIDTH_RATIO;
+        ////let knob_size = switch_height - 2. * SWITCH_PADDING;
+        ////let on_pos = switch_width - knob_size / 2. - SWITCH_PADDING;
+        ////let off_pos = knob_size / 2. + SWITCH_PADDING;
+
+        match event {
+            Event::MouseDown(_) => {
+                ctx.set_active(true);
+                ctx.request_paint();
+            }
+            Event::MouseUp(_) => {
+                // a touch that was taken over, e.g. by a scroll, is neither hot nor a toggle
+                if ctx.is_active() && ctx.is_hot() { ////
+                ////if self.knob_dragged {
+                    if self.knob_dragged { ////
+                        // toggle value when dragging if knob has been moved far enough
+                        (self.set)(data, self.knob_pos.x > geometry.width / 2); ////
+                        ////*data = self.knob_pos.x > switch_width / 2.;
+                    } else { ////
+                    ////} else if ctx.is_active() {
+                        // toggle value on click
+                        let value = (self.get)(data); ////
+                        (self.set)(data, !value); ////
+                        ////*data = !*data;
+                    }
+                }
+
+                ctx.set_active(false);
+
+                self.knob_dragged = false;
+                self.animation_in_progress = true;
+                ctx.request_anim_frame();
+            }
+            Event::MouseMove(mouse) => {
+                if ctx.is_active() {
+                    self.set_knob_x(mouse.pos.x as ScreenFactor, &geometry); ////
+                    ////self.knob_pos.x = mouse.pos.x.min(on_pos).max(off_pos);
+                    self.knob_dragged = true;
+                }
+                if ctx.is_hot() {
+                    self.knob_hovered = self.knob_hit_test(geometry.knob_size, mouse.pos) ////
+                    ////self.knob_hovered = self.knob_hit_test(knob_size, mouse.pos)
+                }
+                ctx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) { ////
+    ////fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &bool, env: &Env) {
+        if let LifeCycle::WidgetAdded = event { ////
+            ctx.register_for_focus(); ////
+        } ////
+        if let LifeCycle::AnimFrame(interval) = event {
+            let delta = Duration::from_nanos(*interval).as_secs_f32(); ////
+            ////let delta = Duration::from_nanos(*interval).as_secs_f64();
+            let geometry = Geometry::new(env); ////
+            ////let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+            ////let switch_width = switch_height * SWITCH_WIDTH_RATIO;
+            ////let knob_size = switch_height - 2. * SWITCH_PADDING;
+            ////let on_pos = switch_width - knob_size / 2. - SWITCH_PADDING;
+            ////let off_pos = knob_size / 2. + SWITCH_PADDING;
+
+            // move knob to right position depending on the value
+            if self.animation_in_progress {
+                let change_time = if (self.get)(data) { ////
+                ////let change_time = if *data {
+                    SWITCH_CHANGE_TIME
+                } else {
+                    -SWITCH_CHANGE_TIME
+                };
+                let change = (geometry.width as ScreenFactor / change_time) * delta; ////
+                ////let change = (switch_width / change_time) * delta;
+                self.set_knob_x(self.knob_x + change, &geometry); ////
+                ////self.knob_pos.x = (self.knob_pos.x + change).min(on_pos).max(off_pos);
+
+                if self.knob_pos.x > geometry.off_pos && self.knob_pos.x < geometry.on_pos { ////
+                ////if self.knob_pos.x > off_pos && self.knob_pos.x < on_pos {
+                    ctx.request_anim_frame();
+                } else {
+                    self.animation_in_progress = false;
+                }
+                ctx.request_paint(); ////
+            }
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, _env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, old_data: &bool, data: &bool, _env: &Env) {
+        if (self.get)(old_data) != (self.get)(data) { ////
+        ////if old_data != data {
+            self.animation_in_progress = true;
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &T, ////
+        ////_data: &bool,
+        env: &Env,
+    ) -> Size {
+        let geometry = Geometry::new(env); ////
+        // position knob
+        if !self.animation_in_progress && !self.knob_dragged { ////
+            let x = if (self.get)(data) { geometry.on_pos } else { geometry.off_pos };
+            self.set_knob_x(x as ScreenFactor, &geometry);
+        }
+        let switch_height = theme::BORDERED_WIDGET_HEIGHT.resolve(env); ////
+        self.knob_pos.y = geometry.knob_size / 2 + SWITCH_PADDING; ////
+        bc.constrain(Size::new(geometry.width, switch_height)) ////
+        ////let width = env.get(theme::BORDERED_WIDGET_HEIGHT) * SWITCH_WIDTH_RATIO;
+        ////bc.constrain(Size::new(width, env.get(theme::BORDERED_WIDGET_HEIGHT)))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &bool, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        let sw = lv_switch_create(lv_scr_act(), NULL);          //  Add a switch to the current screen
+        lv_obj_set_size(sw, geometry.width, switch_height);     //  Set its size
+        if (self.get)(_data) { lv_switch_on(sw, LV_ANIM_ON); }  //  Slide the knob to the new state,
+        else { lv_switch_off(sw, LV_ANIM_ON); }                 //  LVGL animates the knob itself
+        if ctx.is_focused() { lv_obj_add_state(sw, LV_STATE_FOCUSED); }  //  Show the focus highlight
+        */
+        let geometry = Geometry::new(env); ////
+        let switch_height = theme::BORDERED_WIDGET_HEIGHT.resolve(env); ////
+        ////let switch_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        ////let switch_width = switch_height * SWITCH_WIDTH_RATIO;
+        ////let knob_size = switch_height - 2. * SWITCH_PADDING;
+        ////let on_pos = switch_width - knob_size / 2. - SWITCH_PADDING;
+        ////let off_pos = knob_size / 2. + SWITCH_PADDING;
+        let stroke_width = 2; ////
+        ////let stroke_width = 2.0;
+
+        let background_rect = Size::new(geometry.width, switch_height) ////
+        ////let background_rect = Size::new(switch_width, switch_height)
+            .to_rect()
+            ////.inset(-stroke_width / 2.0)
+            .to_rounded_rect(switch_height / 2); ////
+            ////.to_rounded_rect(switch_height / 2.);
+
+        //  The knob is positioned in `layout`
+        ////// position knob
+        ////if !self.animation_in_progress && !self.knob_dragged {
+            ////if *data {
+                ////self.knob_pos.x = on_pos;
+            ////} else {
+                ////self.knob_pos.x = off_pos;
+            ////}
+        ////};
+
+        ////self.knob_pos = Point::new(self.knob_pos.x, knob_size / 2. + SWITCH_PADDING);
+        let knob_circle = Circle::new(self.knob_pos, geometry.knob_size / 2 + stroke_width / 2); ////
+        ////let knob_circle = Circle::new(self.knob_pos, knob_size / 2.);
+
+        // paint different background for on and off state
+        // opacity of background color depends on knob position
+        // todo: make color configurable
+        let opacity = (self.knob_x - geometry.off_pos as ScreenFactor) ////
+            / geometry.on_pos.saturating_sub(geometry.off_pos).max(1) as ScreenFactor; ////
+        ////let opacity = (self.knob_pos.x - off_pos) / (on_pos - off_pos);
+
+        //  Colors have no alpha, so the gradients of the off and on states are blended
+        let background_gradient = LinearGradient::new( ////
+            UnitPoint::TOP, ////
+            UnitPoint::BOTTOM, ////
+            ( ////
+                theme::BACKGROUND_LIGHT.resolve(env).lerp(theme::PRIMARY_LIGHT.resolve(env), opacity), ////
+                theme::BACKGROUND_DARK.resolve(env).lerp(theme::PRIMARY_DARK.resolve(env), opacity), ////
+            ), ////
+        ); ////
+        ////let background_gradient_on_state = LinearGradient::new(
+            ////UnitPoint::TOP,
+            ////UnitPoint::BOTTOM,
+            ////(
+                ////env.get(theme::PRIMARY_LIGHT).with_alpha(opacity),
+                ////env.get(theme::PRIMARY_DARK).with_alpha(opacity),
+            ////),
+        ////);
+        ////let background_gradient_off_state = LinearGradient::new(
+            ////UnitPoint::TOP,
+            ////UnitPoint::BOTTOM,
+            ////(
+                ////env.get(theme::BACKGROUND_LIGHT).with_alpha(1. - opacity),
+                ////env.get(theme::BACKGROUND_DARK).with_alpha(1. - opacity),
+            ////),
+        ////);
+
+        //  Strokes are drawn inside the shape, so the outer half of the border
+        //  is painted over the fill
+        ctx.fill(background_rect, &background_gradient); ////
+        ctx.stroke(background_rect, &theme::BORDER_DARK.resolve(env), stroke_width / 2); ////
+        ////ctx.stroke(background_rect, &env.get(theme::BORDER_DARK), stroke_width);
+        ////ctx.fill(background_rect, &background_gradient_on_state);
+        ////ctx.fill(background_rect, &background_gradient_off_state);
+        ////ctx.clip(background_rect);
+
+        // paint the knob
+        let is_active = ctx.is_active();
+        let is_hovered = self.knob_hovered;
+
+        let normal_knob_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::FOREGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::FOREGROUND_LIGHT),
+                theme::FOREGROUND_DARK.resolve(env), ////
+                ////env.get(theme::FOREGROUND_DARK),
+            ),
+        );
+        let flipped_knob_gradient = LinearGradient::new(
+            UnitPoint::TOP,
+            UnitPoint::BOTTOM,
+            (
+                theme::FOREGROUND_DARK.resolve(env), ////
+                ////env.get(theme::FOREGROUND_DARK),
+                theme::FOREGROUND_LIGHT.resolve(env), ////
+                ////env.get(theme::FOREGROUND_LIGHT),
+            ),
+        );
+
+        let knob_gradient = if is_active {
+            flipped_knob_gradient
+        } else {
+            normal_knob_gradient
+        };
+
+        // paint the border
+        let border_color = if is_hovered || is_active {
+            theme::FOREGROUND_LIGHT.resolve(env) ////
+            ////env.get(theme::FOREGROUND_LIGHT)
+        } else {
+            theme::FOREGROUND_DARK.resolve(env) ////
+            ////env.get(theme::FOREGROUND_DARK)
+        };
+
+        ctx.fill(knob_circle, &knob_gradient); ////
+        ctx.stroke(knob_circle, &border_color, stroke_width / 2); ////
+        ////ctx.stroke(knob_circle, &border_color, 2.);
+        ////ctx.fill(knob_circle, &knob_gradient);
+
+        //// TODO: Paint the on and off labels, once text is rendered
+        ////// paint on/off label
+        ////self.paint_labels(ctx, env, switch_width);
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::Switch(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::framebuffer::{draw, Framebuffer, SIZE};
+    use crate::tests::harness::Harness;
+    use crate::tests::helpers::lifecycle_ctx;
+    use crate::MouseEvent;
+
+    /// Touch the switch, and lift the touch at `up`.
+    fn tap(harness: &mut Harness<bool, Switch<bool>>, up: Point) {
+        harness.event(Event::MouseDown(MouseEvent::new(Point::new(10, 10))));
+        harness.event(Event::MouseUp(MouseEvent::new(up)));
+    }
+
+    #[test]
+    fn tap_toggles() {
+        Harness::create_simple(false, Switch::new, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            tap(harness, Point::new(10, 10));
+            assert!(*harness.data());
+            harness.inspect_state(|state| assert!(!state.is_active));
+            tap(harness, Point::new(10, 10));
+            assert!(!*harness.data());
+        });
+    }
+
+    #[test]
+    fn cancelled_touch_does_not_toggle() {
+        Harness::create_simple(false, Switch::new, |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            // The touch is lifted outside the switch
+            tap(harness, Point::new(250, 10));
+            assert!(!*harness.data());
+        });
+    }
+
+    #[test]
+    fn knob_slides_on_anim_frame() {
+        let env = theme::init();
+        let geometry = Geometry::new(&env);
+        let mut switch = Switch::new();
+        let mut ctx = lifecycle_ctx();
+        switch.set_knob_x(geometry.off_pos as ScreenFactor, &geometry);
+        switch.animation_in_progress = true;
+
+        let frame = LifeCycle::AnimFrame(50_000_000);
+        switch.lifecycle(&mut ctx, &frame, &true, &env);
+        assert!(switch.knob_pos.x > geometry.off_pos && switch.knob_pos.x < geometry.on_pos);
+        for _ in 0..4 {
+            switch.lifecycle(&mut ctx, &frame, &true, &env);
+        }
+        assert_eq!(switch.knob_pos.x, geometry.on_pos);
+        assert!(!switch.animation_in_progress);
+    }
+
+    /// Lay out and paint a switch that is `on` over the whole framebuffer.
+    fn paint(fb: &Framebuffer, on: bool) {
+        Harness::create_with_render(on, Switch::new, fb.size(), |harness| {
+            harness.send_initial_events();
+            harness.just_layout();
+            harness.paint();
+        }, Some(draw));
+    }
+
+    #[test]
+    fn knob_is_painted_over_the_track() {
+        let fb = Framebuffer::lock();
+        let off_pos = Geometry::new(&theme::init()).off_pos as usize;
+        paint(&fb, false);
+        assert!(fb.row(SIZE / 2)[off_pos] >= 0xbf);
+        // The track has rounded ends
+        assert_eq!(fb.row(0)[0], 0);
+
+        // When on, the knob is past the framebuffer and the track shows
+        fb.clear();
+        paint(&fb, true);
+        assert!(fb.row(SIZE / 2)[off_pos] < 0x5c);
+    }
+}
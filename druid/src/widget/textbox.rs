@@ -0,0 +1,751 @@
+// Copyright 2018 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A textbox widget.
+
+use core::ops::Range; ////
+use core::time::Duration; ////
+////use std::time::Duration;
+
+use crate::{
+    BoxConstraints, Env, Event, EventCtx, LayoutCtx, LifeCycle, ////
+    ////Application, BoxConstraints, Cursor, Env, Event, EventCtx, HotKey, KbKey, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, TimerToken, UpdateCtx, Widget, ////
+    ////LifeCycleCtx, PaintCtx, Selector, SysMods, TimerToken, UpdateCtx, Widget,
+};
+
+use crate::{Line, Point, Rect, RoundedRect, Size}; ////
+////use crate::kurbo::{Affine, Line, Point, RoundedRect, Size, Vec2};
+/* ////
+use crate::piet::{
+    FontBuilder, PietText, PietTextLayout, RenderContext, Text, TextLayout, TextLayoutBuilder,
+};
+*/ ////
+use crate::theme;
+
+use crate::text::{
+    EditAction, EditableText, Editor, MouseAction, Selection, ////
+    ////movement, offset_for_delete_backwards, BasicTextInput, EditAction, EditableText, MouseAction,
+    ////Movement, Selection, TextInput,
+};
+use crate::{Data, ScreenCoord, String, WidgetId, WidgetType}; ////
+use crate::widget::{Keyboard, Roller}; ////
+use heapless::ArrayLength; ////
+
+const BORDER_WIDTH: ScreenCoord = 1; ////
+////const BORDER_WIDTH: f64 = 1.;
+const PADDING_TOP: ScreenCoord = 5; ////
+////const PADDING_TOP: f64 = 5.;
+const PADDING_LEFT: ScreenCoord = 4; ////
+////const PADDING_LEFT: f64 = 4.;
+/// Width of each character, until text layouts can be measured ////
+const CHAR_WIDTH: u16 = 10; ////
+
+// we send ourselves this when we want to reset blink, which must be done in event.
+////const RESET_BLINK: Selector = Selector::new("druid-builtin.reset-textbox-blink");
+const CURSOR_BLINK_DURATION: Duration = Duration::from_millis(500);
+
+/// A widget that allows user text input.
+///
+/// The text is edited by touch, to move the caret or select, and by the
+/// on-screen `Keyboard` added with `with_keyboard`, or on tiny screens by the ////
+/// `Roller` of characters added with `with_roller`.
+#[derive(Clone)] ////
+////#[derive(Debug, Clone)]
+pub struct TextBox<T> { ////
+////pub struct TextBox {
+    id: WidgetId, ////
+    placeholder: String,
+    width: ScreenCoord, ////
+    ////width: f64,
+    hscroll_offset: u16, ////
+    ////hscroll_offset: f64,
+    editor: Editor, ////
+    ////selection: Selection,
+    cursor_timer: TimerToken,
+    cursor_on: bool,
+    keyboard: Option<Keyboard<T>>, ////
+    roller: Option<Roller<T>>, ////
+    access: TextAccess<T>, ////
+}
+
+/// Reads and edits the text within the data of a `TextBox` or `Keyboard`. ////
+pub(crate) struct TextAccess<T> {
+    /// Reads the text.
+    pub(crate) text: fn(&T) -> &str,
+    /// Replaces a range of the text, returning the length inserted.
+    pub(crate) edit: fn(&mut T, Range<usize>, &str) -> usize,
+}
+
+impl<N: ArrayLength<u8>> TextAccess<heapless::String<N>> { ////
+    /// Access a `heapless::String` as the text.
+    pub(crate) fn string() -> Self {
+        TextAccess {
+            text: |data| data.as_str(),
+            edit: |data, range, new| data.edit(range, new),
+        }
+    }
+}
+
+impl<T> TextAccess<T> { ////
+    /// Borrow the text in `data` for editing.
+    pub(crate) fn bind<'a>(&self, data: &'a mut T) -> BoundText<'a, T> {
+        BoundText { data, access: *self }
+    }
+}
+
+impl<T> Clone for TextAccess<T> { ////
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TextAccess<T> {} ////
+
+/// The text in the data of a `TextBox` or `Keyboard`, borrowed for editing. ////
+pub(crate) struct BoundText<'a, T> {
+    data: &'a mut T,
+    access: TextAccess<T>,
+}
+
+impl<'a, T> EditableText for BoundText<'a, T> { ////
+    fn as_str(&self) -> &str {
+        (self.access.text)(self.data)
+    }
+
+    fn edit(&mut self, range: Range<usize>, new: &str) -> usize {
+        (self.access.edit)(self.data, range, new)
+    }
+}
+
+impl<N: ArrayLength<u8> + 'static> TextBox<heapless::String<N>> { ////
+////impl TextBox {
+    /* ////
+    /// Perform an `EditAction`. The payload *must* be an `EditAction`.
+    pub const PERFORM_EDIT: Selector<EditAction> =
+        Selector::new("druid-builtin.textbox.perform-edit");
+    */ ////
+
+    /// Create a new TextBox widget
+    pub fn new() -> Self { ////
+    ////pub fn new() -> TextBox {
+        Self {
+            id: WidgetId::next(), ////
+            width: 0, ////
+            ////width: 0.0,
+            hscroll_offset: 0, ////
+            ////hscroll_offset: 0.,
+            editor: Editor::new(), ////
+            ////selection: Selection::caret(0),
+            cursor_timer: TimerToken::INVALID,
+            cursor_on: false,
+            placeholder: String::new(),
+            keyboard: None, ////
+            roller: None, ////
+            access: TextAccess::string(), ////
+        }
+    }
+}
+
+impl<T> TextBox<T> { ////
+    /// Builder-style method to set the `TextBox`'s placeholder text.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Builder-style method to show an on-screen `Keyboard` below the text, ////
+    /// which edits the text at the caret.
+    pub fn with_keyboard(mut self) -> Self { ////
+        self.keyboard = Some(Keyboard::hosted(self.id, self.access));
+        self
+    }
+
+    /// Builder-style method to show a `Roller` of characters below the text, ////
+    /// with a backspace key beside it. Tapping the middle row of the roller
+    /// inserts its option at the caret.
+    ///
+    /// The options are separated by `\n`, such as `ROLLER_DIGITS` for PINs
+    /// and alarm times.
+    pub fn with_roller(mut self, options: &'static str) -> Self { ////
+        self.roller = Some(Roller::hosted(self.id, options).with_wraparound(true));
+        self
+    }
+
+    /* ////
+    #[deprecated(since = "0.5.0", note = "Use TextBox::new instead")]
+    #[doc(hidden)]
+    pub fn raw() -> TextBox {
+        Self::new()
+    }
+
+    /// Calculate the PietTextLayout from the given text, font, and font size
+    fn get_layout(&self, piet_text: &mut PietText, text: &str, env: &Env) -> PietTextLayout {
+        let font_name = env.get(theme::FONT_NAME);
+        let font_size = env.get(theme::TEXT_SIZE_NORMAL);
+        // TODO: caching of both the format and the layout
+        let font = piet_text
+            .new_font_by_name(font_name, font_size)
+            .build()
+            .unwrap();
+
+        piet_text
+            .new_text_layout(&font, &text.to_string(), std::f64::INFINITY)
+            .build()
+            .unwrap()
+    }
+    */ ////
+
+    /// Return the active edge of the current selection or cursor.
+    // TODO: is this the right name?
+    fn cursor(&self) -> usize {
+        self.editor.cursor() ////
+        ////self.selection.end
+    }
+
+    /// Perform `edit_action` on the text in `data`. ////
+    fn do_edit_action(&mut self, edit_action: EditAction, data: &mut T) {
+        self.editor.do_edit_action(&edit_action, &mut self.access.bind(data)); ////
+        /* //// Moved to `text::Editor`
+        match edit_action {
+            EditAction::Insert(chars) | EditAction::Paste(chars) => self.insert(text, &chars),
+            ...
+        }
+        */ ////
+    }
+
+    /// For a given point, returns the corresponding offset (in bytes) of
+    /// the character closest to that point.
+    fn offset_for_point(&self, point: Point, text: &str) -> usize { ////
+    ////fn offset_for_point(&self, point: Point, layout: &PietTextLayout) -> usize {
+        // Translating from screenspace to the text.
+        // We need to account for hscroll_offset state and TextBox's padding.
+        let x = (point.x as u16 + self.hscroll_offset).saturating_sub(PADDING_LEFT as u16); ////
+        ////let translated_point = Point::new(point.x + self.hscroll_offset - PADDING_LEFT, point.y);
+        let column = ((x + CHAR_WIDTH / 2) / CHAR_WIDTH) as usize; ////
+        text.char_indices().nth(column).map(|(offset, _)| offset).unwrap_or(text.len()) ////
+        ////let hit_test = layout.hit_test_point(translated_point);
+        ////hit_test.metrics.text_position
+    }
+
+    /// Given an offset (in bytes) of a valid character, return
+    /// the corresponding x coordinate of that character within the text.
+    fn x_for_offset(&self, text: &str, offset: usize) -> u16 { ////
+    ////fn x_for_offset(&self, layout: &PietTextLayout, offset: usize) -> f64 {
+        let chars = text.get(..offset).map(|text| text.chars().count()).unwrap_or(0); ////
+        (chars as u16).saturating_mul(CHAR_WIDTH) ////
+        /* ////
+        if let Some(position) = layout.hit_test_text_position(offset) {
+            position.point.x
+        } else {
+            //TODO: what is the correct fallback here?
+            0.0
+        }
+        */ ////
+    }
+
+    /// Calculate a stateful scroll offset
+    fn update_hscroll(&mut self, text: &str) { ////
+    ////fn update_hscroll(&mut self, layout: &PietTextLayout) {
+        let cursor_x = self.x_for_offset(text, self.cursor()); ////
+        ////let cursor_x = self.x_for_offset(layout, self.cursor());
+        let overall_text_width = self.x_for_offset(text, text.len()); ////
+        ////let overall_text_width = layout.width();
+
+        let padding = PADDING_LEFT as u16 * 2; ////
+        ////let padding = PADDING_LEFT * 2.;
+        let width = self.width as u16; ////
+        if overall_text_width < width.saturating_sub(padding) { ////
+        ////if overall_text_width < self.width {
+            // There's no offset if text is smaller than text box
+            //
+            // [***I*  ]
+            // ^
+            self.hscroll_offset = 0; ////
+            ////self.hscroll_offset = 0.;
+        } else if cursor_x > (width + self.hscroll_offset).saturating_sub(padding) { ////
+        ////} else if cursor_x > self.width + self.hscroll_offset - padding {
+            // If cursor goes past right side, bump the offset
+            //       ->
+            // **[****I]****
+            //   ^
+            self.hscroll_offset = (cursor_x + padding).saturating_sub(width); ////
+            ////self.hscroll_offset = cursor_x - self.width + padding;
+        } else if cursor_x < self.hscroll_offset {
+            // If cursor goes past left side, match the offset
+            //    <-
+            // **[I****]****
+            //   ^
+            self.hscroll_offset = cursor_x
+        }
+    }
+
+    fn reset_cursor_blink(&mut self, ctx: &mut EventCtx) {
+        self.cursor_on = true;
+        self.cursor_timer = ctx.request_timer(CURSOR_BLINK_DURATION);
+    }
+
+    /// The area of the text field, above the keyboard or roller. ////
+    fn field_rect(&self, env: &Env) -> Rect {
+        Size::new(self.width, theme::BORDERED_WIDGET_HEIGHT.resolve(env)).to_rect()
+    }
+
+    /// The area of the keyboard, below the text field. ////
+    fn keyboard_rect(&self, size: Size, env: &Env) -> Rect {
+        Rect::new(0, self.field_rect(env).y1, size.width, size.height)
+    }
+
+    /// The areas of the roller and its backspace key, below the text field. ////
+    fn roller_rects(&self, size: Size, env: &Env) -> (Rect, Rect) {
+        let area = self.keyboard_rect(size, env);
+        let key_x = area.x1.saturating_sub(theme::BASIC_WIDGET_HEIGHT.resolve(env));
+        (Rect::new(area.x0, area.y0, key_x, area.y1), Rect::new(key_x, area.y0, area.x1, area.y1))
+    }
+}
+
+impl<T: Data> Widget<T> for TextBox<T> { ////
+////impl Widget<String> for TextBox {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) { ////
+    ////fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut String, env: &Env) {
+        // Guard against external changes in data?
+        self.editor.constrain_to(&self.access.bind(data)); ////
+        ////self.selection = self.selection.constrain_to(data);
+
+        ////let mut text_layout = self.get_layout(&mut ctx.text(), &data, env);
+        let mut edit_action = None;
+
+        // Touches on the keyboard edit the text at the caret ////
+        let keyboard_rect = self.keyboard_rect(ctx.size(), env); ////
+        if let Some(keyboard) = &mut self.keyboard { ////
+            if let Some(action) = keyboard.key_event(ctx, event, keyboard_rect) {
+                edit_action = Some(action);
+            }
+        }
+        let (roller_rect, backspace_rect) = self.roller_rects(ctx.size(), env); ////
+        if let Some(roller) = &mut self.roller { ////
+            if let Some(option) = roller.roll_event(ctx, event, roller_rect).and_then(|index| roller.option(index)) {
+                edit_action = Some(EditAction::Insert(String::from(option)));
+            }
+            if let Event::MouseDown(mouse) = event {
+                if backspace_rect.winding(mouse.pos) != 0 {
+                    ctx.set_active(true);
+                    edit_action = Some(EditAction::Backspace);
+                }
+            }
+        }
+
+        match event {
+            Event::MouseDown(mouse) if self.field_rect(env).winding(mouse.pos) != 0 => { ////
+            ////Event::MouseDown(mouse) => {
+                ////ctx.request_focus();
+                ctx.set_active(true);
+
+                ////if !mouse.focus {
+                let cursor_offset = self.offset_for_point(mouse.pos, (self.access.text)(data)); ////
+                ////let cursor_offset = self.offset_for_point(mouse.pos, &text_layout);
+                edit_action = Some(EditAction::Click(MouseAction {
+                    row: 0,
+                    column: cursor_offset,
+                    extend: false, ////
+                    ////mods: mouse.mods,
+                }));
+                ////}
+
+                ctx.request_paint();
+            }
+            Event::MouseMove(mouse) => {
+                ////ctx.set_cursor(&Cursor::IBeam);
+                if ctx.is_active() && self.keyboard.as_ref().map_or(true, |k| !k.is_pressed()) ////
+                    && self.roller.as_ref().map_or(true, |r| !r.is_touched()) {
+                ////if ctx.is_active() {
+                    let cursor_offset = self.offset_for_point(mouse.pos, (self.access.text)(data)); ////
+                    ////let cursor_offset = self.offset_for_point(mouse.pos, &text_layout);
+                    edit_action = Some(EditAction::Drag(MouseAction {
+                        row: 0,
+                        column: cursor_offset,
+                        extend: true, ////
+                        ////mods: mouse.mods,
+                    }));
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(_) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    ctx.request_paint();
+                }
+            }
+            Event::Timer(id) => {
+                if *id == self.cursor_timer {
+                    self.cursor_on = !self.cursor_on;
+                    ctx.request_paint();
+                    self.cursor_timer = ctx.request_timer(CURSOR_BLINK_DURATION);
+                }
+            }
+            /* //// No clipboard, commands or key events yet
+            Event::Command(ref cmd)
+                if ctx.is_focused()
+                    && (cmd.is(crate::commands::COPY) || cmd.is(crate::commands::CUT)) =>
+            {
+                if let Some(text) = data.slice(self.selection.range()) {
+                    Application::global().clipboard().put_string(text);
+                }
+                if !self.selection.is_caret() && cmd.is(crate::commands::CUT) {
+                    edit_action = Some(EditAction::Delete);
+                }
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(RESET_BLINK) => self.reset_cursor_blink(ctx),
+            Event::Command(cmd) if cmd.is(TextBox::PERFORM_EDIT) => {
+                let edit = cmd.get_unchecked(TextBox::PERFORM_EDIT);
+                self.do_edit_action(edit.to_owned(), data);
+            }
+            Event::Paste(ref item) => {
+                if let Some(string) = item.get_string() {
+                    edit_action = Some(EditAction::Paste(string));
+                    ctx.request_paint();
+                }
+            }
+            Event::KeyDown(key_event) => {
+                let event_handled = match key_event {
+                    // Tab and shift+tab
+                    k_e if HotKey::new(None, KbKey::Tab).matches(k_e) => {
+                        ctx.focus_next();
+                        true
+                    }
+                    k_e if HotKey::new(SysMods::Shift, KbKey::Tab).matches(k_e) => {
+                        ctx.focus_prev();
+                        true
+                    }
+                    k_e if HotKey::new(None, KbKey::Enter).matches(k_e) => {
+                        // 'enter' should do something, maybe?
+                        // but for now we are suppressing it, because we don't want
+                        // newlines.
+                        true
+                    }
+                    _ => false,
+                };
+
+                if !event_handled {
+                    edit_action = BasicTextInput::new().handle_event(key_event);
+                }
+
+                ctx.request_paint();
+            }
+            */ ////
+            _ => (),
+        }
+
+        if let Some(edit_action) = edit_action {
+            let is_select_all = matches!(edit_action, EditAction::SelectAll);
+
+            self.do_edit_action(edit_action, data);
+            self.reset_cursor_blink(ctx);
+
+            if !is_select_all {
+                ////text_layout = self.get_layout(&mut ctx.text(), &data, env);
+                self.update_hscroll((self.access.text)(data)); ////
+                ////self.update_hscroll(&text_layout);
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &T, env: &Env) { ////
+    ////fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &String, _env: &Env) {
+        if let (LifeCycle::AnimFrame(interval), Some(roller)) = (event, &mut self.roller) { ////
+            if roller.settle(*interval, theme::BASIC_WIDGET_HEIGHT.resolve(env)) {
+                ctx.request_anim_frame();
+            }
+            ctx.request_paint();
+        }
+        match event {
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            // an open question: should we be able to schedule timers here?
+            LifeCycle::FocusChanged(true) => { ////
+                self.cursor_on = true; ////
+                ctx.request_paint(); ////
+            } ////
+            ////LifeCycle::FocusChanged(true) => ctx.submit_command(RESET_BLINK, ctx.widget_id()),
+            _ => (),
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, _data: &T, _env: &Env) { ////
+    ////fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &String, _data: &String, _env: &Env) {
+        ctx.request_paint();
+    }
+
+    fn layout(
+        &mut self,
+        _layout_ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &T, ////
+        ////_data: &String,
+        env: &Env,
+    ) -> Size {
+        let mut width = theme::WIDE_WIDGET_WIDTH.resolve(env); ////
+        ////let width = env.get(theme::WIDE_WIDGET_WIDTH);
+        let mut height = theme::BORDERED_WIDGET_HEIGHT.resolve(env); ////
+        ////let height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        if self.keyboard.is_some() { ////
+            // The keyboard is as wide as possible, like the screen
+            if bc.is_width_bounded() {
+                width = bc.max().width;
+            }
+            height = height.saturating_add(Keyboard::<T>::height(env));
+        }
+        if let Some(roller) = &self.roller { ////
+            height = height.saturating_add(roller.height(env));
+        }
+
+        let size = bc.constrain(Size::new(width, height)); ////
+        ////let size = bc.constrain((width, height));
+        self.width = size.width;
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) { ////
+    ////fn paint(&mut self, ctx: &mut PaintCtx, data: &String, env: &Env) {
+        // Guard against changes in data following `event`
+        let text = (self.access.text)(data); ////
+        let content = if text.is_empty() { ////
+        ////let content = if data.is_empty() {
+            &self.placeholder
+        } else {
+            text ////
+            ////data
+        };
+        //// TODO: Render with LVGL
+        /*
+        lv_textarea_set_one_line(ta, true);                             //  Single line with horizontal scrolling
+        lv_textarea_set_text(ta, text);
+        lv_textarea_set_placeholder_text(ta, placeholder);
+        lv_textarea_set_cursor_pos(ta, cursor);                         //  In characters, not bytes
+        lv_textarea_set_text_sel(ta, true);
+        */
+
+        let selection = if text.is_empty() { Selection::caret(0) } else { self.editor.selection() }; //// Constrained in `event`
+        ////self.selection = self.selection.constrain_to(content);
+
+        let font_size = theme::TEXT_SIZE_NORMAL.resolve(env) * 14.; //// Montserrat 14 is the LVGL default font
+        ////let font_size = env.get(theme::TEXT_SIZE_NORMAL);
+        let height = theme::BORDERED_WIDGET_HEIGHT.resolve(env); ////
+        ////let height = env.get(theme::BORDERED_WIDGET_HEIGHT);
+        let background_color = theme::BACKGROUND_LIGHT.resolve(env); ////
+        ////let background_color = env.get(theme::BACKGROUND_LIGHT);
+        let selection_color = theme::SELECTION_COLOR.resolve(env); ////
+        ////let selection_color = env.get(theme::SELECTION_COLOR);
+        ////let selection_text_color = env.get(theme::SELECTION_TEXT_COLOR);
+        ////let text_color = env.get(theme::LABEL_COLOR);
+        ////let placeholder_color = env.get(theme::PLACEHOLDER_COLOR);
+        let cursor_color = theme::CURSOR_COLOR.resolve(env); ////
+        ////let cursor_color = env.get(theme::CURSOR_COLOR);
+
+        let is_focused = ctx.is_focused() || self.cursor_timer != TimerToken::INVALID; //// Or focused once touched
+        ////let is_focused = ctx.is_focused();
+
+        let border_color = if is_focused {
+            theme::PRIMARY_LIGHT.resolve(env) ////
+            ////env.get(theme::PRIMARY_LIGHT)
+        } else {
+            theme::BORDER_DARK.resolve(env) ////
+            ////env.get(theme::BORDER_DARK)
+        };
+
+        // Paint the background
+        let clip_rect = RoundedRect::from_rect( ////
+            Size::new(self.width, height).to_rect(), ////
+            theme::TEXTBOX_BORDER_RADIUS.resolve(env), ////
+        ); ////
+        ////let clip_rect = Size::new(self.width - BORDER_WIDTH, height)
+        ////    .to_rect()
+        ////    .inset(-BORDER_WIDTH / 2.0)
+        ////    .to_rounded_rect(env.get(theme::TEXTBOX_BORDER_RADIUS));
+
+        ctx.fill(clip_rect, &background_color);
+
+        // Render text, selection, and cursor inside a clip
+        let hscroll_offset = self.hscroll_offset; ////
+        let cursor_x = self.x_for_offset(content, self.cursor()); ////
+        let left_offset = self.x_for_offset(content, selection.min()); ////
+        let right_offset = self.x_for_offset(content, selection.max()); ////
+        let cursor_on = self.cursor_on; ////
+        ctx.with_save(|rc| {
+            rc.clip(clip_rect.rect()); ////
+            ////rc.clip(clip_rect);
+
+            // Calculate layout
+            ////let text_layout = self.get_layout(&mut rc.text(), &content, env);
+
+            // Shift everything inside the clip by the hscroll_offset
+            let to_screen = |x: u16| (x + PADDING_LEFT as u16).saturating_sub(hscroll_offset).min(ScreenCoord::MAX as u16) as ScreenCoord; ////
+            ////rc.transform(Affine::translate((-self.hscroll_offset, 0.)));
+
+            // Layout, measure, and draw text
+            let text_height = (font_size * 0.8) as ScreenCoord; ////
+            ////let text_height = font_size * 0.8;
+            let text_pos = Point::new(PADDING_LEFT, text_height + PADDING_TOP); ////
+            ////let text_pos = Point::new(0.0 + PADDING_LEFT, text_height + PADDING_TOP);
+            /* //// TODO: Draw text
+            let color = if data.is_empty() {
+                &placeholder_color
+            } else {
+                &text_color
+            };
+
+            rc.draw_text(&text_layout, text_pos, color);
+            */ ////
+
+            // Draw selection rect
+            if !selection.is_caret() {
+                ////let (left, right) = (self.selection.min(), self.selection.max());
+                ////let left_offset = self.x_for_offset(&text_layout, left);
+                ////let right_offset = self.x_for_offset(&text_layout, right);
+
+                let selection_rect = Rect::new( ////
+                    to_screen(left_offset).saturating_sub(1), ////
+                    PADDING_TOP.saturating_sub(2), ////
+                    to_screen(right_offset).saturating_add(1), ////
+                    PADDING_TOP.saturating_add(font_size as ScreenCoord + 2), ////
+                ).to_rounded_rect(1); ////
+                ////let selection_width = right_offset - left_offset;
+                ////let selection_pos = Point::new(left_offset + PADDING_LEFT - 1., PADDING_TOP - 2.);
+                ////let selection_rect = RoundedRect::from_origin_size(
+                ////    selection_pos,
+                ////    Size::new(selection_width + 2., font_size + 4.).to_vec2(),
+                ////    1.,
+                ////);
+                rc.fill(selection_rect, &selection_color);
+
+                // Draw selection text
+                ////rc.clip(selection_rect);
+                ////rc.draw_text(&text_layout, text_pos, &selection_text_color);
+            }
+
+            // Paint the cursor if focused and there's no selection
+            if is_focused && cursor_on && selection.is_caret() { ////
+            ////if is_focused && self.cursor_on && self.selection.is_caret() {
+                ////let cursor_x = self.x_for_offset(&text_layout, self.cursor());
+                let x = to_screen(cursor_x); ////
+                let top = text_pos.y.saturating_sub(font_size as ScreenCoord).saturating_add(2); ////
+                let line = Line::new(Point::new(x, top), Point::new(x, top.saturating_add(font_size as ScreenCoord + 2))); ////
+                ////let xy = text_pos + Vec2::new(cursor_x, 2. - font_size);
+                ////let x2y2 = xy + Vec2::new(0., font_size + 2.);
+                ////let line = Line::new(xy, x2y2);
+
+                rc.stroke(line, &cursor_color, 1);
+            }
+        });
+
+        // Paint the border
+        ctx.stroke(clip_rect, &border_color, BORDER_WIDTH);
+
+        // Paint the keyboard below ////
+        let keyboard_rect = self.keyboard_rect(ctx.size(), env); ////
+        if let Some(keyboard) = &self.keyboard { ////
+            keyboard.paint_keys(ctx, keyboard_rect, env);
+        }
+        // Or the roller and its backspace key ////
+        let (roller_rect, backspace_rect) = self.roller_rects(ctx.size(), env); ////
+        if let Some(roller) = &self.roller { ////
+            roller.paint_rows(ctx, roller_rect, env);
+            let backspace_rect = backspace_rect.to_rounded_rect(theme::TEXTBOX_BORDER_RADIUS.resolve(env));
+            ctx.fill(backspace_rect, &theme::BACKGROUND_LIGHT.resolve(env));
+            ctx.stroke(backspace_rect, &theme::BORDER_DARK.resolve(env), BORDER_WIDTH);
+            //// TODO: Draw the backspace symbol
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::TextBox(self)
+    }
+}
+
+impl<N: ArrayLength<u8> + 'static> Default for TextBox<heapless::String<N>> { ////
+////impl Default for TextBox {
+    fn default() -> Self {
+        TextBox::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::event_ctx;
+    use crate::widget::ROLLER_DIGITS;
+    use crate::MouseEvent;
+    use heapless::consts::U32;
+
+    /// Touches are mapped to the characters under them, and the text
+    /// scrolls to keep the caret visible.
+    #[test]
+    fn caret_follows_touch() {
+        let mut widget = TextBox::<heapless::String<U32>>::new();
+        widget.width = 100;
+        let text = "h\u{00e9}llo world";
+        // 4 pixels of padding, and 10 pixels for each character
+        assert_eq!(widget.offset_for_point(Point::new(0, 10), text), 0);
+        assert_eq!(widget.offset_for_point(Point::new(20, 10), text), 3);
+        assert_eq!(widget.offset_for_point(Point::new(99, 10), text), 11);
+        assert_eq!(widget.offset_for_point(Point::new(200, 10), text), 12);
+        assert_eq!(widget.x_for_offset(text, 3), 20);
+
+        widget.editor.do_edit_action(&EditAction::Insert(String::from(text)), &mut heapless::String::<U32>::new());
+        widget.update_hscroll(text);
+        assert_eq!(widget.hscroll_offset, 18);
+        assert_eq!(widget.offset_for_point(Point::new(4, 10), text), 3);
+    }
+
+    #[test]
+    fn keyboard_edits_at_caret() {
+        let mut widget = TextBox::<heapless::String<U32>>::new().with_keyboard();
+        let mut data = heapless::String::<U32>::from("wifi");
+        widget.do_edit_action(EditAction::Click(MouseAction { row: 0, column: 2, extend: false }), &mut data);
+        let action = widget.keyboard.as_mut().unwrap().key_action('\u{232B}');
+        widget.do_edit_action(action.unwrap(), &mut data);
+        let action = widget.keyboard.as_mut().unwrap().key_action('-');
+        widget.do_edit_action(action.unwrap(), &mut data);
+        assert_eq!("w-fi", data);
+        assert_eq!(widget.cursor(), 2);
+    }
+
+    #[test]
+    fn roller_enters_digits() {
+        let env = theme::init();
+        let mut widget = TextBox::<heapless::String<U32>>::new().with_roller(ROLLER_DIGITS);
+        widget.width = 240;
+        let mut data = heapless::String::<U32>::from("12");
+        widget.do_edit_action(EditAction::Click(MouseAction { row: 0, column: 2, extend: false }), &mut data);
+        let (roller_rect, backspace_rect) = widget.roller_rects(Size::new(240, 156), &env);
+        assert_eq!(roller_rect, Rect::new(0, 48, 204, 156));
+        assert_eq!(backspace_rect, Rect::new(204, 48, 240, 156));
+
+        // Tap the row below the middle to select it, then the middle row to insert it
+        let mut ctx = event_ctx();
+        let roller = widget.roller.as_mut().unwrap();
+        let mut tap = |y| {
+            let mouse = MouseEvent::new(Point::new(100, y));
+            roller.roll_event(&mut ctx, &Event::MouseDown(mouse.clone()), roller_rect);
+            roller.roll_event(&mut ctx, &Event::MouseUp(mouse), roller_rect)
+        };
+        assert_eq!(tap(140), None);
+        let index = tap(100).unwrap();
+        let option = widget.roller.as_ref().unwrap().option(index).unwrap();
+        widget.do_edit_action(EditAction::Insert(String::from(option)), &mut data);
+        assert_eq!("121", data);
+        assert_eq!(widget.cursor(), 3);
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A widget that can dynamically switch between one of many views.
+
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx,
+    Point, Rect, Size, UpdateCtx, Widget, WidgetPod,
+};
+use crate::{BoxedWidget, Vec, WidgetId, WidgetType}; ////
+
+type ChildPicker<T> = fn(&T, &Env) -> usize; ////
+////type ChildPicker<T, U> = dyn Fn(&T, &Env) -> U;
+////type ChildBuilder<T, U> = dyn Fn(&U, &T, &Env) -> Box<dyn Widget<T>>;
+
+/// A widget that can switch dynamically between one of many views depending
+/// on application state.
+///
+/// The candidate views are registered in the static `Widgets` up front with
+/// [`with_child`], so switching never allocates. Only the active one is sent
+/// events and lifecycle events, laid out and painted, so a view receives
+/// `WidgetAdded` when it's first shown, and the hidden views are left out of
+/// the focus chain. All the views are sent `WidgetRemoved`.
+///
+/// [`with_child`]: #method.with_child
+#[derive(Clone)] ////
+pub struct ViewSwitcher<T> { ////
+////pub struct ViewSwitcher<T, U> {
+    id: WidgetId, ////
+    child_picker: ChildPicker<T>, ////
+    ////child_picker: Box<ChildPicker<T, U>>,
+    ////child_builder: Box<ChildBuilder<T, U>>,
+    children: Vec<WidgetPod<T, BoxedWidget<T>>>, ////
+    ////active_child: Option<WidgetPod<T, Box<dyn Widget<T>>>>,
+    active_child_id: Option<usize>, ////
+    ////active_child_id: Option<U>,
+}
+
+impl<T: Data> ViewSwitcher<T> { ////
+////impl<T: Data, U: Data> ViewSwitcher<T, U> {
+    /// Create a new view switcher.
+    ///
+    /// The `child_picker` closure is called every time the application data changes.
+    /// It returns the index of the child to show, in the order the children were
+    /// added. If the value it returns is the same as the one it returned during the
+    /// previous data change, nothing happens. An index past the last child shows
+    /// nothing.
+    pub fn new(child_picker: ChildPicker<T>) -> Self { ////
+    ////pub fn new(
+        ////child_picker: impl Fn(&T, &Env) -> U + 'static,
+        ////child_builder: impl Fn(&U, &T, &Env) -> Box<dyn Widget<T>> + 'static,
+    ////) -> Self {
+        Self {
+            id: WidgetId::next(), ////
+            child_picker, ////
+            ////child_picker: Box::new(child_picker),
+            ////child_builder: Box::new(child_builder),
+            children: Vec::new(), ////
+            ////active_child: None,
+            active_child_id: None,
+        }
+    }
+
+    /// Builder-style method to add a candidate child, at the next index.
+    ///
+    /// At most the capacity of a `Vec` of children can be added; further children are ignored.
+    pub fn with_child(mut self, child: impl Widget<T> + 'static + Clone) -> Self {
+        self.children.push(WidgetPod::new(child).boxed()).ok();
+        self
+    }
+
+    fn active_child(&mut self) -> Option<&mut WidgetPod<T, BoxedWidget<T>>> {
+        let children = &mut self.children;
+        self.active_child_id.and_then(move |index| children.get_mut(index))
+    }
+}
+
+impl<T: Data> Widget<T> for ViewSwitcher<T> { ////
+////impl<T: Data, U: Data> Widget<T> for ViewSwitcher<T, U> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) } ////
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        if let Some(child) = self.active_child() { ////
+        ////if let Some(child) = self.active_child.as_mut() {
+            child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.active_child_id = Some((self.child_picker)(data, env)); ////
+            ////let child_id = (self.child_picker)(data, env);
+            ////self.active_child = Some(WidgetPod::new((self.child_builder)(&child_id, data, env)));
+            ////self.active_child_id = Some(child_id);
+        }
+        if let LifeCycle::WidgetRemoved = event { ////
+            for child in self.children.iter_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        } else if let Some(child) = self.active_child() { ////
+        ////if let Some(child) = self.active_child.as_mut() {
+            child.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let child_id = (self.child_picker)(data, env);
+        if Some(child_id) != self.active_child_id { ////
+        ////// Safe to unwrap because self.active_child_id should not be empty
+        ////if !child_id.same(self.active_child_id.as_ref().unwrap()) {
+            ////self.active_child = Some(WidgetPod::new((self.child_builder)(&child_id, data, env)));
+            self.active_child_id = Some(child_id);
+            ctx.children_changed();
+            ctx.request_paint(); ////
+        ////} else if let Some(child) = self.active_child.as_mut() {
+        } ////
+        // Because the new child has not yet been initialized, we have to skip the update after switching.
+        // A child shown before catches up with the data it missed while hidden.
+        if let Some(child) = self.active_child().filter(|child| child.is_initialized()) { ////
+            child.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        match self.active_child() { ////
+        ////match self.active_child {
+            Some(child) => { ////
+            ////Some(ref mut child) => {
+                let size = child.layout(ctx, bc, data, env);
+                child.set_layout_rect(ctx, data, env, Rect::from_origin_size(Point::ORIGIN, size));
+                size
+            }
+            None => bc.max(),
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        lv_obj_set_hidden(child, index != self.active_child_id);  //  Show only the active child
+        */
+        if let Some(child) = self.active_child() { ////
+        ////if let Some(ref mut child) = self.active_child {
+            child.paint_raw(ctx, data, env);
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> { ////
+        WidgetType::ViewSwitcher(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::harness::Harness;
+    use crate::widget::RadioGroup;
+    use ::core::cell::Cell;
+    use ::core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// The child to show, as the data of the tests can't change.
+    static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+    #[test]
+    fn switching_adds_and_lays_out_the_child() {
+        let ids = Cell::new(None);
+        let widget = || {
+            let (first, second) = (RadioGroup::new([("first", ())]), RadioGroup::new([("second", ())]));
+            ids.set(Some((first.id().unwrap(), second.id().unwrap())));
+            ViewSwitcher::new(|_, _| ACTIVE.load(Ordering::Relaxed))
+                .with_child(first)
+                .with_child(second)
+        };
+        ACTIVE.store(0, Ordering::Relaxed);
+        Harness::create_simple((), widget, |harness| {
+            let (first, second) = ids.get().unwrap();
+            harness.send_initial_events();
+            harness.just_layout();
+            // The hidden child is not added, and can't take focus
+            assert_eq!(harness.window().focus_chain(), &[first]);
+            assert!(harness.try_get_state(second).is_none());
+
+            ACTIVE.store(1, Ordering::Relaxed);
+            harness.force_update();
+            assert_eq!(harness.window().focus_chain(), &[second]);
+            harness.just_layout();
+            assert!(harness.get_state(second).layout_rect().height() > 0);
+
+            // Past the last child nothing is shown
+            ACTIVE.store(2, Ordering::Relaxed);
+            harness.force_update();
+            assert!(harness.window().focus_chain().is_empty());
+
+            ACTIVE.store(0, Ordering::Relaxed);
+            harness.force_update();
+            assert_eq!(harness.window().focus_chain(), &[first]);
+        });
+    }
+}
@@ -16,7 +16,7 @@
 ////use std::ops::{Deref, DerefMut};
 
 use super::prelude::*;
-use crate::{BoxedWidget, Counter, CounterType, WidgetType}; ////
+use crate::{theme, BoxedWidget, Counter, CounterType, WidgetType}; ////
 
 /// A unique identifier for a single [`Widget`].
 ///
@@ -170,6 +170,17 @@ pub trait Widget<T: Clone> { ////
     /// [`RenderContext`]: trait.RenderContext.html
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env);
 
+    /// Paint the focus highlight, after [`paint`], when the widget has the focus.
+    ///
+    /// By default this strokes a ring inside the widget's bounds. Widgets that
+    /// highlight the focus themselves can paint nothing instead.
+    ///
+    /// [`paint`]: #tymethod.paint
+    fn paint_focus_ring(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) { ////
+        let rect = ctx.size().to_rect();
+        ctx.stroke(rect, &theme::FOCUS_RING_COLOR.resolve(env), theme::FOCUS_RING_WIDTH.resolve(env));
+    }
+
     #[doc(hidden)]
     /// Get the identity of the widget; this is basically only implemented by
     /// `IdentityWrapper`. Widgets should not implement this on their own.
@@ -189,7 +200,15 @@ pub trait Widget<T: Clone> { ////
     fn to_type(self) -> WidgetType<T>; ////
 }
 
+/// Allocates the ids of `WidgetId::next`, below the reserved ids. ////
+static WIDGET_ID_COUNTER: Counter = Counter::new(WidgetId::FIRST_RESERVED); ////
+
 impl WidgetId {
+    /// The number of ids for `WidgetId::reserved`, at the top of the id range. ////
+    pub const RESERVED: CounterType = 32; ////
+    /// The lowest reserved id, which `WidgetId::next` never reaches. ////
+    const FIRST_RESERVED: CounterType = CounterType::max_value() - (WidgetId::RESERVED - 1); ////
+
     /// Allocate a new, unique `WidgetId`.
     ///
     /// All widgets are assigned ids automatically; you should only create
@@ -198,11 +217,38 @@ impl WidgetId {
     ///
     /// You must ensure that a given `WidgetId` is only ever used for one
     /// widget at a time.
+    ///
+    /// The lowest id that is not in use is returned, so ids that are freed
+    /// with [`free`] are reused. Panics if all ids are in use. ////
+    ///
+    /// [`free`]: #method.free
     pub fn next() -> WidgetId {
         ////use crate::shell::Counter;
-        static WIDGET_ID_COUNTER: Counter = Counter{}; ////
         ////static WIDGET_ID_COUNTER: Counter = Counter::new();
-        WidgetId(WIDGET_ID_COUNTER.next_nonzero())
+        WidgetId(WIDGET_ID_COUNTER.next()) ////
+        ////WidgetId(WIDGET_ID_COUNTER.next_nonzero())
+    }
+
+    /// Return the id for reuse by [`next`], once its widget has been dropped,
+    /// e.g. when a screen is rebuilt. Reserved ids are not affected. ////
+    ///
+    /// The [`WidgetPod`] of a widget calls this when it's sent
+    /// [`LifeCycle::WidgetRemoved`].
+    ///
+    /// [`next`]: #method.next
+    /// [`WidgetPod`]: struct.WidgetPod.html
+    /// [`LifeCycle::WidgetRemoved`]: enum.LifeCycle.html#variant.WidgetRemoved
+    pub fn free(self) { ////
+        if !self.is_reserved() {
+            WIDGET_ID_COUNTER.free(self.0);
+        }
+    }
+
+    /// Returns `true` if the id was created by [`reserved`]. ////
+    ///
+    /// [`reserved`]: #method.reserved
+    pub fn is_reserved(self) -> bool { ////
+        self.0 >= WidgetId::FIRST_RESERVED
     }
 
     /// Create a reserved `WidgetId`, suitable for reuse.
@@ -212,10 +258,13 @@ impl WidgetId {
     ///
     /// The actual inner representation of the returned `WidgetId` will not
     /// be the same as the raw value that is passed in; it will be
-    /// `u64::max_value() - raw`.
+    /// `CounterType::max_value() - raw`. `raw` must be less than `WidgetId::RESERVED`, ////
+    /// so reserved ids never collide with the ids from `next`. ////
+    ////`u64::max_value() - raw`.
     #[allow(unsafe_code)]
     pub const fn reserved(raw: CounterType) -> WidgetId { ////
     ////pub const fn reserved(raw: u16) -> WidgetId {
+        assert!(raw < WidgetId::RESERVED, "reserved id out of range"); ////
         let id = CounterType::max_value() - raw as CounterType; ////
         ////let id = u64::max_value() - raw as u64;
         // safety: by construction this can never be zero.
@@ -258,4 +307,41 @@ impl<T> Widget<T> for Box<dyn Widget<T>> {
         self.deref().type_name()
     }
 }
-*/ ////
\ No newline at end of file
+*/ ////
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_recycles_ids() {
+        let counter = Counter::new(4);
+        assert_eq!([counter.next(), counter.next(), counter.next()], [0, 1, 2]);
+
+        // Freed ids are reused, lowest first
+        counter.free(1);
+        counter.free(0);
+        assert!(!counter.is_used(1));
+        assert_eq!([counter.next(), counter.next(), counter.next()], [0, 1, 3]);
+        assert!(counter.is_used(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of ids")]
+    fn counter_runs_out() {
+        let counter = Counter::new(2);
+        for _ in 0..3 {
+            counter.next();
+        }
+    }
+
+    #[test]
+    fn reserved_ids_do_not_collide() {
+        let first = WidgetId::reserved(WidgetId::RESERVED - 1);
+        assert!(first.is_reserved() && WidgetId::reserved(0).is_reserved());
+        assert_eq!(WidgetId::reserved(0), WidgetId(CounterType::max_value()));
+
+        let id = WidgetId::next();
+        assert!(!id.is_reserved() && id.0 < first.0);
+        id.free();
+    }
+}
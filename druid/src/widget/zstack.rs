@@ -0,0 +1,212 @@
+// Copyright 2020 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! A widget that stacks its children on top of each other.
+
+use crate::{
+    BoxConstraints, BoxedWidget, Data, Env, Event, EventCtx, InternalEvent, LayoutCtx, LifeCycle,
+    LifeCycleCtx, PaintCtx, Point, Rect, Size, UnitPoint, UpdateCtx, Vec, Widget, WidgetId,
+    WidgetPod, WidgetType,
+};
+
+/// A container that lays out its children in the same rect, like layers.
+///
+/// Each child is aligned within the stack by its own `UnitPoint`, and layers
+/// with a larger z-index are painted on top and receive touch events first.
+/// Children added with the same z-index are stacked in the order they were added.
+#[derive(Clone)]
+pub struct ZStack<T> {
+    id: WidgetId,
+    children: Vec<ZChild<T>>,
+}
+
+#[derive(Clone)]
+struct ZChild<T> {
+    widget: WidgetPod<T, BoxedWidget<T>>,
+    alignment: UnitPoint,
+    z_index: u32,
+}
+
+impl<T: Data + Clone> ZStack<T> {
+    /// Create a new, empty stack.
+    pub fn new() -> Self {
+        ZStack {
+            id: WidgetId::next(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Builder-style method to add a centered child above the children added so far.
+    pub fn with_child(self, child: impl Widget<T> + 'static + Clone) -> Self {
+        self.with_aligned_child(child, UnitPoint::CENTER)
+    }
+
+    /// Builder-style method to add a child above the children added so far,
+    /// aligned within the stack.
+    pub fn with_aligned_child(self, child: impl Widget<T> + 'static + Clone, alignment: UnitPoint) -> Self {
+        let z_index = self.children.iter().map(|child| child.z_index).max().unwrap_or(0);
+        self.with_layer(child, alignment, z_index)
+    }
+
+    /// Builder-style method to add a child aligned within the stack, at the given z-index.
+    ///
+    /// Layers with a larger `z_index` are painted above layers with a smaller one.
+    pub fn with_layer(mut self, child: impl Widget<T> + 'static + Clone, alignment: UnitPoint, z_index: u32) -> Self {
+        let child = ZChild {
+            widget: WidgetPod::new(BoxedWidget::new(child)),
+            alignment,
+            z_index,
+        };
+        if self.children.push(child).is_err() {
+            log::warn!("ZStack has too many children");
+        }
+        self
+    }
+
+    /// The index of the topmost child whose layout rect contains `pos`.
+    fn child_at(&self, pos: Point) -> Option<usize> {
+        topmost(self.children.iter().map(|child| (child.widget.layout_rect(), child.z_index)), pos)
+    }
+}
+
+/// The index of the topmost layer containing `pos`, given the rect and z-index of each layer.
+///
+/// Of the layers with the same z-index, the last one is on top.
+fn topmost(layers: impl Iterator<Item = (Rect, u32)>, pos: Point) -> Option<usize> {
+    layers
+        .enumerate()
+        .filter(|(_, (rect, _))| rect.winding(pos) != 0)
+        .max_by_key(|(index, (_, z_index))| (*z_index, *index))
+        .map(|(index, _)| index)
+}
+
+impl<T: Data + Clone> Default for ZStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Data> Widget<T> for ZStack<T> {
+    fn id(&self) -> Option<WidgetId> { Some(self.id) }
+
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let pos = match event {
+            Event::MouseDown(mouse) | Event::MouseUp(mouse) | Event::MouseMove(mouse) => mouse.pos,
+            _ => {
+                for child in &mut self.children {
+                    child.widget.event(ctx, event, data, env);
+                }
+                return;
+            }
+        };
+        // Only the topmost layer under the touch, and any layer tracking a touch
+        // that started on it, get the event. Layers below are covered, so they leave.
+        let top = self.child_at(pos);
+        for (index, child) in self.children.iter_mut().enumerate() {
+            if Some(index) == top || child.widget.has_active() {
+                child.widget.event(ctx, event, data, env);
+            } else {
+                child.widget.event(ctx, &Event::Internal(InternalEvent::MouseLeave), data, env);
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        for child in &mut self.children {
+            child.widget.update(ctx, data, env);
+        }
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        bc.debug_check("ZStack");
+
+        let loosened = bc.loosen();
+        let mut sizes: Vec<Size> = Vec::new();
+        let mut my_size = Size::ZERO;
+        for child in &mut self.children {
+            let size = child.widget.layout(ctx, &loosened, data, env);
+            my_size.width = my_size.width.max(size.width);
+            my_size.height = my_size.height.max(size.height);
+            sizes.push(size).ok();
+        }
+        let my_size = bc.constrain(my_size);
+
+        for (child, size) in self.children.iter_mut().zip(sizes) {
+            let extra_width = my_size.width.saturating_sub(size.width);
+            let extra_height = my_size.height.saturating_sub(size.height);
+            let origin = child.alignment.resolve(Rect::new(0, 0, extra_width, extra_height));
+            child.widget.set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, size));
+        }
+        my_size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        //// TODO: Render with LVGL
+        /*
+        lv_obj_align(child, stack, LV_ALIGN_CENTER, x, y);              //  For each child, by its alignment
+        */
+        for (index, child) in self.children.iter().enumerate() {
+            ctx.paint_with_z_index(child.z_index, index);
+        }
+        for op in ctx.take_z_ops() {
+            self.children[op.child].widget.paint(ctx, data, env);
+        }
+    }
+
+    fn to_type(self) -> WidgetType<T> {
+        WidgetType::ZStack(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::helpers::paint_ctx;
+    use crate::ZOrderPaintOp;
+
+    #[test]
+    fn hit_test_from_top() {
+        let layers = [
+            (Rect::new(0, 0, 100, 100), 0),
+            (Rect::new(20, 20, 60, 60), 2),
+            (Rect::new(40, 40, 80, 80), 1),
+            (Rect::new(0, 0, 30, 30), 0),
+        ];
+        let hit = |x, y| topmost(layers.iter().copied(), Point::new(x, y));
+        assert_eq!(hit(50, 50), Some(1));  // Highest z-index wins
+        assert_eq!(hit(70, 70), Some(2));
+        assert_eq!(hit(10, 10), Some(3));  // Same z-index, added later
+        assert_eq!(hit(90, 10), Some(0));
+        assert_eq!(hit(120, 10), None);
+    }
+
+    #[test]
+    fn z_ops_in_paint_order() {
+        let mut ctx = paint_ctx(Size::new(240, 240));
+        ctx.paint_with_z_index(1, 0);
+        ctx.paint_with_z_index(0, 1);
+        ctx.paint_with_z_index(1, 2);
+        ctx.paint_with_z_index(0, 3);
+        let order: Vec<usize> = ctx.take_z_ops().iter().map(|op: &ZOrderPaintOp| op.child).collect();
+        assert_eq!(&order[..], &[1, 3, 0, 2]);
+        assert!(ctx.z_ops.is_empty());
+    }
+}
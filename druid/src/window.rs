@@ -0,0 +1,802 @@
+// Copyright 2019 The Druid Authors.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Management of multiple windows.
+
+////use std::collections::HashMap;
+////use std::mem;
+
+// Automatically defaults to std::time::Instant on non Wasm platforms
+////use instant::Instant;
+
+use crate::{Point, Rect, Size}; ////
+////use crate::kurbo::{Point, Rect, Size};
+use crate::piet::Piet; ////
+////use crate::piet::{Piet, RenderContext};
+////use crate::shell::{Counter, Cursor, WindowHandle};
+
+use crate::ContextState; ////
+////use crate::contexts::ContextState;
+use crate::core::{FocusChange, Timer, WidgetState}; ////
+////use crate::core::{CommandQueue, FocusChange, WidgetState};
+////use crate::util::ExtendDrain;
+////use crate::widget::LabelText;
+////use crate::win_handler::RUN_COMMANDS_TOKEN;
+use crate::{
+    BoxConstraints, Data, Env, Event, EventCtx, InternalEvent, InternalLifeCycle, ////
+    ////BoxConstraints, Command, Data, Env, Event, EventCtx, InternalEvent, InternalLifeCycle,
+    LayoutCtx, LifeCycle, LifeCycleCtx, PaintCtx, TimerToken, UpdateCtx, Widget, ////
+    ////LayoutCtx, LifeCycle, LifeCycleCtx, MenuDesc, PaintCtx, TimerToken, UpdateCtx, Widget,
+    WidgetId, WidgetPod, WindowId, ////
+    ////WidgetId, WidgetPod, WindowDesc,
+};
+use crate::{ButtonAction, BoxedWidget, Region, Vec, CONTEXT_STATE}; ////
+use core::time::Duration; ////
+
+/* //// Defined in lib.rs
+/// A unique identifier for a window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WindowId(u64);
+*/ ////
+
+/// Per-window state not owned by user code.
+///
+/// The window routes events to the root widget, and moves the focus as
+/// requested by the widgets, or by the hardware buttons when the focused
+/// widget doesn't handle them. ////
+pub struct Window<T, W = BoxedWidget<T>> { ////
+////pub struct Window<T> {
+    pub(crate) id: WindowId,
+    pub(crate) root: WidgetPod<T, W>, ////
+    ////pub(crate) root: WidgetPod<T, Box<dyn Widget<T>>>,
+    ////pub(crate) title: LabelText<T>,
+    size: Size,
+    ////pub(crate) menu: Option<MenuDesc<T>>,
+    ////pub(crate) context_menu: Option<MenuDesc<T>>,
+    ////pub(crate) last_anim: Option<Instant>,
+    pub(crate) last_mouse_pos: Option<Point>,
+    pub(crate) focus: Option<WidgetId>,
+    ////pub(crate) handle: WindowHandle,
+    pub(crate) timers: Vec<Timer>, ////
+    ////pub(crate) timers: HashMap<TimerToken, WidgetId>,
+    // delegate?
+}
+
+impl<T: Data, W: Widget<T> + Clone> Window<T, W> { ////
+////impl<T> Window<T> {
+    pub(crate) fn new(id: WindowId, root: W) -> Window<T, W> { ////
+    ////pub(crate) fn new(id: WindowId, handle: WindowHandle, desc: WindowDesc<T>) -> Window<T> {
+        Window {
+            id,
+            root: WidgetPod::new(root), ////
+            ////root: WidgetPod::new(desc.root),
+            size: Size::ZERO,
+            ////title: desc.title,
+            ////menu: desc.menu,
+            ////context_menu: None,
+            ////last_anim: None,
+            last_mouse_pos: None,
+            focus: None,
+            ////handle,
+            timers: Vec::new(), ////
+            ////timers: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Data, W: Widget<T> + Clone> Window<T, W> { ////
+////impl<T: Data> Window<T> {
+    /* //// No animation timing yet
+    /// `true` iff any child requested an animation frame during the last `AnimFrame` event.
+    pub(crate) fn wants_animation_frame(&self) -> bool {
+        self.last_anim.is_some()
+    }
+    */ ////
+
+    pub(crate) fn focus_chain(&self) -> &[WidgetId] {
+        &self.root.state().focus_chain
+    }
+
+    /// Returns `true` if the provided widget may be in this window,
+    /// but it may also be a false positive.
+    /// However when this returns `false` the widget is definitely not in this window.
+    pub(crate) fn may_contain_widget(&self, widget_id: WidgetId) -> bool {
+        // The bloom filter we're checking can return false positives.
+        self.root.state().children.may_contain(&widget_id)
+    }
+
+    /* //// No menus
+    pub(crate) fn set_menu(&mut self, mut menu: MenuDesc<T>, data: &T, env: &Env) {
+        let platform_menu = menu.build_window_menu(data, env);
+        self.handle.set_menu(platform_menu);
+        self.menu = Some(menu);
+    }
+
+    pub(crate) fn show_context_menu(
+        &mut self,
+        mut menu: MenuDesc<T>,
+        point: Point,
+        data: &T,
+        env: &Env,
+    ) {
+        let platform_menu = menu.build_popup_menu(data, env);
+        self.handle.show_context_menu(platform_menu, point);
+        self.context_menu = Some(menu);
+    }
+
+    /// On macos we need to update the global application menu to be the menu
+    /// for the current window.
+    #[cfg(target_os = "macos")]
+    pub(crate) fn macos_update_app_menu(&mut self, data: &T, env: &Env) {
+        if let Some(menu) = self.menu.as_mut().map(|m| m.build_window_menu(data, env)) {
+            self.handle.set_menu(menu);
+        }
+    }
+    */ ////
+
+    fn post_event_processing(
+        &mut self,
+        widget_state: &mut WidgetState,
+        ////queue: &mut CommandQueue,
+        data: &T,
+        env: &Env,
+        ////process_commands: bool,
+    ) {
+        // If children are changed during the handling of an event,
+        // we need to send RouteWidgetAdded now, so that they are ready for update/layout.
+        if widget_state.children_changed {
+            self.lifecycle(
+                ////queue,
+                &LifeCycle::Internal(InternalLifeCycle::RouteWidgetAdded),
+                data,
+                env,
+                ////false,
+            );
+        }
+        // Add all the requested timers to the window's timers map.
+        self.add_timers(widget_state); ////
+        ////self.timers.extend_drain(&mut widget_state.timers);
+        /* //// No commands yet
+        // If there are any commands and they should be processed
+        if process_commands && !queue.is_empty() {
+            // Ask the handler to call us back on idle
+            // so we can process them in a new event/update pass.
+            if let Some(mut handle) = self.handle.get_idle_handle() {
+                handle.schedule_idle(RUN_COMMANDS_TOKEN);
+            } else {
+                log::error!("failed to get idle handle");
+            }
+        }
+        */ ////
+    }
+
+    pub(crate) fn event(
+        &mut self,
+        ////queue: &mut CommandQueue,
+        event: Event,
+        data: &mut T,
+        env: &Env,
+    ) -> bool {
+        match &event {
+            Event::WindowSize(size) => self.size = *size,
+            Event::MouseDown(e) | Event::MouseUp(e) | Event::MouseMove(e) | Event::Wheel(e) => {
+                self.last_mouse_pos = Some(e.pos)
+            }
+            Event::Internal(InternalEvent::MouseLeave) => self.last_mouse_pos = None,
+            _ => (),
+        }
+
+        /* //// No cursor yet
+        let mut cursor = match event {
+            Event::MouseMove(..) => Some(Cursor::Arrow),
+            _ => None,
+        };
+        */ ////
+
+        let event = match event {
+            Event::Timer(token) => {
+                if let Some(timer) = self.timers.iter().find(|timer| timer.token == token) { ////
+                    Event::Internal(InternalEvent::RouteTimer(token, timer.widget)) ////
+                ////if let Some(widget_id) = self.timers.get(&token) {
+                    ////Event::Internal(InternalEvent::RouteTimer(token, *widget_id))
+                } else {
+                    log::error!("No widget found for timer {:?}", token);
+                    return false;
+                }
+            }
+            other => other,
+        };
+
+        if let Event::WindowConnected = event {
+            self.lifecycle(
+                ////queue,
+                &LifeCycle::Internal(InternalLifeCycle::RouteWidgetAdded),
+                data,
+                env,
+                ////false,
+            );
+        }
+
+        ////let mut widget_state = WidgetState::new(self.root.id());
+        let (is_handled, mut widget_state) = { ////
+        ////let is_handled = {
+            ////let mut state = ContextState::new::<T>(queue, &self.handle, self.id, self.focus);
+            let mut ctx = EventCtx {
+                ////cursor: &mut cursor,
+                state: ContextState(), ////
+                ////state: &mut state,
+                widget_state: WidgetState::new(self.root.id()), ////
+                ////widget_state: &mut widget_state,
+                is_handled: false,
+                is_root: true,
+            };
+
+            self.root.event(&mut ctx, &event, data, env);
+            (ctx.is_handled, ctx.widget_state) ////
+            ////ctx.is_handled
+        };
+
+        // Clean up the timer token and do it immediately after the event handling
+        // because the token may be reused and re-added in a lifecycle pass below.
+        if let Event::Internal(InternalEvent::RouteTimer(token, _)) = event {
+            if let Some(index) = self.timers.iter().position(|timer| timer.token == token) { ////
+                self.timers.swap_remove(index);
+            } ////
+            ////self.timers.remove(&token);
+        }
+
+        // The buttons move the focus, unless the focused widget handled them ////
+        if let (Event::Button(button), false) = (&event, is_handled) {
+            match button.action {
+                ButtonAction::Release { long: false } => widget_state.request_focus = Some(FocusChange::Next),
+                ButtonAction::LongPress | ButtonAction::Repeat => widget_state.request_focus = Some(FocusChange::Previous),
+                _ => (),
+            }
+        }
+
+        if let Some(focus_req) = widget_state.request_focus.take() {
+            let old = self.focus;
+            let new = self.widget_for_focus_request(focus_req);
+            // Only send RouteFocusChanged in case there's actual change
+            if old != new {
+                let event = LifeCycle::Internal(InternalLifeCycle::RouteFocusChanged { old, new });
+                self.lifecycle(&event, data, env); ////
+                ////self.lifecycle(queue, &event, data, env, false);
+                self.focus = new;
+            }
+        }
+
+        /* //// No cursor yet
+        if let Some(cursor) = cursor {
+            self.handle.set_cursor(&cursor);
+        }
+        */ ////
+
+        self.post_event_processing(&mut widget_state, data, env); ////
+        ////self.post_event_processing(&mut widget_state, queue, data, env, false);
+
+        is_handled
+    }
+
+    pub(crate) fn lifecycle(
+        &mut self,
+        ////queue: &mut CommandQueue,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+        ////process_commands: bool,
+    ) {
+        /* //// The platform sends `AnimFrame` with the elapsed time
+        // for AnimFrame, the event the window receives doesn't have the correct
+        // elapsed time; we calculate it here.
+        let now = Instant::now();
+        let substitute_event = if let LifeCycle::AnimFrame(_) = event {
+            // TODO: this calculation uses wall-clock time of the paint call, which
+            // potentially has jitter.
+            //
+            // See https://github.com/linebender/druid/issues/85 for discussion.
+            let last = self.last_anim.take();
+            let elapsed_ns = last.map(|t| now.duration_since(t).as_nanos()).unwrap_or(0) as u64;
+            Some(LifeCycle::AnimFrame(elapsed_ns))
+        } else {
+            None
+        };
+        */ ////
+
+        ////let mut widget_state = WidgetState::new(self.root.id());
+        ////let mut state = ContextState::new::<T>(queue, &self.handle, self.id, self.focus);
+        let mut ctx = LifeCycleCtx {
+            state: ContextState(), ////
+            ////state: &mut state,
+            widget_state: WidgetState::new(self.root.id()), ////
+            ////widget_state: &mut widget_state,
+        };
+        ////let event = substitute_event.as_ref().unwrap_or(event);
+        self.root.lifecycle(&mut ctx, event, data, env);
+
+        /* ////
+        if substitute_event.is_some() && ctx.widget_state.request_anim {
+            self.last_anim = Some(now);
+        }
+        */ ////
+
+        let mut widget_state = ctx.widget_state; ////
+        self.add_timers(&mut widget_state); ////
+        if widget_state.children_changed && self.root.state().children_changed { ////
+            // Children are still to be added, after this event
+            self.post_event_processing(&mut widget_state, data, env); ////
+        }
+        ////self.post_event_processing(&mut widget_state, queue, data, env, process_commands);
+    }
+
+    /// Count down the timers by `elapsed`, and send `Event::Timer` to the widgets
+    /// whose timers are due. The platform calls this as time passes, e.g. on
+    /// each tick of its event loop. ////
+    pub(crate) fn advance_timers(&mut self, elapsed: Duration, data: &mut T, env: &Env) { ////
+        let mut due: Vec<TimerToken> = Vec::new();
+        for timer in self.timers.iter_mut() {
+            timer.deadline = timer.deadline.checked_sub(elapsed).unwrap_or_default();
+            if timer.deadline == Duration::default() {
+                due.push(timer.token).ok();
+            }
+        }
+        // Timers requested while handling these fire on a later call
+        for token in due {
+            self.event(Event::Timer(token), data, env);
+        }
+    }
+
+    /// Move the timers requested by the widgets to the window. ////
+    fn add_timers(&mut self, widget_state: &mut WidgetState) { ////
+        for timer in widget_state.timers.iter() {
+            if self.timers.push(*timer).is_err() {
+                log::warn!("too many timers, {:?} of {:?} won't fire", timer.token, timer.widget);
+            }
+        }
+        // heapless 0.5 `clear` indexes past the end when dropping the items
+        widget_state.timers = Vec::new();
+    }
+
+    /// Tear down the widget tree before the window is dropped, e.g. when its
+    /// screen is rebuilt, so that the `WidgetId`s and static `Widgets` of the
+    /// widgets can be reused. ////
+    pub(crate) fn remove(mut self, data: &T, env: &Env) { ////
+        let mut ctx = LifeCycleCtx {
+            state: ContextState(),
+            widget_state: WidgetState::new(self.root.id()),
+        };
+        // Nothing is left to be added afterwards
+        self.root.lifecycle(&mut ctx, &LifeCycle::WidgetRemoved, data, env);
+    }
+
+    pub(crate) fn update(&mut self, data: &T, env: &Env) { ////
+    ////pub(crate) fn update(&mut self, queue: &mut CommandQueue, data: &T, env: &Env) {
+        ////self.update_title(data, env);
+
+        ////let mut widget_state = WidgetState::new(self.root.id());
+        ////let mut state = ContextState::new::<T>(queue, &self.handle, self.id, self.focus);
+        let mut update_ctx = UpdateCtx {
+            widget_state: WidgetState::new(self.root.id()), ////
+            ////widget_state: &mut widget_state,
+            state: ContextState(), ////
+            ////state: &mut state,
+        };
+
+        self.root.update(&mut update_ctx, data, env);
+        self.post_event_processing(&mut update_ctx.widget_state, data, env); ////
+        ////self.post_event_processing(&mut widget_state, queue, data, env, false);
+    }
+
+    /* //// No window handle
+    pub(crate) fn invalidate_and_finalize(&mut self) {
+        if self.root.state().needs_layout {
+            self.handle.invalidate();
+        } else {
+            let invalid = &self.root.state().invalid;
+            if !invalid.is_empty() {
+                self.handle.invalidate_rect(invalid.to_rect());
+            }
+        }
+    }
+    */ ////
+
+    /// Do all the stuff we do in response to a paint call from the system:
+    /// layout, send an `AnimFrame` event, and then actually paint.
+    pub(crate) fn do_paint(
+        &mut self,
+        piet: Piet, ////
+        ////piet: &mut Piet,
+        invalid_rect: Rect,
+        ////queue: &mut CommandQueue,
+        data: &T,
+        env: &Env,
+    ) {
+        // FIXME: only do AnimFrame if root has requested_anim?
+        self.lifecycle(&LifeCycle::AnimFrame(0), data, env); ////
+        ////self.lifecycle(queue, &LifeCycle::AnimFrame(0), data, env, true);
+
+        if self.root.state().needs_layout {
+            self.layout(data, env); ////
+            ////self.layout(queue, data, env);
+        }
+
+        /* //// The screen is cleared by the display driver
+        piet.fill(
+            invalid_rect,
+            &env.get(crate::theme::WINDOW_BACKGROUND_COLOR),
+        );
+        */ ////
+        self.paint(piet, invalid_rect, data, env); ////
+        ////self.paint(piet, invalid_rect, queue, data, env);
+    }
+
+    fn layout(&mut self, data: &T, env: &Env) { ////
+    ////fn layout(&mut self, queue: &mut CommandQueue, data: &T, env: &Env) {
+        ////let mut widget_state = WidgetState::new(self.root.id());
+        ////let mut state = ContextState::new::<T>(queue, &self.handle, self.id, self.focus);
+        let mut layout_ctx = LayoutCtx {
+            state: ContextState(), ////
+            ////state: &mut state,
+            widget_state: WidgetState::new(self.root.id()), ////
+            ////widget_state: &mut widget_state,
+            mouse_pos: self.last_mouse_pos,
+        };
+        let bc = BoxConstraints::tight(self.size);
+        let size = self.root.layout(&mut layout_ctx, &bc, data, env);
+        self.root.set_layout_rect(
+            &mut layout_ctx,
+            data,
+            env,
+            Rect::from_origin_size(Point::ORIGIN, size),
+        );
+        self.post_event_processing(&mut layout_ctx.widget_state, data, env); ////
+        ////self.post_event_processing(&mut widget_state, queue, data, env, true);
+    }
+
+    /// only expose `layout` for testing; normally it is called as part of `do_paint`
+    #[cfg(test)]
+    pub(crate) fn just_layout(&mut self, data: &T, env: &Env) { ////
+    ////#[cfg(not(target_arch = "wasm32"))]
+    ////pub(crate) fn just_layout(&mut self, queue: &mut CommandQueue, data: &T, env: &Env) {
+        self.layout(data, env) ////
+        ////self.layout(queue, data, env)
+    }
+
+    fn paint(
+        &mut self,
+        piet: Piet, ////
+        ////piet: &mut Piet,
+        invalid_rect: Rect,
+        ////queue: &mut CommandQueue,
+        data: &T,
+        env: &Env,
+    ) {
+        // we need to destructure to get around some lifetime issues,
+        // just like in the good old days!
+        ////let id = self.id;
+        ////let focus = self.focus;
+        let Window { root, .. } = self; ////
+        ////let Window { root, handle, .. } = self;
+
+        let widget_state = WidgetState::new(root.id());
+        ////let mut state = ContextState::new::<T>(queue, handle, id, focus);
+        let mut ctx = PaintCtx {
+            render_ctx: piet,
+            state: &CONTEXT_STATE, ////
+            ////state: &mut state,
+            widget_state, ////
+            ////widget_state: &widget_state,
+            z_ops: Vec::new(),
+            region: Region::from(invalid_rect), ////
+            ////region: invalid_rect.into(),
+            depth: 0,
+        };
+
+        ctx.with_child_ctx(invalid_rect, |ctx| root.paint_raw(ctx, data, env));
+        /* //// Z-order paint ops refer to the children of containers, which paint them
+        let mut z_ops = mem::take(&mut ctx.z_ops);
+        z_ops.sort_by_key(|k| k.z_index);
+
+        for z_op in z_ops.into_iter() {
+            ctx.with_child_ctx(invalid_rect, |ctx| {
+                ctx.with_save(|ctx| {
+                    ctx.render_ctx.transform(z_op.transform);
+                    (z_op.paint_func)(ctx);
+                });
+            });
+        }
+        */ ////
+    }
+
+    /* //// No title or menus
+    pub(crate) fn update_title(&mut self, data: &T, env: &Env) {
+        if self.title.resolve(data, env) {
+            self.handle.set_title(self.title.display_text());
+        }
+    }
+
+    pub(crate) fn get_menu_cmd(&self, cmd_id: u32) -> Option<Command> {
+        self.context_menu
+            .as_ref()
+            .and_then(|m| m.command_for_id(cmd_id))
+            .or_else(|| self.menu.as_ref().and_then(|m| m.command_for_id(cmd_id)))
+    }
+    */ ////
+
+    fn widget_for_focus_request(&self, focus: FocusChange) -> Option<WidgetId> {
+        match focus {
+            FocusChange::Resign => None,
+            FocusChange::Focus(id) => Some(id),
+            FocusChange::Next => self.widget_from_focus_chain(true),
+            FocusChange::Previous => self.widget_from_focus_chain(false),
+        }
+    }
+
+    fn widget_from_focus_chain(&self, forward: bool) -> Option<WidgetId> {
+        // Without a focused widget, the buttons start from either end of the chain ////
+        let focus = self.focus; ////
+        ////self.focus.and_then(|focus| {
+            self.focus_chain()
+                .iter()
+                // Find where the focused widget is in the focus chain
+                .position(|id| Some(*id) == focus) ////
+                ////.position(|id| id == &focus)
+                .map(|idx| {
+                    // Return the id that's next to it in the focus chain
+                    let len = self.focus_chain().len();
+                    let new_idx = if forward {
+                        (idx + 1) % len
+                    } else {
+                        (idx + len - 1) % len
+                    };
+                    self.focus_chain()[new_idx]
+                })
+                .or_else(|| {
+                    // If the currently focused widget isn't in the focus chain,
+                    // then we'll just return the first/last entry of the chain, if any.
+                    if forward {
+                        self.focus_chain().first().copied()
+                    } else {
+                        self.focus_chain().last().copied()
+                    }
+                })
+        ////})
+    }
+}
+
+/* //// Defined in lib.rs
+impl WindowId {
+    /// Allocate a new, unique window id.
+    pub fn next() -> WindowId {
+        static WINDOW_COUNTER: Counter = Counter::new();
+        WindowId(WINDOW_COUNTER.next())
+    }
+}
+*/ ////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::WidgetType;
+    use crate::{theme, ButtonEvent, Color, CounterType};
+
+    /// A widget that may take the focus, and optionally handles the buttons.
+    #[derive(Clone)]
+    struct Focusable {
+        id: WidgetId,
+        focusable: bool,
+        focused: bool,
+        handles_buttons: bool,
+    }
+
+    impl Widget<u32> for Focusable {
+        fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut u32, _env: &Env) {
+            if let (Event::Button(_), true) = (event, self.handles_buttons) {
+                *data += 1;
+                ctx.set_handled();
+            }
+        }
+        fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, _data: &u32, _env: &Env) {
+            match event {
+                LifeCycle::WidgetAdded if self.focusable => ctx.register_for_focus(),
+                LifeCycle::FocusChanged(focused) => self.focused = *focused,
+                _ => (),
+            }
+        }
+        fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &u32, _data: &u32, _env: &Env) {}
+        fn layout(&mut self, _ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &u32, _env: &Env) -> Size {
+            bc.min()
+        }
+        fn paint(&mut self, _ctx: &mut PaintCtx, _data: &u32, _env: &Env) {}
+        fn id(&self) -> Option<WidgetId> { Some(self.id) }
+        fn to_type(self) -> WidgetType<u32> { WidgetType::None }
+    }
+
+    /// Widgets in a row.
+    #[derive(Clone)]
+    struct Row {
+        id: WidgetId,
+        children: heapless::Vec<WidgetPod<u32, Focusable>, heapless::consts::U16>,
+    }
+
+    impl Widget<u32> for Row {
+        fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut u32, env: &Env) {
+            for child in self.children.iter_mut() {
+                child.event(ctx, event, data, env);
+            }
+        }
+        fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &u32, env: &Env) {
+            for child in self.children.iter_mut() {
+                child.lifecycle(ctx, event, data, env);
+            }
+        }
+        fn update(&mut self, _ctx: &mut UpdateCtx, _old_data: &u32, _data: &u32, _env: &Env) {}
+        fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &u32, env: &Env) -> Size {
+            let size = bc.max();
+            let width = size.width / self.children.len() as u8;
+            for (i, child) in self.children.iter_mut().enumerate() {
+                let child_size = Size::new(width, size.height);
+                child.layout(ctx, &BoxConstraints::tight(child_size), data, env);
+                let origin = Point::new(width * i as u8, 0);
+                child.set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, child_size));
+            }
+            size
+        }
+        fn paint(&mut self, ctx: &mut PaintCtx, data: &u32, env: &Env) {
+            for child in self.children.iter_mut() {
+                child.paint(ctx, data, env);
+            }
+        }
+        fn id(&self) -> Option<WidgetId> { Some(self.id) }
+        fn to_type(self) -> WidgetType<u32> { WidgetType::None }
+    }
+
+    /// A window with three focusable widgets in a row.
+    fn window(handles_buttons: bool) -> Window<u32, Row> {
+        window_of(3, handles_buttons, |_| true)
+    }
+
+    /// A window with `count` widgets in a row, numbered from 1.
+    fn window_of(count: CounterType, handles_buttons: bool, focusable: fn(CounterType) -> bool) -> Window<u32, Row> {
+        let mut row = Row {
+            id: WidgetId::reserved(0),
+            children: heapless::Vec::new(),
+        };
+        for raw in 1..=count {
+            let child = WidgetPod::new(Focusable {
+                id: WidgetId::reserved(raw),
+                focusable: focusable(raw),
+                focused: false,
+                handles_buttons,
+            });
+            row.children.push(child).ok().unwrap();
+        }
+        let mut window = Window::new(WindowId::next(), row);
+        window.event(Event::WindowConnected, &mut 0, &theme::init());
+        window
+    }
+
+    fn button(window: &mut Window<u32, Row>, action: ButtonAction, data: &mut u32) -> bool {
+        let event = Event::Button(ButtonEvent { button: 0, action });
+        window.event(event, data, &theme::init())
+    }
+
+    fn focused(window: &Window<u32, Row>) -> [bool; 3] {
+        let children = &window.root.widget().children;
+        [children[0].widget().focused, children[1].widget().focused, children[2].widget().focused]
+    }
+
+    #[test]
+    fn buttons_move_focus() {
+        let mut window = window(false);
+        let data = &mut 0;
+        assert_eq!(window.focus_chain(), &[WidgetId::reserved(1), WidgetId::reserved(2), WidgetId::reserved(3)]);
+        assert_eq!(window.focus, None);
+
+        // A short press moves to the next widget, starting from the first
+        button(&mut window, ButtonAction::Press, data);
+        assert_eq!(window.focus, None);
+        button(&mut window, ButtonAction::Release { long: false }, data);
+        assert_eq!(window.focus, Some(WidgetId::reserved(1)));
+        assert_eq!(focused(&window), [true, false, false]);
+        button(&mut window, ButtonAction::Release { long: false }, data);
+        assert_eq!(focused(&window), [false, true, false]);
+
+        // Holding the button moves back, wrapping around
+        button(&mut window, ButtonAction::LongPress, data);
+        assert_eq!(focused(&window), [true, false, false]);
+        button(&mut window, ButtonAction::Repeat, data);
+        assert_eq!(focused(&window), [false, false, true]);
+        button(&mut window, ButtonAction::Release { long: true }, data);
+        assert_eq!(window.focus, Some(WidgetId::reserved(3)));
+
+        button(&mut window, ButtonAction::Release { long: false }, data);
+        assert_eq!(focused(&window), [true, false, false]);
+    }
+
+    #[test]
+    fn focused_widget_handles_buttons() {
+        let mut window = window(true);
+        let data = &mut 0;
+
+        // Without focus, no widget gets the buttons
+        assert!(!button(&mut window, ButtonAction::Release { long: false }, data));
+        assert_eq!(window.focus, Some(WidgetId::reserved(1)));
+        assert_eq!(*data, 0);
+
+        // The focused widget keeps the focus by handling the buttons
+        assert!(button(&mut window, ButtonAction::Release { long: false }, data));
+        assert!(button(&mut window, ButtonAction::LongPress, data));
+        assert_eq!(window.focus, Some(WidgetId::reserved(1)));
+        assert_eq!(*data, 2);
+    }
+
+    #[test]
+    fn focus_reaches_widgets_in_large_trees() {
+        // Only the last of 12 widgets takes the focus
+        let mut window = window_of(12, false, |raw| raw == 12);
+        for raw in 1..=12 {
+            assert!(window.may_contain_widget(WidgetId::reserved(raw)));
+        }
+        assert_eq!(window.focus_chain(), &[WidgetId::reserved(12)]);
+
+        button(&mut window, ButtonAction::Release { long: false }, &mut 0);
+        assert_eq!(window.focus, Some(WidgetId::reserved(12)));
+        assert!(window.root.widget().children[11].widget().focused);
+    }
+
+    #[test]
+    fn focus_chain_overflow_is_dropped() {
+        // 12 focusable widgets don't fit in the focus chain, the last ones are left out
+        let mut window = window_of(12, false, |_| true);
+        let chain: heapless::Vec<WidgetId, heapless::consts::U16> = (1..=10).map(WidgetId::reserved).collect();
+        assert_eq!(window.focus_chain(), &chain[..]);
+
+        // Moving back from the first widget wraps around to the last one that fits
+        button(&mut window, ButtonAction::Release { long: false }, &mut 0);
+        assert_eq!(window.focus, Some(WidgetId::reserved(1)));
+        button(&mut window, ButtonAction::LongPress, &mut 0);
+        assert_eq!(window.focus, Some(WidgetId::reserved(10)));
+    }
+
+    /// Bounds of the pixels painted in the focus ring colour.
+    static mut RING: Option<Rect> = None;
+
+    fn draw(rect: Rect, color: Color) {
+        if color == theme::FOCUS_RING_COLOR.resolve(&theme::init()) {
+            unsafe { RING = Some(RING.map_or(rect, |ring| ring.union(rect))); }
+        }
+    }
+
+    #[test]
+    fn focus_ring_is_painted() {
+        let mut window = window(false);
+        let data = &mut 0;
+        let size = Size::new(30, 10);
+        window.event(Event::WindowSize(size), data, &theme::init());
+        let mut paint = |window: &mut Window<u32, Row>| {
+            unsafe { RING = None; }
+            let piet = Piet::with_display(size, Some(draw));
+            window.do_paint(piet, size.to_rect(), data, &theme::init());
+            unsafe { RING }
+        };
+        assert_eq!(paint(&mut window), None);
+
+        // The ring follows the focus
+        button(&mut window, ButtonAction::Release { long: false }, &mut 0);
+        assert_eq!(paint(&mut window), Some(Rect::new(0, 0, 10, 10)));
+        button(&mut window, ButtonAction::Release { long: false }, &mut 0);
+        assert_eq!(paint(&mut window), Some(Rect::new(10, 0, 20, 10)));
+    }
+}